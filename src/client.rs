@@ -0,0 +1,2727 @@
+// Off-chain helpers for CLI tools and integrators interacting with the fixed supply token program
+
+use solana_program::{
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::Message,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+#[cfg(feature = "rpc")]
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "rpc")]
+use borsh::BorshDeserialize;
+#[cfg(feature = "rpc")]
+use solana_client::{client_error::ClientError, nonblocking::rpc_client::RpcClient};
+#[cfg(feature = "rpc")]
+use solana_sdk::{
+    address_lookup_table::{self, state::AddressLookupTable, AddressLookupTableAccount},
+    instruction::InstructionError,
+    message::{v0, CompileError, VersionedMessage},
+    signature::Signature,
+    signers::Signers,
+    transaction::{Transaction, TransactionError},
+};
+#[cfg(feature = "rpc")]
+use spl_token_2022::{extension::StateWithExtensions, state::Mint};
+#[cfg(feature = "rpc")]
+use crate::state::{AccountType, LaunchReceipt};
+#[cfg(feature = "test-mode")]
+use crate::{instruction::TestMintArgs, pda::find_test_mint_address};
+
+use crate::{
+    error::CustomError,
+    instruction::{
+        AdditionalMetadataArgs, AllocationPlan, BatchInitializeArgs, BatchRevokeArgs,
+        ConfigAdminArgs, CreateDistributionListArgs, CreateMetadataArgs, CreateMintArgs,
+        DistributeArgs, DistributeChunkArgs, DistributeToOwnersArgs, GovernanceLaunchArgs,
+        InitializeArgs, InitializeGroupArgs, IsAdminArgs, LaunchAllArgs, LauncherArgs,
+        LockedLaunchArgs, MintChunkArgs, MintPolicy, MultisigTreasuryArgs, OwnerAllocation,
+        PdaAuthorityArgs, RescueArgs, RevokeAuthoritiesArgs, RevokeAuthorityArgs, SeedLaunchArgs,
+        StartChunkedMintArgs, SupplyCapArgs, SupplyHashArgs, TimelockFreezeArgs, TokenInstruction,
+        TreasuryAndLiquidityArgs, UiSupplyArgs, ValidateOnlyArgs, ValuationArgs, VerifyCreatorArgs,
+        VerifyMetadataArgs, WriteDistributionListArgs, WritePackedDistributionListArgs,
+        CAPABILITY_CREATE_ATA, CAPABILITY_PROTOCOL_DESTINATION, LAUNCH_AUTHORIZATION_PREFIX,
+    },
+    pda::{
+        find_associated_token_address, find_chunked_mint_address, find_config_address,
+        find_distribution_cursor_address, find_distribution_list_address,
+        find_event_authority_address, find_governance_authority_address,
+        find_governance_record_address, find_launch_stats_address,
+        find_launcher_allowlist_address, find_mint_authority_address, find_program_data_address,
+        find_receipt_address, find_supply_cap_address, find_supply_lock_address,
+        find_timelock_authority_address,
+    },
+    processor::ASSOCIATED_TOKEN_PROGRAM_ID,
+    state::{ConfigSettings, ProgramConfig, VestingSchedule},
+    utils::supply_hash,
+    AMM_PROGRAM_ID,
+};
+
+pub use crate::{
+    instruction::recommended_compute_units,
+    utils::{token2022_mint_len, token2022_mint_rent},
+};
+
+/// Turns a program error into a human-readable message
+/// Our custom codes get a specific description, anything else falls back to the generic
+/// `ProgramError` display text.
+pub fn explain_error(e: &ProgramError) -> String {
+    match decode_custom_error(e) {
+        Some(custom) => describe(custom).to_string(),
+        None => e.to_string(),
+    }
+}
+
+/// Decodes one of this program's custom errors, undoing the build's `ERROR_CODE_OFFSET`
+/// Returns `None` for other errors, including custom codes of composing programs.
+pub fn decode_custom_error(e: &ProgramError) -> Option<CustomError> {
+    match e {
+        ProgramError::Custom(code) => CustomError::from_code(*code),
+        _ => None,
+    }
+}
+
+// Human-readable description for each custom error code
+fn describe(e: CustomError) -> &'static str {
+    match e {
+        CustomError::InvalidMintState => "mint account is not in the expected initial state",
+        CustomError::TokenAccountNotEmpty => {
+            "token account already contains tokens or has delegates"
+        }
+        CustomError::MintAuthorityNotRevoked => "mint authority was not successfully revoked",
+        CustomError::TokenAccountOwnerMismatch => "token account owner doesn't match the payer",
+        CustomError::MintAuthorityMismatch => "mint authority doesn't match the payer",
+        CustomError::PreExistingTokensFound => {
+            "candidate token accounts already hold tokens for this mint"
+        }
+        CustomError::InvalidAuthorityAccount => {
+            "mint authority account is a well-known program or sysvar"
+        }
+        CustomError::InvalidCreatorShares => "metadata creator shares don't sum to 100",
+        CustomError::InvalidPdaBump => "provided bump doesn't derive the expected program address",
+        CustomError::DecimalsNotAllowed => "configured decimals are not in the allowed set",
+        CustomError::UnsupportedExtension => {
+            "token account uses a Token-2022 extension that is not supported"
+        }
+        CustomError::UnexpectedBalanceDelta => {
+            "destination balance didn't grow by exactly the minted amount"
+        }
+        CustomError::InvalidMultisig => "account is not a valid initialized SPL multisig",
+        CustomError::TokenAccountWrongMint => "token account belongs to a different mint",
+        CustomError::DuplicateNonce => "a launch receipt with this nonce already exists",
+        CustomError::DistributionTotalMismatch => {
+            "distribution amounts don't add up to the total supply"
+        }
+        CustomError::ZeroAmountRecipient => {
+            "A recipient's share of the supply is zero, pass allow_zero_amounts if this is intended"
+        }
+        CustomError::DisallowedInstructionInTx => {
+            "The transaction contains an instruction for a program outside the launch whitelist"
+        }
+        CustomError::GroupFull => "The token group already holds its maximum number of members",
+        CustomError::NotGroupMember => "The mint is not a member of the expected token group",
+        CustomError::MetadataUpdateAuthorityNotCleared => {
+            "The metadata update authority was not cleared or the metadata is still mutable"
+        }
+        CustomError::AliasedAccounts => {
+            "The same account was passed for the mint, a token account or the authority"
+        }
+        CustomError::FreezeAuthorityMismatch => {
+            "The signer is not the mint's current freeze authority"
+        }
+        CustomError::FreezeAuthorityNotRevoked => {
+            "The freeze authority is still set after revoking it"
+        }
+        CustomError::MissingCoSigner => {
+            "The launch requires a second, distinct co-signer that did not sign"
+        }
+        CustomError::FreezeFailed => {
+            "The destination token account is not frozen after freezing it"
+        }
+        CustomError::DecimalsTooLarge => {
+            "The decimals exceed the maximum of 19 for which one whole token fits in a u64"
+        }
+        CustomError::ChunkExceedsTarget => {
+            "The chunk would take the minted total past the target supply"
+        }
+        CustomError::MintAlreadyFinalized => {
+            "The mint is already finalized: its authority is revoked and it has a supply"
+        }
+        CustomError::CannotLockEntireSupply => {
+            "The locked amount must be smaller than the total supply"
+        }
+        CustomError::SupplyStillLocked => {
+            "None of the locked supply has vested since the last release"
+        }
+        CustomError::AuthorityTransferFailed => {
+            "The authority was not transferred to the intended new key"
+        }
+        CustomError::TokenAccountWrongSize => {
+            "The token account data has the wrong size for a token account"
+        }
+        CustomError::MintWrongSize => "The mint account data has the wrong size for a mint",
+        CustomError::AuthorizationMintMismatch => {
+            "The launch authorization was signed for a different mint"
+        }
+        CustomError::CannotUseNativeMint => {
+            "The native SOL mint can't be used as a fixed-supply mint"
+        }
+        CustomError::SupplyBelowMinimum => "The configured supply is below the minimum supply",
+        CustomError::CreatorMismatch => "The launch receipt was created by a different creator",
+        CustomError::RevocationCpiFailed => {
+            "The set_authority call revoking the mint authority failed"
+        }
+        CustomError::SupplyOverflow => "Minting the amount would overflow the mint's supply",
+        CustomError::MetadataMismatch => {
+            "The metadata name, symbol or uri differs from the expected value"
+        }
+        CustomError::MetadataNotFound => "The mint has no Metaplex metadata account",
+        CustomError::DestinationOwnerNotAllowed => {
+            "The destination owner is not an allowed program or an account owned by one"
+        }
+        CustomError::AllocationPlanSumMismatch => {
+            "The allocation plan bucket shares don't sum to 10000 basis points"
+        }
+        CustomError::OracleNotApproved => {
+            "The verification oracle has not approved this launch yet, retry after approval"
+        }
+        CustomError::AdminMismatch => "The signer is not the admin stored in the program's config",
+        CustomError::RescueExceedsSurplus => {
+            "The rescue amount exceeds the tokens held beyond the locked supply"
+        }
+        CustomError::InsufficientRentFunds => {
+            "The payer doesn't have enough lamports for the rent of the accounts being created"
+        }
+        CustomError::DuplicateDestination => {
+            "The same destination account appears more than once in a distribution"
+        }
+        CustomError::FreezeAuthorityTampered => {
+            "The preserved freeze authority changed during the launch"
+        }
+        CustomError::ImpracticalDecimalsSupplyCombo => {
+            "The decimals are too high for the supply to be practical, set the override to allow it"
+        }
+        CustomError::ConfidentialConfigInvalid => {
+            "The confidential transfer configuration is invalid"
+        }
+        CustomError::GovernanceTransferFailed => {
+            "The mint authority was not handed to the governance authority"
+        }
+        CustomError::NameTooLong => "The metadata name is longer than 32 bytes",
+        CustomError::SymbolTooLong => "The metadata symbol is longer than 10 bytes",
+        CustomError::UriTooLong => "The metadata uri is longer than 200 bytes",
+        CustomError::MintSupplyNotZero => {
+            "The mint already has a supply, only mints with zero supply can be launched"
+        }
+        CustomError::ValuationOverflow => "The fully diluted value is too large to represent",
+        CustomError::SeedDerivationFailed => {
+            "The seed derives unusable launch parameters, pick another seed"
+        }
+        CustomError::LauncherNotAllowlisted => {
+            "The mint authority is not on the launcher allowlist, ask the admin to add it"
+        }
+        CustomError::SupplyHashMismatch => {
+            "The finalized supply or decimals differ from the committed supply hash"
+        }
+        CustomError::CannotCleanupBeforeFinalize => {
+            "The launch's PDAs can only be closed once the mint authority is revoked"
+        }
+        CustomError::ExtensionAuthorityNotRevoked => {
+            "A Token-2022 extension authority of the mint is still set after revoking it"
+        }
+        CustomError::IncompleteSupplyBeforeRevoke => {
+            "The mint authority would be revoked before the full supply was minted"
+        }
+        CustomError::CollectionVerificationFailed => {
+            "The collection authority doesn't match the collection or Metaplex didn't verify it"
+        }
+        CustomError::InsufficientForLaunchFee => {
+            "The payer can't cover the launch fee plus the rent of the new accounts, fund it"
+        }
+        CustomError::TokenStandardDecimalsMismatch => {
+            "The metadata's token standard needs other decimals, NonFungible requires 0"
+        }
+        CustomError::AuthorityNotSigner => "The mint authority must sign the transaction",
+        CustomError::PayerNotSigner => "The payer must sign the transaction",
+        CustomError::ProgramStillUpgradeable => {
+            "The program still has an upgrade authority, launch once it is made immutable"
+        }
+        CustomError::InvalidMultisigTreasury => {
+            "The treasury must be owned by a multisig requiring at least 2 distinct signers"
+        }
+        CustomError::ValidationFailed => {
+            "The launch would fail, the returned ValidationReport lists every failed check"
+        }
+        CustomError::MetadataTooLarge => {
+            "The additional metadata is too large for one instruction, write it in smaller batches"
+        }
+        CustomError::ReceiptCorrupted => "The launch receipt doesn't match its stored checksum",
+        CustomError::RecipientCountMismatch => {
+            "The declared number of recipients doesn't match the destination accounts passed"
+        }
+        CustomError::TimelockTransferFailed => {
+            "The freeze authority was not handed to the timelock authority"
+        }
+        CustomError::DistributionAlreadyComplete => {
+            "Every recipient of the distribution list has already been minted to"
+        }
+        CustomError::FreezeAuthorityConflict => {
+            "The mint has a freeze authority, launch it in a mode that keeps or revokes it"
+        }
+    }
+}
+
+// Instruction builders
+// Each builder returns an `Instruction` with accounts in the exact order the processor expects.
+// The program requires the mint authority and payer to be the same key, so builders take a
+// single `authority` and place it in both slots. Builders of launching instructions take the
+// `launch_gate_accounts` of the program's current config as `gate`.
+
+/// Launch gate accounts every launching instruction starts with, for the program's current
+/// `config`, `None` while no config exists
+/// Fetch the config PDA from `find_config_address` and decode it with
+/// `ProgramConfig::try_from_slice`, the program rejects a gate built from outdated settings.
+/// `oracle_approvals` are the oracle's approval accounts of the launched mints, in launch order,
+/// only used while the config sets an oracle program.
+pub fn launch_gate_accounts(
+    program_id: &Pubkey,
+    config: Option<&ProgramConfig>,
+    oracle_approvals: &[Pubkey],
+) -> Vec<AccountMeta> {
+    let (config_address, _) = find_config_address(program_id);
+    let mut accounts = vec![AccountMeta::new_readonly(config_address, false)];
+    let settings = config.map(|config| &config.settings).cloned().unwrap_or_default();
+    if settings.launcher_allowlist_enabled {
+        let (allowlist, _) = find_launcher_allowlist_address(program_id);
+        accounts.push(AccountMeta::new_readonly(allowlist, false));
+    }
+    if settings.charges_launch_fee() {
+        accounts.push(AccountMeta::new(settings.fee_treasury, false));
+        accounts.push(AccountMeta::new_readonly(system_program::id(), false));
+    }
+    if settings.requires_oracle_approval() {
+        accounts.extend(
+            oracle_approvals.iter().map(|approval| AccountMeta::new_readonly(*approval, false)),
+        );
+    }
+    accounts
+}
+
+// Puts the launch gate accounts in front of a launching instruction's own accounts
+fn with_launch_gate(gate: &[AccountMeta], accounts: Vec<AccountMeta>) -> Vec<AccountMeta> {
+    gate.iter().cloned().chain(accounts).collect()
+}
+
+/// Builds a `CreateMint` instruction, `mint` must be a fresh keypair that signs the transaction
+pub fn create_mint(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    payer: &Pubkey,
+    token_program: &Pubkey,
+    args: CreateMintArgs,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*mint, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: TokenInstruction::CreateMint(args).pack(),
+    }
+}
+
+/// Builds a `LaunchAll` instruction for a fresh `mint` keypair, minting into the payer's
+/// associated token account
+pub fn launch_all(
+    program_id: &Pubkey,
+    gate: &[AccountMeta],
+    mint: &Pubkey,
+    payer: &Pubkey,
+    token_program: &Pubkey,
+    decimals: Option<u8>,
+) -> Instruction {
+    let token_account = find_associated_token_address(payer, mint, token_program);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: with_launch_gate(
+            gate,
+            vec![
+                AccountMeta::new(*mint, true),
+                AccountMeta::new(token_account, false),
+                AccountMeta::new(*payer, true),
+                AccountMeta::new_readonly(*token_program, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            ],
+        ),
+        data: TokenInstruction::LaunchAll(LaunchAllArgs { decimals }).pack(),
+    }
+}
+
+/// Message an `InitializeArgs::authorizer` signs to authorize launching `mint`
+/// Verify it with an Ed25519 program instruction placed right before the launch, e.g. built with
+/// `solana_sdk::ed25519_instruction::new_ed25519_instruction`.
+pub fn launch_authorization_message(mint: &Pubkey) -> Vec<u8> {
+    [LAUNCH_AUTHORIZATION_PREFIX, mint.as_ref()].concat()
+}
+
+/// Builds an `InitializeFixedSupply` instruction
+/// `freeze_authority` is required when `args.needs_freeze_authority()`, the receipt PDA is
+/// derived from `args.receipt` when set, and `candidates` are optional token accounts for the
+/// mint that must hold no pre-existing tokens
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_fixed_supply(
+    program_id: &Pubkey,
+    gate: &[AccountMeta],
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    authority: &Pubkey,
+    token_program: &Pubkey,
+    freeze_authority: Option<&Pubkey>,
+    args: InitializeArgs,
+    candidates: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*token_program, false),
+    ];
+    if let Some(freeze_authority) = freeze_authority {
+        accounts.push(AccountMeta::new_readonly(*freeze_authority, true));
+    }
+    if let Some(receipt) = &args.receipt {
+        // The payer funds the receipt account, so it has to be writable
+        accounts[3].is_writable = true;
+        let (receipt_address, _) = find_receipt_address(program_id, mint, receipt.nonce);
+        accounts.push(AccountMeta::new(receipt_address, false));
+        accounts.push(AccountMeta::new_readonly(system_program::id(), false));
+    }
+    if args.verify_transaction {
+        accounts.push(AccountMeta::new_readonly(sysvar::instructions::id(), false));
+    }
+    if let Some(co_signer) = &args.co_signer {
+        accounts.push(AccountMeta::new_readonly(*co_signer, true));
+    }
+    if args.event.is_some() {
+        let (event_authority, _) = find_event_authority_address(program_id);
+        accounts.push(AccountMeta::new_readonly(event_authority, false));
+        accounts.push(AccountMeta::new_readonly(*program_id, false));
+    }
+    if args.has_capability(CAPABILITY_CREATE_ATA) {
+        // The payer funds the associated token account, so it has to be writable
+        accounts[3].is_writable = true;
+        accounts.push(AccountMeta::new_readonly(system_program::id(), false));
+        accounts.push(AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false));
+    }
+    if args.authorizer.is_some() {
+        accounts.push(AccountMeta::new_readonly(sysvar::instructions::id(), false));
+    }
+    accounts.extend(candidates.iter().map(|key| AccountMeta::new_readonly(*key, false)));
+
+    Instruction {
+        program_id: *program_id,
+        accounts: with_launch_gate(gate, accounts),
+        data: TokenInstruction::InitializeFixedSupply(args).pack(),
+    }
+}
+
+/// Builds an `InitializeFixedSupply` instruction minting into a protocol-owned token account
+/// Sets `CAPABILITY_PROTOCOL_DESTINATION` on `args`. `destination_owner` is the token account's
+/// owner, which must be an `ALLOWED_DESTINATION_PROGRAMS` program or an account one of them owns.
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_into_protocol_account(
+    program_id: &Pubkey,
+    gate: &[AccountMeta],
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    authority: &Pubkey,
+    token_program: &Pubkey,
+    destination_owner: &Pubkey,
+    mut args: InitializeArgs,
+) -> Instruction {
+    args.capabilities |= CAPABILITY_PROTOCOL_DESTINATION;
+    let freeze_authority = args.needs_freeze_authority().then_some(authority);
+    let mut instruction = initialize_fixed_supply(
+        program_id,
+        gate,
+        mint,
+        token_account,
+        authority,
+        token_program,
+        freeze_authority,
+        args,
+        &[],
+    );
+    instruction.accounts.push(AccountMeta::new_readonly(*destination_owner, false));
+    instruction
+}
+
+/// Compiles launch instructions into an unsigned message, for authorities whose key never
+/// enters the process, e.g. a hardware wallet
+/// The serialized message (`Message::serialize`) is what each key in `required_signers` signs
+/// externally. `fee_payer` may be the authority itself.
+pub fn unsigned_launch_message(
+    instructions: &[Instruction],
+    fee_payer: &Pubkey,
+    recent_blockhash: &Hash,
+) -> Message {
+    Message::new_with_blockhash(instructions, Some(fee_payer), recent_blockhash)
+}
+
+/// Keys that must sign `message`, in the order their signatures go into the transaction
+pub fn required_signers(message: &Message) -> &[Pubkey] {
+    &message.account_keys[..message.header.num_required_signatures as usize]
+}
+
+/// Builds an `InitializeWithUiSupply` instruction launching `args.ui_supply` whole tokens
+pub fn initialize_with_ui_supply(
+    program_id: &Pubkey,
+    gate: &[AccountMeta],
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    authority: &Pubkey,
+    token_program: &Pubkey,
+    args: UiSupplyArgs,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: with_launch_gate(
+            gate,
+            vec![
+                AccountMeta::new(*mint, false),
+                AccountMeta::new(*token_account, false),
+                AccountMeta::new_readonly(*authority, true),
+                AccountMeta::new_readonly(*authority, true),
+                AccountMeta::new_readonly(*token_program, false),
+            ],
+        ),
+        data: TokenInstruction::InitializeWithUiSupply(args).pack(),
+    }
+}
+
+/// Builds an `InitializeIntoMultisig` instruction launching into the `multisig`-owned
+/// `token_account`
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_into_multisig(
+    program_id: &Pubkey,
+    gate: &[AccountMeta],
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    multisig: &Pubkey,
+    authority: &Pubkey,
+    token_program: &Pubkey,
+    decimals: Option<u8>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: with_launch_gate(
+            gate,
+            vec![
+                AccountMeta::new(*mint, false),
+                AccountMeta::new(*token_account, false),
+                AccountMeta::new_readonly(*multisig, false),
+                AccountMeta::new_readonly(*authority, true),
+                AccountMeta::new_readonly(*authority, true),
+                AccountMeta::new_readonly(*token_program, false),
+            ],
+        ),
+        data: TokenInstruction::InitializeIntoMultisig(MultisigTreasuryArgs { decimals }).pack(),
+    }
+}
+
+/// Builds an `InitializeFromSeed` instruction
+/// `mint` must use the decimals `utils::derive_launch_params` derives from `seed`.
+pub fn initialize_from_seed(
+    program_id: &Pubkey,
+    gate: &[AccountMeta],
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    authority: &Pubkey,
+    token_program: &Pubkey,
+    seed: [u8; 32],
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: with_launch_gate(
+            gate,
+            vec![
+                AccountMeta::new(*mint, false),
+                AccountMeta::new(*token_account, false),
+                AccountMeta::new_readonly(*authority, true),
+                AccountMeta::new_readonly(*authority, true),
+                AccountMeta::new_readonly(*token_program, false),
+            ],
+        ),
+        data: TokenInstruction::InitializeFromSeed(SeedLaunchArgs { seed }).pack(),
+    }
+}
+
+/// Builds an `InitializeWithPdaAuthority` instruction, deriving the mint authority PDA and bump
+/// the program expects for `mint`
+pub fn initialize_with_pda_authority(
+    program_id: &Pubkey,
+    gate: &[AccountMeta],
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    payer: &Pubkey,
+    token_program: &Pubkey,
+    decimals: Option<u8>,
+) -> Instruction {
+    let (mint_authority, bump) = find_mint_authority_address(program_id, mint);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: with_launch_gate(
+            gate,
+            vec![
+                AccountMeta::new(*mint, false),
+                AccountMeta::new(*token_account, false),
+                AccountMeta::new_readonly(mint_authority, false),
+                AccountMeta::new_readonly(*payer, true),
+                AccountMeta::new_readonly(*token_program, false),
+            ],
+        ),
+        data: TokenInstruction::InitializeWithPdaAuthority(PdaAuthorityArgs { bump, decimals })
+            .pack(),
+    }
+}
+
+/// Builds an `InitializeWithLock` instruction for the mint's supply lock PDA
+/// `vault` must be a token account owned by the address from `find_supply_lock_address`.
+/// Pass `VestingSchedule::CLIFF` to release the whole lock at `unlock_timestamp`.
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_with_lock(
+    program_id: &Pubkey,
+    gate: &[AccountMeta],
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    vault: &Pubkey,
+    authority: &Pubkey,
+    token_program: &Pubkey,
+    lock_amount: u64,
+    unlock_timestamp: i64,
+    vesting: VestingSchedule,
+    decimals: Option<u8>,
+) -> Instruction {
+    let (lock, bump) = find_supply_lock_address(program_id, mint);
+    let args = LockedLaunchArgs {
+        lock_amount,
+        unlock_timestamp,
+        vesting,
+        bump,
+        decimals,
+    };
+
+    Instruction {
+        program_id: *program_id,
+        accounts: with_launch_gate(
+            gate,
+            vec![
+                AccountMeta::new(*mint, false),
+                AccountMeta::new(*token_account, false),
+                AccountMeta::new(lock, false),
+                AccountMeta::new(*vault, false),
+                AccountMeta::new_readonly(*authority, true),
+                AccountMeta::new(*authority, true),
+                AccountMeta::new_readonly(*token_program, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        ),
+        data: TokenInstruction::InitializeWithLock(args).pack(),
+    }
+}
+
+/// Builds an `InitializeWithGovernance` instruction handing the mint authority to the PDA of
+/// `governance_program` from `find_governance_authority_address`
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_with_governance(
+    program_id: &Pubkey,
+    gate: &[AccountMeta],
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    authority: &Pubkey,
+    token_program: &Pubkey,
+    governance_program: &Pubkey,
+    decimals: Option<u8>,
+) -> Instruction {
+    let (governance, governance_bump) = find_governance_authority_address(governance_program, mint);
+    let (record, record_bump) = find_governance_record_address(program_id, mint);
+    let args = GovernanceLaunchArgs {
+        governance_program: *governance_program,
+        governance_bump,
+        record_bump,
+        decimals,
+    };
+
+    Instruction {
+        program_id: *program_id,
+        accounts: with_launch_gate(
+            gate,
+            vec![
+                AccountMeta::new(*mint, false),
+                AccountMeta::new(*token_account, false),
+                AccountMeta::new_readonly(governance, false),
+                AccountMeta::new(record, false),
+                AccountMeta::new_readonly(*authority, true),
+                AccountMeta::new(*authority, true),
+                AccountMeta::new_readonly(*token_program, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        ),
+        data: TokenInstruction::InitializeWithGovernance(args).pack(),
+    }
+}
+
+/// Builds an `InitializeWithTimelockFreeze` instruction handing the freeze authority to the
+/// timelock PDA from `find_timelock_authority_address`, `timelock_program` must be the one the
+/// config sets
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_with_timelock_freeze(
+    program_id: &Pubkey,
+    gate: &[AccountMeta],
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    authority: &Pubkey,
+    token_program: &Pubkey,
+    timelock_program: &Pubkey,
+    decimals: Option<u8>,
+) -> Instruction {
+    let (timelock, timelock_bump) = find_timelock_authority_address(timelock_program, mint);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: with_launch_gate(
+            gate,
+            vec![
+                AccountMeta::new(*mint, false),
+                AccountMeta::new(*token_account, false),
+                AccountMeta::new_readonly(timelock, false),
+                AccountMeta::new_readonly(*authority, true),
+                AccountMeta::new(*authority, true),
+                AccountMeta::new_readonly(*token_program, false),
+            ],
+        ),
+        data: TokenInstruction::InitializeWithTimelockFreeze(TimelockFreezeArgs {
+            timelock_bump,
+            decimals,
+        })
+        .pack(),
+    }
+}
+
+/// Builds a `ReleaseLocked` instruction paying the vault out to `token_account`
+pub fn release_locked(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    token_account: &Pubkey,
+    beneficiary: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let (lock, _) = find_supply_lock_address(program_id, mint);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(lock, false),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*token_account, false),
+            AccountMeta::new_readonly(*beneficiary, true),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+        data: TokenInstruction::ReleaseLocked.pack(),
+    }
+}
+
+/// Builds a `Rescue` instruction moving `amount` out of a token account owned by the supply
+/// lock PDA of `lock_mint`
+#[allow(clippy::too_many_arguments)]
+pub fn rescue(
+    program_id: &Pubkey,
+    lock_mint: &Pubkey,
+    source: &Pubkey,
+    source_mint: &Pubkey,
+    destination: &Pubkey,
+    admin: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (lock, _) = find_supply_lock_address(program_id, lock_mint);
+    let (config, _) = find_config_address(program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(lock, false),
+            AccountMeta::new(*source, false),
+            AccountMeta::new_readonly(*source_mint, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(config, false),
+        ],
+        data: TokenInstruction::Rescue(RescueArgs { amount }).pack(),
+    }
+}
+
+/// Builds a `StartChunkedMint` instruction for the mint's chunked mint PDA
+pub fn start_chunked_mint(program_id: &Pubkey, mint: &Pubkey, authority: &Pubkey) -> Instruction {
+    let (chunked_mint, bump) = find_chunked_mint_address(program_id, mint);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(chunked_mint, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: TokenInstruction::StartChunkedMint(StartChunkedMintArgs { bump }).pack(),
+    }
+}
+
+/// Builds a `MintChunk` instruction minting `amount` base units into `token_account`
+#[allow(clippy::too_many_arguments)]
+pub fn mint_chunk(
+    program_id: &Pubkey,
+    gate: &[AccountMeta],
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    authority: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+    record_stats: bool,
+) -> Instruction {
+    let (chunked_mint, _) = find_chunked_mint_address(program_id, mint);
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(chunked_mint, false),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*token_program, false),
+    ];
+    let stats_bump = record_stats.then(|| {
+        // The payer funds the stats account on the first chunk, so it has to be writable
+        accounts[4].is_writable = true;
+        let (stats, bump) = find_launch_stats_address(program_id, mint);
+        accounts.push(AccountMeta::new(stats, false));
+        accounts.push(AccountMeta::new_readonly(system_program::id(), false));
+        bump
+    });
+
+    Instruction {
+        program_id: *program_id,
+        accounts: with_launch_gate(gate, accounts),
+        data: TokenInstruction::MintChunk(MintChunkArgs { amount, stats_bump }).pack(),
+    }
+}
+
+/// Builds a read-only `GetLaunchStats` instruction for the mint's launch stats PDA
+/// Decode the return data with `LaunchStats::try_from_slice`.
+pub fn get_launch_stats(program_id: &Pubkey, mint: &Pubkey) -> Instruction {
+    let (stats, _) = find_launch_stats_address(program_id, mint);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![AccountMeta::new_readonly(stats, false)],
+        data: TokenInstruction::GetLaunchStats.pack(),
+    }
+}
+
+/// Builds a `RecordSupplyCap` instruction for the mint's supply cap PDA
+pub fn record_supply_cap(program_id: &Pubkey, mint: &Pubkey, payer: &Pubkey) -> Instruction {
+    let (cap, bump) = find_supply_cap_address(program_id, mint);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(cap, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: TokenInstruction::RecordSupplyCap(SupplyCapArgs { bump }).pack(),
+    }
+}
+
+/// Builds a read-only `GetSupplyCap` instruction for the mint's supply cap PDA
+/// Decode the return data with `SupplyCap::try_from_slice`.
+pub fn get_supply_cap(program_id: &Pubkey, mint: &Pubkey) -> Instruction {
+    let (cap, _) = find_supply_cap_address(program_id, mint);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![AccountMeta::new_readonly(cap, false)],
+        data: TokenInstruction::GetSupplyCap.pack(),
+    }
+}
+
+/// Builds a `ValidateOnly` dry run of a plain launch, to be simulated without sending it
+/// Decode the return data with `ValidationReport::try_from_slice`, the simulation can skip
+/// signature verification to report missing signers too.
+pub fn validate_only(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    authority: &Pubkey,
+    token_program: &Pubkey,
+    decimals: Option<u8>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_account, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+        data: TokenInstruction::ValidateOnly(ValidateOnlyArgs { decimals }).pack(),
+    }
+}
+
+/// Builds a `VerifyAndFinalize` instruction launching only if every `ValidateOnly` check passes
+/// Decode the return data with `FinalizeReport::try_from_slice` after a successful launch, or with
+/// `ValidationReport::try_from_slice` when it fails with `ValidationFailed`.
+pub fn verify_and_finalize(
+    program_id: &Pubkey,
+    gate: &[AccountMeta],
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    authority: &Pubkey,
+    token_program: &Pubkey,
+    decimals: Option<u8>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: with_launch_gate(
+            gate,
+            vec![
+                AccountMeta::new(*mint, false),
+                AccountMeta::new(*token_account, false),
+                AccountMeta::new_readonly(*authority, true),
+                AccountMeta::new_readonly(*authority, true),
+                AccountMeta::new_readonly(*token_program, false),
+            ],
+        ),
+        data: TokenInstruction::VerifyAndFinalize(ValidateOnlyArgs { decimals }).pack(),
+    }
+}
+
+/// Builds a `WriteAdditionalMetadata` instruction setting `fields` in the mint's on-mint metadata
+pub fn write_additional_metadata(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    fields: Vec<(String, String)>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(spl_token_2022::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: TokenInstruction::WriteAdditionalMetadata(AdditionalMetadataArgs { fields }).pack(),
+    }
+}
+
+/// Builds a read-only `VerifyReceiptIntegrity` instruction for the receipt of the launch of
+/// `mint` with `nonce`
+pub fn verify_receipt_integrity(program_id: &Pubkey, mint: &Pubkey, nonce: u64) -> Instruction {
+    let (receipt, _) = find_receipt_address(program_id, mint, nonce);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![AccountMeta::new_readonly(receipt, false)],
+        data: TokenInstruction::VerifyReceiptIntegrity.pack(),
+    }
+}
+
+/// Builds a `TreasuryAndLiquidity` instruction
+/// `pool_accounts` are the accounts of the AMM pool instruction and are only used when
+/// `args.pool` is set
+#[allow(clippy::too_many_arguments)]
+pub fn treasury_and_liquidity(
+    program_id: &Pubkey,
+    gate: &[AccountMeta],
+    mint: &Pubkey,
+    treasury_account: &Pubkey,
+    liquidity_account: &Pubkey,
+    authority: &Pubkey,
+    token_program: &Pubkey,
+    args: TreasuryAndLiquidityArgs,
+    pool_accounts: &[AccountMeta],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(*treasury_account, false),
+        AccountMeta::new(*liquidity_account, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*token_program, false),
+    ];
+    if args.pool.is_some() {
+        accounts.push(AccountMeta::new_readonly(AMM_PROGRAM_ID, false));
+        accounts.extend_from_slice(pool_accounts);
+    }
+
+    Instruction {
+        program_id: *program_id,
+        accounts: with_launch_gate(gate, accounts),
+        data: TokenInstruction::TreasuryAndLiquidity(args).pack(),
+    }
+}
+
+/// Builds an `InitializeWithAllocationPlan` instruction, with one destination per plan bucket
+pub fn initialize_with_allocation_plan(
+    program_id: &Pubkey,
+    gate: &[AccountMeta],
+    mint: &Pubkey,
+    authority: &Pubkey,
+    token_program: &Pubkey,
+    destinations: &[Pubkey],
+    plan: AllocationPlan,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*token_program, false),
+    ];
+    accounts.extend(destinations.iter().map(|key| AccountMeta::new(*key, false)));
+
+    Instruction {
+        program_id: *program_id,
+        accounts: with_launch_gate(gate, accounts),
+        data: TokenInstruction::InitializeWithAllocationPlan(plan).pack(),
+    }
+}
+
+/// Decodes the allocation plan from the data of an `InitializeWithAllocationPlan` instruction,
+/// e.g. taken from a raw transaction for auditing
+/// The data goes through the same validation as on-chain, and data of any other instruction is
+/// rejected with `InvalidInstructionData`.
+pub fn decode_allocation_plan(data: &[u8]) -> Result<AllocationPlan, ProgramError> {
+    match TokenInstruction::unpack(data)? {
+        TokenInstruction::InitializeWithAllocationPlan(plan) => Ok(plan),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+/// Builds a `CreateMetadata` instruction, deriving the Metaplex metadata PDA for the mint
+/// `collection_authority` is the update authority of `args.collection` and is only used when the
+/// collection is set.
+pub fn create_metadata(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    collection_authority: Option<&Pubkey>,
+    args: CreateMetadataArgs,
+) -> Instruction {
+    let (metadata, _) = mpl_token_metadata::accounts::Metadata::find_pda(mint);
+
+    let mut accounts = vec![
+        AccountMeta::new(metadata, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*authority, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+    ];
+    if let Some(collection) = &args.collection {
+        let (collection_metadata, _) =
+            mpl_token_metadata::accounts::Metadata::find_pda(collection);
+        let (collection_master_edition, _) =
+            mpl_token_metadata::accounts::MasterEdition::find_pda(collection);
+        accounts.push(AccountMeta::new_readonly(*collection_authority.unwrap_or(authority), true));
+        accounts.push(AccountMeta::new_readonly(*collection, false));
+        accounts.push(AccountMeta::new(collection_metadata, false));
+        accounts.push(AccountMeta::new_readonly(collection_master_edition, false));
+        accounts.push(AccountMeta::new_readonly(sysvar::instructions::id(), false));
+    }
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: TokenInstruction::CreateMetadata(args).pack(),
+    }
+}
+
+/// Builds a `LockMetadata` instruction, to be sent after the supply has been finalized
+pub fn lock_metadata(program_id: &Pubkey, mint: &Pubkey, update_authority: &Pubkey) -> Instruction {
+    let (metadata, _) = mpl_token_metadata::accounts::Metadata::find_pda(mint);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*update_authority, true),
+            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+        ],
+        data: TokenInstruction::LockMetadata.pack(),
+    }
+}
+
+/// Builds a read-only `VerifyMetadata` instruction, deriving the Metaplex metadata PDA for the mint
+pub fn verify_metadata(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    args: VerifyMetadataArgs,
+) -> Instruction {
+    let (metadata, _) = mpl_token_metadata::accounts::Metadata::find_pda(mint);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(*mint, false),
+        ],
+        data: TokenInstruction::VerifyMetadata(args).pack(),
+    }
+}
+
+/// Builds a `VerifySupplyHash` instruction committing the mint to `supply` base units with
+/// `decimals`, to bundle after the launch instruction
+pub fn verify_supply_hash(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    supply: u64,
+    decimals: u8,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![AccountMeta::new_readonly(*mint, false)],
+        data: TokenInstruction::VerifySupplyHash(SupplyHashArgs {
+            supply_hash: supply_hash(mint, supply, decimals),
+        })
+        .pack(),
+    }
+}
+
+/// Builds a `Cleanup` instruction closing the launch's `transient` PDAs once it is finalized
+/// `transient` holds addresses from `find_chunked_mint_address`, `find_launch_stats_address` and
+/// `find_distribution_list_address` for accounts that exist, their rent goes to `receiver`.
+pub fn cleanup(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    receiver: &Pubkey,
+    transient: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*receiver, false),
+    ];
+    accounts.extend(transient.iter().map(|key| AccountMeta::new(*key, false)));
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: TokenInstruction::Cleanup.pack(),
+    }
+}
+
+/// Builds a `TestMint` instruction minting the test supply into the authority's `token_account`
+#[cfg(feature = "test-mode")]
+pub fn test_mint(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    authority: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let (test_mint, bump) = find_test_mint_address(program_id, mint);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(*token_account, false),
+            AccountMeta::new(test_mint, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: TokenInstruction::TestMint(TestMintArgs { bump }).pack(),
+    }
+}
+
+/// Builds a `ResetTestMint` instruction burning the test supply held in `token_account`, the
+/// record's rent goes back to the authority
+#[cfg(feature = "test-mode")]
+pub fn reset_test_mint(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    authority: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let (test_mint, _) = find_test_mint_address(program_id, mint);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(*token_account, false),
+            AccountMeta::new(test_mint, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new(*authority, false),
+        ],
+        data: TokenInstruction::ResetTestMint.pack(),
+    }
+}
+
+/// Builds a read-only `RemainingMintable` query instruction
+pub fn remaining_mintable(program_id: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![AccountMeta::new_readonly(*mint, false)],
+        data: TokenInstruction::RemainingMintable.pack(),
+    }
+}
+
+/// Builds an `AddLauncher` instruction adding `launcher` to the program's launcher allowlist
+pub fn add_launcher(program_id: &Pubkey, admin: &Pubkey, launcher: &Pubkey) -> Instruction {
+    let (allowlist, bump) = find_launcher_allowlist_address(program_id);
+    let (config, _) = find_config_address(program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(allowlist, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(config, false),
+        ],
+        data: TokenInstruction::AddLauncher(LauncherArgs { launcher: *launcher, bump }).pack(),
+    }
+}
+
+/// Builds a `RemoveLauncher` instruction removing `launcher` from the launcher allowlist
+pub fn remove_launcher(program_id: &Pubkey, admin: &Pubkey, launcher: &Pubkey) -> Instruction {
+    let (allowlist, bump) = find_launcher_allowlist_address(program_id);
+    let (config, _) = find_config_address(program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(allowlist, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(config, false),
+        ],
+        data: TokenInstruction::RemoveLauncher(LauncherArgs { launcher: *launcher, bump }).pack(),
+    }
+}
+
+/// Builds a read-only `IsAdmin` query instruction for `candidate` against the config PDA
+pub fn is_admin(program_id: &Pubkey, candidate: &Pubkey) -> Instruction {
+    let (config, _) = find_config_address(program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![AccountMeta::new_readonly(config, false)],
+        data: TokenInstruction::IsAdmin(IsAdminArgs { candidate: *candidate }).pack(),
+    }
+}
+
+/// Builds a `SetConfigAdmin` instruction in which `current_admin` hands the admin role to
+/// `new_admin`, the program's upgrade authority being the current admin until the config exists
+pub fn set_config_admin(
+    program_id: &Pubkey,
+    current_admin: &Pubkey,
+    new_admin: &Pubkey,
+) -> Instruction {
+    let (config, bump) = find_config_address(program_id);
+    let (program_data, _) = find_program_data_address(program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new(*current_admin, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(program_data, false),
+        ],
+        data: TokenInstruction::SetConfigAdmin(ConfigAdminArgs { admin: *new_admin, bump }).pack(),
+    }
+}
+
+/// Builds an `UpdateConfig` instruction in which the config admin replaces the launch policy
+pub fn update_config(program_id: &Pubkey, admin: &Pubkey, settings: ConfigSettings) -> Instruction {
+    let (config, _) = find_config_address(program_id);
+    let mut accounts = vec![
+        AccountMeta::new(config, false),
+        AccountMeta::new_readonly(*admin, true),
+    ];
+    if settings.requires_oracle_approval() {
+        accounts.push(AccountMeta::new_readonly(settings.oracle_program, false));
+    }
+    if settings.has_timelock_program() {
+        accounts.push(AccountMeta::new_readonly(settings.timelock_program, false));
+    }
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: TokenInstruction::UpdateConfig(settings).pack(),
+    }
+}
+
+/// Builds a read-only `GetValuation` instruction pricing one whole token at `price_per_token`
+pub fn get_valuation(program_id: &Pubkey, mint: &Pubkey, price_per_token: u64) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![AccountMeta::new_readonly(*mint, false)],
+        data: TokenInstruction::GetValuation(ValuationArgs { price_per_token }).pack(),
+    }
+}
+
+/// Builds a read-only `CheckPolicy` instruction evaluating `mint` against `policy`
+pub fn check_policy(program_id: &Pubkey, mint: &Pubkey, policy: MintPolicy) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![AccountMeta::new_readonly(*mint, false)],
+        data: TokenInstruction::CheckPolicy(policy).pack(),
+    }
+}
+
+/// Builds a read-only `VerifyCreator` instruction against the receipt of the launch with `nonce`
+pub fn verify_creator(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    nonce: u64,
+    creator: &Pubkey,
+) -> Instruction {
+    let (receipt, _) = find_receipt_address(program_id, mint, nonce);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(receipt, false),
+            AccountMeta::new_readonly(*mint, false),
+        ],
+        data: TokenInstruction::VerifyCreator(VerifyCreatorArgs { creator: *creator }).pack(),
+    }
+}
+
+/// Builds a read-only `VerifyGroupMembership` instruction
+pub fn verify_group_membership(
+    program_id: &Pubkey,
+    member_mint: &Pubkey,
+    group_mint: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*member_mint, false),
+            AccountMeta::new_readonly(*group_mint, false),
+        ],
+        data: TokenInstruction::VerifyGroupMembership.pack(),
+    }
+}
+
+/// Builds a `BatchInitialize` instruction
+/// `pairs` holds one (mint, destination token account) pair per entry of `args.mints`
+pub fn batch_initialize(
+    program_id: &Pubkey,
+    gate: &[AccountMeta],
+    authority: &Pubkey,
+    token_program: &Pubkey,
+    pairs: &[(Pubkey, Pubkey)],
+    args: BatchInitializeArgs,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*token_program, false),
+    ];
+    for (mint, token_account) in pairs {
+        accounts.push(AccountMeta::new(*mint, false));
+        accounts.push(AccountMeta::new(*token_account, false));
+    }
+
+    Instruction {
+        program_id: *program_id,
+        accounts: with_launch_gate(gate, accounts),
+        data: TokenInstruction::BatchInitialize(args).pack(),
+    }
+}
+
+/// Builds a `RevokeAuthority` instruction, `authority` must hold the authority being revoked
+pub fn revoke_authority(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    token_program: &Pubkey,
+    args: RevokeAuthorityArgs,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+        data: TokenInstruction::RevokeAuthority(args).pack(),
+    }
+}
+
+/// Builds a `RevokeAuthorities` instruction
+/// `revocations` holds one (authority type in the Token-2022 encoding, current holder) pair per
+/// authority to revoke, every holder must sign
+pub fn revoke_authorities(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    revocations: &[(u8, Pubkey)],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*token_program, false),
+    ];
+    accounts.extend(revocations.iter().map(|(_, holder)| AccountMeta::new_readonly(*holder, true)));
+    let authority_types = revocations.iter().map(|(authority_type, _)| *authority_type).collect();
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: TokenInstruction::RevokeAuthorities(RevokeAuthoritiesArgs { authority_types })
+            .pack(),
+    }
+}
+
+/// Builds a `BatchRevoke` instruction
+/// `pairs` holds one (mint, mint authority) pair per mint, every authority must sign
+pub fn batch_revoke(
+    program_id: &Pubkey,
+    token_program: &Pubkey,
+    pairs: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let mut accounts = vec![AccountMeta::new_readonly(*token_program, false)];
+    for (mint, authority) in pairs {
+        accounts.push(AccountMeta::new(*mint, false));
+        accounts.push(AccountMeta::new_readonly(*authority, true));
+    }
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: TokenInstruction::BatchRevoke(BatchRevokeArgs { mints: pairs.len() as u8 }).pack(),
+    }
+}
+
+/// Builds a `SetFreezeAuthority` instruction handing the freeze authority to `multisig`
+pub fn set_freeze_authority(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    freeze_authority: &Pubkey,
+    multisig: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(*freeze_authority, true),
+            AccountMeta::new_readonly(*multisig, false),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+        data: TokenInstruction::SetFreezeAuthority.pack(),
+    }
+}
+
+/// Builds a `CreateDistributionList` instruction for the mint's distribution list PDA
+pub fn create_distribution_list(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    capacity: u16,
+) -> Instruction {
+    let (list, bump) = find_distribution_list_address(program_id, mint);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(list, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: TokenInstruction::CreateDistributionList(CreateDistributionListArgs {
+            capacity,
+            bump,
+        })
+        .pack(),
+    }
+}
+
+/// Builds a `WriteDistributionList` instruction appending entries to the mint's list
+pub fn write_distribution_list(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    args: WriteDistributionListArgs,
+) -> Instruction {
+    let (list, _) = find_distribution_list_address(program_id, mint);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(list, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: TokenInstruction::WriteDistributionList(args).pack(),
+    }
+}
+
+/// Builds a `WritePackedDistributionList` instruction appending entries to the mint's list
+pub fn write_packed_distribution_list(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    args: WritePackedDistributionListArgs,
+) -> Instruction {
+    let (list, _) = find_distribution_list_address(program_id, mint);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(list, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: TokenInstruction::WritePackedDistributionList(args).pack(),
+    }
+}
+
+/// Builds a `Distribute` instruction, `destinations` must follow the order of the stored list
+pub fn distribute(
+    program_id: &Pubkey,
+    gate: &[AccountMeta],
+    mint: &Pubkey,
+    authority: &Pubkey,
+    token_program: &Pubkey,
+    destinations: &[Pubkey],
+    args: DistributeArgs,
+) -> Instruction {
+    let (list, _) = find_distribution_list_address(program_id, mint);
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(list, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*token_program, false),
+    ];
+    accounts.extend(destinations.iter().map(|key| AccountMeta::new(*key, false)));
+
+    Instruction {
+        program_id: *program_id,
+        accounts: with_launch_gate(gate, accounts),
+        data: TokenInstruction::Distribute(args).pack(),
+    }
+}
+
+/// Builds a `DistributeChunk` instruction minting to `destinations`, the list entries from
+/// `start_index` on. Send chunks one after another, each starting where the previous one ended.
+#[allow(clippy::too_many_arguments)]
+pub fn distribute_chunk(
+    program_id: &Pubkey,
+    gate: &[AccountMeta],
+    mint: &Pubkey,
+    authority: &Pubkey,
+    token_program: &Pubkey,
+    start_index: u16,
+    destinations: &[Pubkey],
+    allow_zero_amounts: bool,
+) -> Instruction {
+    let (list, _) = find_distribution_list_address(program_id, mint);
+    let (cursor, cursor_bump) = find_distribution_cursor_address(program_id, mint);
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(list, false),
+        AccountMeta::new(cursor, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*authority, true),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend(destinations.iter().map(|key| AccountMeta::new(*key, false)));
+
+    Instruction {
+        program_id: *program_id,
+        accounts: with_launch_gate(gate, accounts),
+        data: TokenInstruction::DistributeChunk(DistributeChunkArgs {
+            start_index,
+            cursor_bump,
+            allow_zero_amounts,
+        })
+        .pack(),
+    }
+}
+
+/// Builds a `DistributeToOwners` instruction minting the supply to the wallets in `recipients`
+/// The authority also pays for any associated token accounts that have to be created.
+pub fn distribute_to_owners(
+    program_id: &Pubkey,
+    gate: &[AccountMeta],
+    mint: &Pubkey,
+    authority: &Pubkey,
+    token_program: &Pubkey,
+    recipients: &[OwnerAllocation],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(*authority, true),
+        AccountMeta::new(*authority, true),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+    ];
+    for recipient in recipients {
+        let token_account = find_associated_token_address(&recipient.owner, mint, token_program);
+        accounts.push(AccountMeta::new_readonly(recipient.owner, false));
+        accounts.push(AccountMeta::new(token_account, false));
+    }
+
+    Instruction {
+        program_id: *program_id,
+        accounts: with_launch_gate(gate, accounts),
+        data: TokenInstruction::DistributeToOwners(DistributeToOwnersArgs {
+            recipients: recipients.to_vec(),
+            num_recipients: recipients.len() as u16,
+        })
+        .pack(),
+    }
+}
+
+/// Builds an `InitializeGroup` instruction turning a Token-2022 mint into a group
+pub fn initialize_group(
+    program_id: &Pubkey,
+    group_mint: &Pubkey,
+    authority: &Pubkey,
+    args: InitializeGroupArgs,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*group_mint, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(spl_token_2022::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: TokenInstruction::InitializeGroup(args).pack(),
+    }
+}
+
+/// Builds an `AddGroupMember` instruction registering `member_mint` in `group_mint`
+pub fn add_group_member(
+    program_id: &Pubkey,
+    member_mint: &Pubkey,
+    group_mint: &Pubkey,
+    authority: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*member_mint, false),
+            AccountMeta::new(*group_mint, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(spl_token_2022::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: TokenInstruction::AddGroupMember.pack(),
+    }
+}
+
+// Instruction schema
+// A hand-maintained description of the Borsh wire format, for tooling that generates bindings
+// without an Anchor IDL. `schema_index` matches every variant exhaustively, so adding an
+// instruction fails to compile until it is described here as well.
+
+// (name, is_writable, is_signer, condition under which the account is present)
+#[cfg(feature = "serde")]
+type SchemaAccount = (&'static str, bool, bool, Option<&'static str>);
+
+#[cfg(feature = "serde")]
+struct InstructionSchema {
+    name: &'static str,
+    // (field name, Borsh type)
+    fields: &'static [(&'static str, &'static str)],
+    accounts: &'static [SchemaAccount],
+}
+
+// Leading account entry of every launching instruction, see `launch_gate_accounts`
+#[cfg(feature = "serde")]
+const LAUNCH_GATE: SchemaAccount =
+    ("launch_gate", false, false, Some("variadic, the config PDA then the accounts it enables"));
+
+// Descriptions in enum order, so the index of an entry is its Borsh discriminator
+#[cfg(feature = "serde")]
+const INSTRUCTION_SCHEMAS: &[InstructionSchema] = &[
+    InstructionSchema {
+        name: "InitializeFixedSupply",
+        fields: &[("args", "InitializeArgs")],
+        accounts: &[
+            LAUNCH_GATE,
+            ("mint", true, false, None),
+            ("token_account", true, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", false, true, None),
+            ("token_program", false, false, None),
+            (
+                "freeze_authority",
+                false,
+                true,
+                Some("thaw, freeze_destination or CAPABILITY_REVOKE_FREEZE is set"),
+            ),
+            ("receipt", true, false, Some("receipt is set, payer must be writable")),
+            ("system_program", false, false, Some("receipt is set")),
+            ("instructions_sysvar", false, false, Some("verify_transaction is set")),
+            ("co_signer", false, true, Some("co_signer is set")),
+            ("event_authority", false, false, Some("event is set")),
+            ("this_program", false, false, Some("event is set")),
+            ("system_program", false, false, Some("CAPABILITY_CREATE_ATA is set, payer writable")),
+            ("associated_token_program", false, false, Some("CAPABILITY_CREATE_ATA is set")),
+            ("authorization_sysvar", false, false, Some("authorizer is set, instructions sysvar")),
+            (
+                "destination_owner",
+                false,
+                false,
+                Some("CAPABILITY_PROTOCOL_DESTINATION is set"),
+            ),
+            ("program_data", false, false, Some("CAPABILITY_IMMUTABLE_PROGRAM is set")),
+            ("candidates", false, false, Some("variadic, any number of trailing accounts")),
+        ],
+    },
+    InstructionSchema {
+        name: "TreasuryAndLiquidity",
+        fields: &[("treasury_bps", "u16"), ("pool", "option<PoolArgs>")],
+        accounts: &[
+            LAUNCH_GATE,
+            ("mint", true, false, None),
+            ("treasury_account", true, false, None),
+            ("liquidity_account", true, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", false, true, None),
+            ("token_program", false, false, None),
+            ("amm_program", false, false, Some("pool is set")),
+            ("pool_accounts", false, false, Some("pool is set, variadic, flags as required")),
+        ],
+    },
+    InstructionSchema {
+        name: "CreateMetadata",
+        fields: &[
+            ("name", "string"),
+            ("symbol", "string"),
+            ("uri", "string"),
+            ("seller_fee_basis_points", "u16"),
+            ("creators", "option<vec<(pubkey, u8)>>"),
+            ("is_mutable", "bool"),
+            ("collection", "option<pubkey>"),
+            ("token_standard", "option<u8>"),
+        ],
+        accounts: &[
+            ("metadata", true, false, None),
+            ("mint", false, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", true, true, None),
+            ("system_program", false, false, None),
+            ("metadata_program", false, false, None),
+            ("collection_authority", false, true, Some("collection is set")),
+            ("collection_mint", false, false, Some("collection is set")),
+            ("collection_metadata", true, false, Some("collection is set")),
+            ("collection_master_edition", false, false, Some("collection is set")),
+            ("instructions_sysvar", false, false, Some("collection is set")),
+        ],
+    },
+    InstructionSchema {
+        name: "RemainingMintable",
+        fields: &[],
+        accounts: &[("mint", false, false, None)],
+    },
+    InstructionSchema {
+        name: "BatchInitialize",
+        fields: &[("mints", "vec<InitializeArgs>")],
+        accounts: &[
+            LAUNCH_GATE,
+            ("mint_authority", false, true, None),
+            ("payer", false, true, None),
+            ("token_program", false, false, None),
+            ("pairs", true, false, Some("variadic, one (mint, token account) pair per entry")),
+        ],
+    },
+    InstructionSchema {
+        name: "SetFreezeAuthority",
+        fields: &[],
+        accounts: &[
+            ("mint", true, false, None),
+            ("freeze_authority", false, true, None),
+            ("multisig", false, false, None),
+            ("token_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "CreateDistributionList",
+        fields: &[("capacity", "u16"), ("bump", "u8")],
+        accounts: &[
+            ("distribution_list", true, false, None),
+            ("mint", false, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", true, true, None),
+            ("system_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "WriteDistributionList",
+        fields: &[("offset", "u16"), ("entries", "vec<DistributionEntry>")],
+        accounts: &[
+            ("distribution_list", true, false, None),
+            ("mint_authority", false, true, None),
+        ],
+    },
+    InstructionSchema {
+        name: "Distribute",
+        fields: &[("allow_zero_amounts", "bool"), ("num_recipients", "u16")],
+        accounts: &[
+            LAUNCH_GATE,
+            ("mint", true, false, None),
+            ("distribution_list", false, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", false, true, None),
+            ("token_program", false, false, None),
+            ("destinations", true, false, Some("variadic, one per list entry in order")),
+        ],
+    },
+    InstructionSchema {
+        name: "InitializeGroup",
+        fields: &[("max_size", "u32")],
+        accounts: &[
+            ("group_mint", true, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", true, true, None),
+            ("token_program", false, false, None),
+            ("system_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "AddGroupMember",
+        fields: &[],
+        accounts: &[
+            ("member_mint", true, false, None),
+            ("group_mint", true, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", true, true, None),
+            ("token_program", false, false, None),
+            ("system_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "VerifyGroupMembership",
+        fields: &[],
+        accounts: &[
+            ("member_mint", false, false, None),
+            ("group_mint", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "LockMetadata",
+        fields: &[],
+        accounts: &[
+            ("metadata", true, false, None),
+            ("mint", false, false, None),
+            ("update_authority", false, true, None),
+            ("metadata_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "CreateMint",
+        fields: &[
+            ("mint_authority", "pubkey"),
+            ("decimals", "option<u8>"),
+            ("freeze_authority", "option<pubkey>"),
+            ("default_account_frozen", "bool"),
+            ("confidential_transfer", "option<ConfidentialTransferConfig>"),
+        ],
+        accounts: &[
+            ("mint", true, true, None),
+            ("payer", true, true, None),
+            ("token_program", false, false, None),
+            ("system_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "RevokeAuthority",
+        fields: &[("authority_type", "u8")],
+        accounts: &[
+            ("mint", true, false, None),
+            ("authority", false, true, None),
+            ("token_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "InitializeWithPdaAuthority",
+        fields: &[("bump", "u8"), ("decimals", "option<u8>")],
+        accounts: &[
+            LAUNCH_GATE,
+            ("mint", true, false, None),
+            ("token_account", true, false, None),
+            ("mint_authority", false, false, None),
+            ("payer", false, true, None),
+            ("token_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "StartChunkedMint",
+        fields: &[("bump", "u8")],
+        accounts: &[
+            ("chunked_mint", true, false, None),
+            ("mint", false, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", true, true, None),
+            ("system_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "MintChunk",
+        fields: &[("amount", "u64"), ("stats_bump", "option<u8>")],
+        accounts: &[
+            LAUNCH_GATE,
+            ("mint", true, false, None),
+            ("chunked_mint", true, false, None),
+            ("token_account", true, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", false, true, None),
+            ("token_program", false, false, None),
+            ("launch_stats", true, false, Some("stats_bump is set, payer must be writable")),
+            ("system_program", false, false, Some("stats_bump is set")),
+        ],
+    },
+    InstructionSchema {
+        name: "CheckPolicy",
+        fields: &[("freeze_authority", "option<pubkey>"), ("allowed_decimals", "vec<u8>")],
+        accounts: &[("mint", false, false, None)],
+    },
+    InstructionSchema {
+        name: "InitializeWithLock",
+        fields: &[
+            ("lock_amount", "u64"),
+            ("unlock_timestamp", "i64"),
+            ("vesting", "VestingSchedule"),
+            ("bump", "u8"),
+            ("decimals", "option<u8>"),
+        ],
+        accounts: &[
+            LAUNCH_GATE,
+            ("mint", true, false, None),
+            ("token_account", true, false, None),
+            ("supply_lock", true, false, None),
+            ("vault", true, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", true, true, None),
+            ("token_program", false, false, None),
+            ("system_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "ReleaseLocked",
+        fields: &[],
+        accounts: &[
+            ("supply_lock", true, false, None),
+            ("vault", true, false, None),
+            ("mint", false, false, None),
+            ("token_account", true, false, None),
+            ("beneficiary", false, true, None),
+            ("token_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "LaunchAll",
+        fields: &[("decimals", "option<u8>")],
+        accounts: &[
+            LAUNCH_GATE,
+            ("mint", true, true, None),
+            ("token_account", true, false, None),
+            ("payer", true, true, None),
+            ("token_program", false, false, None),
+            ("system_program", false, false, None),
+            ("associated_token_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "EmitEvent",
+        fields: &[("bump", "u8"), ("event", "LaunchEvent")],
+        accounts: &[("event_authority", false, true, None)],
+    },
+    InstructionSchema {
+        name: "VerifyCreator",
+        fields: &[("creator", "pubkey")],
+        accounts: &[("receipt", false, false, None), ("mint", false, false, None)],
+    },
+    InstructionSchema {
+        name: "DistributeToOwners",
+        fields: &[("recipients", "vec<OwnerAllocation>"), ("num_recipients", "u16")],
+        accounts: &[
+            LAUNCH_GATE,
+            ("mint", true, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", true, true, None),
+            ("token_program", false, false, None),
+            ("system_program", false, false, None),
+            ("associated_token_program", false, false, None),
+            (
+                "pairs",
+                true,
+                false,
+                Some("variadic, one (wallet, associated token account) pair per recipient"),
+            ),
+        ],
+    },
+    InstructionSchema {
+        name: "VerifyMetadata",
+        fields: &[("name", "string"), ("symbol", "string"), ("uri", "string")],
+        accounts: &[("metadata", false, false, None), ("mint", false, false, None)],
+    },
+    InstructionSchema {
+        name: "InitializeWithAllocationPlan",
+        fields: &[("buckets", "vec<AllocationBucket>")],
+        accounts: &[
+            LAUNCH_GATE,
+            ("mint", true, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", false, true, None),
+            ("token_program", false, false, None),
+            ("destinations", true, false, Some("variadic, one per bucket in order")),
+        ],
+    },
+    InstructionSchema {
+        name: "Rescue",
+        fields: &[("amount", "u64")],
+        accounts: &[
+            ("supply_lock", false, false, None),
+            ("source", true, false, None),
+            ("mint", false, false, None),
+            ("destination", true, false, None),
+            ("admin", false, true, None),
+            ("token_program", false, false, None),
+            ("config", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "GetLaunchStats",
+        fields: &[],
+        accounts: &[("launch_stats", false, false, None)],
+    },
+    InstructionSchema {
+        name: "InitializeWithUiSupply",
+        fields: &[
+            ("ui_supply", "u64"),
+            ("decimals", "u8"),
+            ("allow_impractical_decimals", "bool"),
+        ],
+        accounts: &[
+            LAUNCH_GATE,
+            ("mint", true, false, None),
+            ("token_account", true, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", false, true, None),
+            ("token_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "BatchRevoke",
+        fields: &[("mints", "u8")],
+        accounts: &[
+            ("token_program", false, false, None),
+            ("pairs", true, true, Some("variadic, one (mint, mint authority) pair per mint")),
+        ],
+    },
+    InstructionSchema {
+        name: "WritePackedDistributionList",
+        fields: &[("offset", "u16"), ("entries", "packed<DistributionEntry>")],
+        accounts: &[
+            ("distribution_list", true, false, None),
+            ("mint_authority", false, true, None),
+        ],
+    },
+    InstructionSchema {
+        name: "IsAdmin",
+        fields: &[("candidate", "pubkey")],
+        accounts: &[("config", false, false, None)],
+    },
+    InstructionSchema {
+        name: "InitializeWithGovernance",
+        fields: &[
+            ("governance_program", "pubkey"),
+            ("governance_bump", "u8"),
+            ("record_bump", "u8"),
+            ("decimals", "option<u8>"),
+        ],
+        accounts: &[
+            LAUNCH_GATE,
+            ("mint", true, false, None),
+            ("token_account", true, false, None),
+            ("governance_authority", false, false, None),
+            ("governance_record", true, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", true, true, None),
+            ("token_program", false, false, None),
+            ("system_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "RecordSupplyCap",
+        fields: &[("bump", "u8")],
+        accounts: &[
+            ("supply_cap", true, false, None),
+            ("mint", false, false, None),
+            ("payer", true, true, None),
+            ("system_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "GetSupplyCap",
+        fields: &[],
+        accounts: &[("supply_cap", false, false, None)],
+    },
+    InstructionSchema {
+        name: "GetValuation",
+        fields: &[("price_per_token", "u64")],
+        accounts: &[("mint", false, false, None)],
+    },
+    InstructionSchema {
+        name: "InitializeFromSeed",
+        fields: &[("seed", "[u8; 32]")],
+        accounts: &[
+            LAUNCH_GATE,
+            ("mint", true, false, None),
+            ("token_account", true, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", false, true, None),
+            ("token_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "AddLauncher",
+        fields: &[("launcher", "pubkey"), ("bump", "u8")],
+        accounts: &[
+            ("launcher_allowlist", true, false, None),
+            ("admin", true, true, None),
+            ("system_program", false, false, None),
+            ("config", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "RemoveLauncher",
+        fields: &[("launcher", "pubkey"), ("bump", "u8")],
+        accounts: &[
+            ("launcher_allowlist", true, false, None),
+            ("admin", false, true, None),
+            ("config", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "VerifySupplyHash",
+        fields: &[("supply_hash", "[u8; 32]")],
+        accounts: &[("mint", false, false, None)],
+    },
+    InstructionSchema {
+        name: "Cleanup",
+        fields: &[],
+        accounts: &[
+            ("mint", false, false, None),
+            ("authority", false, true, None),
+            ("rent_receiver", true, false, None),
+            ("transient_pdas", true, false, Some("variadic, any number of trailing accounts")),
+        ],
+    },
+    InstructionSchema {
+        name: "RevokeAuthorities",
+        fields: &[("authority_types", "vec<u8>")],
+        accounts: &[
+            ("mint", true, false, None),
+            ("token_program", false, false, None),
+            ("authorities", false, true, Some("variadic, one per authority type")),
+        ],
+    },
+    InstructionSchema {
+        name: "TestMint",
+        fields: &[("bump", "u8")],
+        accounts: &[
+            ("mint", true, false, None),
+            ("token_account", true, false, None),
+            ("test_mint", true, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", true, true, None),
+            ("token_program", false, false, None),
+            ("system_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "ResetTestMint",
+        fields: &[],
+        accounts: &[
+            ("mint", true, false, None),
+            ("token_account", true, false, None),
+            ("test_mint", true, false, None),
+            ("authority", false, true, None),
+            ("token_program", false, false, None),
+            ("rent_receiver", true, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "InitializeIntoMultisig",
+        fields: &[("decimals", "option<u8>")],
+        accounts: &[
+            LAUNCH_GATE,
+            ("mint", true, false, None),
+            ("token_account", true, false, None),
+            ("multisig", false, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", false, true, None),
+            ("token_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "ValidateOnly",
+        fields: &[("decimals", "option<u8>")],
+        accounts: &[
+            ("mint", false, false, None),
+            ("token_account", false, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", false, true, None),
+            ("token_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "WriteAdditionalMetadata",
+        fields: &[("fields", "vec<(string, string)>")],
+        accounts: &[
+            ("mint", true, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", true, true, None),
+            ("token_program", false, false, None),
+            ("system_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "VerifyReceiptIntegrity",
+        fields: &[],
+        accounts: &[("receipt", false, false, None)],
+    },
+    InstructionSchema {
+        name: "InitializeWithTimelockFreeze",
+        fields: &[("timelock_bump", "u8"), ("decimals", "option<u8>")],
+        accounts: &[
+            LAUNCH_GATE,
+            ("mint", true, false, None),
+            ("token_account", true, false, None),
+            ("timelock_authority", false, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", true, true, None),
+            ("token_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "DistributeChunk",
+        fields: &[("start_index", "u16"), ("cursor_bump", "u8"), ("allow_zero_amounts", "bool")],
+        accounts: &[
+            LAUNCH_GATE,
+            ("mint", true, false, None),
+            ("distribution_list", false, false, None),
+            ("distribution_cursor", true, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", true, true, None),
+            ("token_program", false, false, None),
+            ("system_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "VerifyAndFinalize",
+        fields: &[("decimals", "option<u8>")],
+        accounts: &[
+            LAUNCH_GATE,
+            ("mint", true, false, None),
+            ("token_account", true, false, None),
+            ("mint_authority", false, true, None),
+            ("payer", false, true, None),
+            ("token_program", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "SetConfigAdmin",
+        fields: &[("admin", "pubkey"), ("bump", "u8")],
+        accounts: &[
+            ("config", true, false, None),
+            ("admin", true, true, None),
+            ("system_program", false, false, None),
+            ("program_data", false, false, None),
+        ],
+    },
+    InstructionSchema {
+        name: "UpdateConfig",
+        fields: &[("settings", "ConfigSettings")],
+        accounts: &[
+            ("config", true, false, None),
+            ("admin", false, true, None),
+            ("oracle_program", false, false, Some("oracle_program is set")),
+            ("timelock_program", false, false, Some("timelock_program is set")),
+        ],
+    },
+];
+
+// Argument structs referenced by name from the instruction fields
+#[cfg(feature = "serde")]
+const TYPE_SCHEMAS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "InitializeArgs",
+        &[
+            ("decimals", "option<u8>"),
+            ("thaw", "option<ThawArgs>"),
+            ("receipt", "option<ReceiptArgs>"),
+            ("verify_transaction", "bool"),
+            ("co_signer", "option<pubkey>"),
+            ("freeze_destination", "bool"),
+            ("event", "option<EventArgs>"),
+            ("authorizer", "option<pubkey>"),
+            ("capabilities", "u32"),
+        ],
+    ),
+    ("EventArgs", &[("bump", "u8")]),
+    (
+        "LaunchEvent",
+        &[("mint", "pubkey"), ("creator", "pubkey"), ("supply", "u64"), ("decimals", "u8")],
+    ),
+    ("ThawArgs", &[("refreeze", "bool")]),
+    ("PoolArgs", &[("data", "bytes")]),
+    ("ReceiptArgs", &[("nonce", "u64"), ("bump", "u8")]),
+    ("DistributionEntry", &[("destination", "pubkey"), ("amount", "u64")]),
+    ("OwnerAllocation", &[("owner", "pubkey"), ("amount", "u64")]),
+    ("AllocationBucket", &[("label", "[u8; 8]"), ("bps", "u16")]),
+    (
+        "ConfidentialTransferConfig",
+        &[
+            ("authority", "option<pubkey>"),
+            ("auto_approve_new_accounts", "bool"),
+            ("auditor_elgamal_pubkey", "option<[u8; 32]>"),
+        ],
+    ),
+    (
+        "ConfigSettings",
+        &[
+            ("launcher_allowlist_enabled", "bool"),
+            ("launch_fee_lamports", "u64"),
+            ("fee_treasury", "pubkey"),
+            ("oracle_program", "pubkey"),
+            ("timelock_program", "pubkey"),
+        ],
+    ),
+    (
+        "VestingSchedule",
+        &[("curve", "u8"), ("start", "i64"), ("cliff", "i64"), ("steps", "u16")],
+    ),
+];
+
+// Position of the instruction's description in `INSTRUCTION_SCHEMAS`
+#[cfg(feature = "serde")]
+fn schema_index(instruction: &TokenInstruction) -> usize {
+    match instruction {
+        TokenInstruction::InitializeFixedSupply(_) => 0,
+        TokenInstruction::TreasuryAndLiquidity(_) => 1,
+        TokenInstruction::CreateMetadata(_) => 2,
+        TokenInstruction::RemainingMintable => 3,
+        TokenInstruction::BatchInitialize(_) => 4,
+        TokenInstruction::SetFreezeAuthority => 5,
+        TokenInstruction::CreateDistributionList(_) => 6,
+        TokenInstruction::WriteDistributionList(_) => 7,
+        TokenInstruction::Distribute(_) => 8,
+        TokenInstruction::InitializeGroup(_) => 9,
+        TokenInstruction::AddGroupMember => 10,
+        TokenInstruction::VerifyGroupMembership => 11,
+        TokenInstruction::LockMetadata => 12,
+        TokenInstruction::CreateMint(_) => 13,
+        TokenInstruction::RevokeAuthority(_) => 14,
+        TokenInstruction::InitializeWithPdaAuthority(_) => 15,
+        TokenInstruction::StartChunkedMint(_) => 16,
+        TokenInstruction::MintChunk(_) => 17,
+        TokenInstruction::CheckPolicy(_) => 18,
+        TokenInstruction::InitializeWithLock(_) => 19,
+        TokenInstruction::ReleaseLocked => 20,
+        TokenInstruction::LaunchAll(_) => 21,
+        TokenInstruction::EmitEvent(_) => 22,
+        TokenInstruction::VerifyCreator(_) => 23,
+        TokenInstruction::DistributeToOwners(_) => 24,
+        TokenInstruction::VerifyMetadata(_) => 25,
+        TokenInstruction::InitializeWithAllocationPlan(_) => 26,
+        TokenInstruction::Rescue(_) => 27,
+        TokenInstruction::GetLaunchStats => 28,
+        TokenInstruction::InitializeWithUiSupply(_) => 29,
+        TokenInstruction::BatchRevoke(_) => 30,
+        TokenInstruction::WritePackedDistributionList(_) => 31,
+        TokenInstruction::IsAdmin(_) => 32,
+        TokenInstruction::InitializeWithGovernance(_) => 33,
+        TokenInstruction::RecordSupplyCap(_) => 34,
+        TokenInstruction::GetSupplyCap => 35,
+        TokenInstruction::GetValuation(_) => 36,
+        TokenInstruction::InitializeFromSeed(_) => 37,
+        TokenInstruction::AddLauncher(_) => 38,
+        TokenInstruction::RemoveLauncher(_) => 39,
+        TokenInstruction::VerifySupplyHash(_) => 40,
+        TokenInstruction::Cleanup => 41,
+        TokenInstruction::RevokeAuthorities(_) => 42,
+        TokenInstruction::TestMint(_) => 43,
+        TokenInstruction::ResetTestMint => 44,
+        TokenInstruction::InitializeIntoMultisig(_) => 45,
+        TokenInstruction::ValidateOnly(_) => 46,
+        TokenInstruction::WriteAdditionalMetadata(_) => 47,
+        TokenInstruction::VerifyReceiptIntegrity => 48,
+        TokenInstruction::InitializeWithTimelockFreeze(_) => 49,
+        TokenInstruction::DistributeChunk(_) => 50,
+        TokenInstruction::VerifyAndFinalize(_) => 51,
+        TokenInstruction::SetConfigAdmin(_) => 52,
+        TokenInstruction::UpdateConfig(_) => 53,
+    }
+}
+
+/// Returns the schema name of an instruction, e.g. for labelling decoded transactions
+#[cfg(feature = "serde")]
+pub fn instruction_name(instruction: &TokenInstruction) -> &'static str {
+    INSTRUCTION_SCHEMAS[schema_index(instruction)].name
+}
+
+/// Describes every instruction's discriminator, fields and account layout as JSON
+/// Fields are listed in Borsh order, and accounts with a `condition` are only present when it
+/// holds. Empty instruction data is additionally accepted as `InitializeFixedSupply` with
+/// default arguments.
+#[cfg(feature = "serde")]
+pub fn instruction_schema() -> serde_json::Value {
+    use serde_json::json;
+
+    let fields = |fields: &[(&str, &str)]| -> Vec<serde_json::Value> {
+        fields
+            .iter()
+            .map(|(name, ty)| json!({ "name": name, "type": ty }))
+            .collect()
+    };
+    let instructions: Vec<_> = INSTRUCTION_SCHEMAS
+        .iter()
+        .enumerate()
+        .map(|(discriminator, schema)| {
+            let accounts: Vec<_> = schema
+                .accounts
+                .iter()
+                .map(|(name, writable, signer, condition)| {
+                    json!({
+                        "name": name,
+                        "writable": writable,
+                        "signer": signer,
+                        "condition": condition,
+                    })
+                })
+                .collect();
+            json!({
+                "name": schema.name,
+                "discriminator": discriminator,
+                "fields": fields(schema.fields),
+                "accounts": accounts,
+            })
+        })
+        .collect();
+    let types: Vec<_> = TYPE_SCHEMAS
+        .iter()
+        .map(|(name, type_fields)| json!({ "name": name, "fields": fields(type_fields) }))
+        .collect();
+
+    json!({
+        "name": "fixed_supply_token",
+        "encoding": "borsh",
+        "instructions": instructions,
+        "types": types,
+    })
+}
+
+/// Fetches `mint` and confirms the launch finalized it with `expected_supply` base units
+/// Returns `Ok(false)` when the mint doesn't exist, isn't an initialized token mint, holds a
+/// different supply or still has a mint authority; RPC failures are returned as errors.
+#[cfg(feature = "rpc")]
+pub async fn verify_launch(
+    rpc: &RpcClient,
+    mint: &Pubkey,
+    expected_supply: u64,
+) -> Result<bool, ClientError> {
+    let account = match rpc.get_account_with_commitment(mint, rpc.commitment()).await?.value {
+        Some(account) => account,
+        None => return Ok(false),
+    };
+    if account.owner != spl_token::id() && account.owner != spl_token_2022::id() {
+        return Ok(false);
+    }
+    let mint_data = match StateWithExtensions::<Mint>::unpack(&account.data) {
+        Ok(state) => state.base,
+        Err(_) => return Ok(false),
+    };
+
+    Ok(mint_data.is_initialized
+        && mint_data.supply == expected_supply
+        && mint_data.mint_authority.is_none())
+}
+
+/// How long `await_revocation` waits between polls of the mint
+#[cfg(feature = "rpc")]
+const REVOCATION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Why `await_revocation` stopped waiting before the revocation became visible
+#[cfg(feature = "rpc")]
+#[derive(Debug)]
+pub enum RevocationWaitError {
+    /// The mint authority was still set, or the mint not visible yet, when the timeout elapsed
+    TimedOut,
+    /// The account exists but is not an initialized token mint, so it can never be revoked
+    NotAMint,
+    /// The timeout elapsed while the last poll was failing with this RPC error
+    Rpc(Box<ClientError>),
+}
+
+/// Polls `mint` at the RPC client's commitment until its mint authority is revoked
+/// A missing mint or a still-set authority is treated as not finalized yet, and transient RPC
+/// errors are retried, so the call only fails once `timeout` elapses or the account turns out
+/// not to be a mint.
+#[cfg(feature = "rpc")]
+pub async fn await_revocation(
+    rpc: &RpcClient,
+    mint: &Pubkey,
+    timeout: Duration,
+) -> Result<(), RevocationWaitError> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let error = match rpc.get_account_with_commitment(mint, rpc.commitment()).await {
+            Ok(response) => {
+                if let Some(account) = response.value {
+                    if account.owner != spl_token::id() && account.owner != spl_token_2022::id() {
+                        return Err(RevocationWaitError::NotAMint);
+                    }
+                    let mint_data = match StateWithExtensions::<Mint>::unpack(&account.data) {
+                        Ok(state) => state.base,
+                        Err(_) => return Err(RevocationWaitError::NotAMint),
+                    };
+                    if mint_data.mint_authority.is_none() {
+                        return Ok(());
+                    }
+                }
+                None
+            }
+            Err(e) => Some(e),
+        };
+
+        if Instant::now() + REVOCATION_POLL_INTERVAL > deadline {
+            return Err(error.map_or(RevocationWaitError::TimedOut, |e| {
+                RevocationWaitError::Rpc(Box::new(e))
+            }));
+        }
+        tokio::time::sleep(REVOCATION_POLL_INTERVAL).await;
+    }
+}
+
+/// Why `ensure_mint_address_unused` rejected a mint address
+#[cfg(feature = "rpc")]
+#[derive(Debug)]
+pub enum MintAddressError {
+    /// An account already exists at the address, so creating the mint there would fail
+    InUse { owner: Pubkey, lamports: u64 },
+    /// The RPC request failed, so the address could not be checked
+    Rpc(ClientError),
+}
+
+#[cfg(feature = "rpc")]
+impl From<ClientError> for MintAddressError {
+    fn from(e: ClientError) -> Self {
+        MintAddressError::Rpc(e)
+    }
+}
+
+/// Confirms that no account exists at `mint` yet, before building a `CreateMint` or `LaunchAll`
+/// transaction for it
+/// The system program refuses to create an account at an address that already holds lamports,
+/// so this catches a reused mint keypair before the transaction is signed and sent.
+#[cfg(feature = "rpc")]
+pub async fn ensure_mint_address_unused(
+    rpc: &RpcClient,
+    mint: &Pubkey,
+) -> Result<(), MintAddressError> {
+    match rpc.get_account_with_commitment(mint, rpc.commitment()).await?.value {
+        Some(account) => {
+            Err(MintAddressError::InUse { owner: account.owner, lamports: account.lamports })
+        }
+        None => Ok(()),
+    }
+}
+
+/// Fetches the receipt of the launch of `mint` with `nonce` and confirms `creator` ran it
+/// Returns `Ok(false)` when there is no such receipt or it names another creator.
+#[cfg(feature = "rpc")]
+pub async fn verify_launch_creator(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    nonce: u64,
+    creator: &Pubkey,
+) -> Result<bool, ClientError> {
+    let (receipt_address, _) = find_receipt_address(program_id, mint, nonce);
+    let account = match rpc
+        .get_account_with_commitment(&receipt_address, rpc.commitment())
+        .await?
+        .value
+    {
+        Some(account) if account.owner == *program_id => account,
+        _ => return Ok(false),
+    };
+    let receipt = match LaunchReceipt::try_from_slice(&account.data) {
+        Ok(receipt) => receipt,
+        Err(_) => return Ok(false),
+    };
+
+    Ok(receipt.account_type == AccountType::LaunchReceipt
+        && receipt.mint == *mint
+        && receipt.creator == *creator)
+}
+
+/// Why `simulate_and_launch` didn't submit or land a launch
+#[cfg(feature = "rpc")]
+#[derive(Debug)]
+pub enum LaunchError {
+    /// The simulated launch failed with one of this program's errors
+    Program(CustomError),
+    /// The simulated launch failed for another reason, with the simulation logs
+    Simulation(TransactionError, Vec<String>),
+    /// An RPC request failed, including sending the launch after a clean simulation
+    Rpc(ClientError),
+}
+
+#[cfg(feature = "rpc")]
+impl From<ClientError> for LaunchError {
+    fn from(e: ClientError) -> Self {
+        LaunchError::Rpc(e)
+    }
+}
+
+/// Simulates the launch transaction and only submits it when the simulation succeeds
+/// The simulation runs every check of the real launch without spending a transaction fee, so
+/// a doomed launch comes back as `LaunchError::Program` with the specific program error.
+#[cfg(feature = "rpc")]
+pub async fn simulate_and_launch<T: Signers + ?Sized>(
+    rpc: &RpcClient,
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    signers: &T,
+) -> Result<Signature, LaunchError> {
+    let blockhash = rpc.get_latest_blockhash().await?;
+    let transaction =
+        Transaction::new_signed_with_payer(instructions, Some(payer), signers, blockhash);
+
+    let simulation = rpc.simulate_transaction(&transaction).await?.value;
+    if let Some(err) = simulation.err {
+        if let TransactionError::InstructionError(_, InstructionError::Custom(code)) = &err {
+            if let Some(custom) = CustomError::from_code(*code) {
+                return Err(LaunchError::Program(custom));
+            }
+        }
+        return Err(LaunchError::Simulation(err, simulation.logs.unwrap_or_default()));
+    }
+
+    Ok(rpc.send_and_confirm_transaction(&transaction).await?)
+}
+
+/// Why `distribution_message_v0` couldn't build the message
+#[cfg(feature = "rpc")]
+#[derive(Debug)]
+pub enum LookupTableError {
+    /// No address lookup table exists at the given address
+    InvalidTable(Pubkey),
+    /// The instructions don't compile into a v0 message, e.g. they reference too many accounts
+    Compile(CompileError),
+    /// An RPC request failed
+    Rpc(ClientError),
+}
+
+#[cfg(feature = "rpc")]
+impl From<ClientError> for LookupTableError {
+    fn from(e: ClientError) -> Self {
+        LookupTableError::Rpc(e)
+    }
+}
+
+/// Builds an unsigned v0 message for `Distribute` or `DistributeToOwners` instructions whose
+/// recipient accounts are loaded through the pre-created `lookup_table`
+/// A looked up account takes one byte of the transaction instead of 32, so far more recipients
+/// fit than in a legacy transaction. Signers and program ids stay in the static keys, whatever
+/// the table holds. Sign the result with `VersionedTransaction::try_new`.
+#[cfg(feature = "rpc")]
+pub async fn distribution_message_v0(
+    rpc: &RpcClient,
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    lookup_table: &Pubkey,
+) -> Result<VersionedMessage, LookupTableError> {
+    let account = rpc
+        .get_account_with_commitment(lookup_table, rpc.commitment())
+        .await?
+        .value
+        .filter(|account| account.owner == address_lookup_table::program::id())
+        .ok_or(LookupTableError::InvalidTable(*lookup_table))?;
+    let addresses = AddressLookupTable::deserialize(&account.data)
+        .map_err(|_| LookupTableError::InvalidTable(*lookup_table))?
+        .addresses
+        .to_vec();
+    let table = AddressLookupTableAccount { key: *lookup_table, addresses };
+
+    let blockhash = rpc.get_latest_blockhash().await?;
+    let message = v0::Message::try_compile(payer, instructions, &[table], blockhash)
+        .map_err(LookupTableError::Compile)?;
+    Ok(VersionedMessage::V0(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ERROR_CODE_OFFSET;
+
+    #[test]
+    fn decode_custom_error_undoes_the_offset() {
+        let error = ProgramError::from(CustomError::MintAuthorityNotRevoked);
+        assert_eq!(error, ProgramError::Custom(6002 + ERROR_CODE_OFFSET));
+        let decoded = decode_custom_error(&error).map(CustomError::code);
+        assert_eq!(decoded, Some(CustomError::MintAuthorityNotRevoked.code()));
+        assert!(decode_custom_error(&ProgramError::InvalidArgument).is_none());
+        assert!(decode_custom_error(&ProgramError::Custom(1)).is_none());
+    }
+
+    #[test]
+    fn explain_error_describes_our_codes_and_falls_back_for_others() {
+        let error = ProgramError::from(CustomError::FreezeAuthorityConflict);
+        assert_eq!(explain_error(&error), describe(CustomError::FreezeAuthorityConflict));
+        assert_eq!(
+            explain_error(&ProgramError::InvalidArgument),
+            ProgramError::InvalidArgument.to_string()
+        );
+        assert_eq!(explain_error(&ProgramError::Custom(1)), ProgramError::Custom(1).to_string());
+    }
+}
@@ -0,0 +1,222 @@
+// Custom error codes returned by the fixed supply token program
+
+use solana_program::program_error::ProgramError;
+
+use crate::ERROR_CODE_OFFSET;
+
+/// Custom error codes for specific validation failures
+#[derive(Debug, Copy, Clone)]
+pub enum CustomError {
+    InvalidMintState = 6000,        // Mint account is not in the expected initial state
+    TokenAccountNotEmpty,           // Token account already contains tokens or has delegates
+    MintAuthorityNotRevoked,       // Mint authority was not successfully revoked
+    TokenAccountOwnerMismatch,     // Token account owner doesn't match the payer
+    MintAuthorityMismatch,         // Mint authority doesn't match the payer
+    PreExistingTokensFound,        // Candidate token accounts already hold tokens for this mint
+    InvalidAuthorityAccount,       // Authority is a well-known program or sysvar id
+    InvalidCreatorShares,          // Metadata creator shares don't sum to 100
+    InvalidPdaBump,                // Provided bump doesn't derive the expected PDA
+    DecimalsNotAllowed,            // Configured decimals are not in the allowed set
+    UnsupportedExtension,          // Token account uses an extension we can't mint into
+    UnexpectedBalanceDelta,        // Destination balance didn't grow by the minted amount
+    InvalidMultisig,               // Account is not a valid SPL multisig
+    TokenAccountWrongMint,         // Token account belongs to a different mint
+    DuplicateNonce,                // A launch receipt with this nonce already exists
+    DistributionTotalMismatch,     // Distribution amounts don't add up to the total supply
+    ZeroAmountRecipient,           // A recipient would receive zero tokens
+    DisallowedInstructionInTx,     // Transaction invokes a program outside the whitelist
+    GroupFull,                     // Token group already holds its maximum number of members
+    NotGroupMember,                // Mint is not a member of the expected token group
+    MetadataUpdateAuthorityNotCleared,// Metadata update authority was not cleared
+    AliasedAccounts,               // The same account was passed in slots that must differ
+    FreezeAuthorityMismatch,       // Signer is not the mint's current freeze authority
+    FreezeAuthorityNotRevoked,     // Freeze authority was not successfully revoked
+    MissingCoSigner,               // The required co-signer did not sign the launch
+    FreezeFailed,                  // Destination account is not frozen after freezing it
+    DecimalsTooLarge,              // Decimals exceed the largest value a u64 amount can represent
+    ChunkExceedsTarget,            // Chunk would mint more than the chunked launch target
+    MintAlreadyFinalized,          // Mint already launched: authority revoked with a nonzero supply
+    CannotLockEntireSupply,        // Locked amount must leave part of the supply circulating
+    SupplyStillLocked,             // No locked supply has vested since the last release
+    AuthorityTransferFailed,       // Authority was not handed to the intended new key
+    TokenAccountWrongSize,         // Token account data has the wrong length for its program
+    MintWrongSize,                 // Mint account data has the wrong length for its program
+    AuthorizationMintMismatch,     // Launch authorization was signed for a different mint
+    CannotUseNativeMint,           // The native SOL mint can't be launched as a fixed-supply token
+    SupplyBelowMinimum,            // Configured supply is below the platform minimum
+    CreatorMismatch,               // Launch receipt names a different creator
+    RevocationCpiFailed,           // The set_authority CPI revoking the mint authority failed
+    SupplyOverflow,                // Minting the amount would overflow the mint's u64 supply
+    MetadataMismatch,              // Metadata name, symbol or uri differs from the expected value
+    MetadataNotFound,              // The mint has no Metaplex metadata account
+    DestinationOwnerNotAllowed,    // Destination owner is not an allowed program or owned by one
+    AllocationPlanSumMismatch,     // Allocation plan bucket shares don't sum to 10000 basis points
+    OracleNotApproved,             // The verification oracle has not approved this launch
+    AdminMismatch,                 // Signer is not the admin stored in the program's config
+    RescueExceedsSurplus,          // Rescue would dip into tokens that are still locked
+    InsufficientRentFunds,         // Payer can't fund the rent of the accounts being created
+    DuplicateDestination,          // The same destination appears more than once in a distribution
+    FreezeAuthorityTampered,       // Preserved freeze authority changed during the launch
+    ImpracticalDecimalsSupplyCombo,// Decimals are too high for the supply to be practical
+    ConfidentialConfigInvalid,     // Confidential transfer mint configuration is invalid
+    GovernanceTransferFailed,      // Mint authority was not handed to the governance authority
+    NameTooLong,                   // Metadata name exceeds the Metaplex limit
+    SymbolTooLong,                 // Metadata symbol exceeds the Metaplex limit
+    UriTooLong,                    // Metadata uri exceeds the Metaplex limit
+    MintSupplyNotZero,             // Mint already has a supply before the launch
+    ValuationOverflow,             // Fully diluted value doesn't fit in a u64
+    SeedDerivationFailed,          // Seed derived degenerate launch parameters
+    LauncherNotAllowlisted,        // Mint authority is not on the launcher allowlist
+    SupplyHashMismatch,            // Finalized supply doesn't match the off-chain commitment
+    CannotCleanupBeforeFinalize,   // Transient PDAs can only be closed once the mint is final
+    ExtensionAuthorityNotRevoked,  // A Token-2022 extension authority is still set after revoking
+    IncompleteSupplyBeforeRevoke,  // Mint authority revocation attempted before the full supply
+    CollectionVerificationFailed,  // Token could not be verified into the Metaplex collection
+    InsufficientForLaunchFee,      // Payer can't cover the launch fee on top of the rent
+    TokenStandardDecimalsMismatch, // Metadata token standard contradicts the mint's decimals
+    AuthorityNotSigner,            // Mint authority didn't sign the transaction
+    PayerNotSigner,                // Payer didn't sign the transaction
+    ProgramStillUpgradeable,       // This program still has an upgrade authority
+    InvalidMultisigTreasury,       // Treasury isn't owned by a multisig that needs several signers
+    ValidationFailed,              // Dry run found failing checks, see the returned report
+    MetadataTooLarge,              // On-mint metadata outgrows what one instruction can reallocate
+    ReceiptCorrupted,              // Launch receipt doesn't match its stored checksum
+    RecipientCountMismatch,        // Declared recipient count differs from the accounts passed
+    TimelockTransferFailed,        // Freeze authority was not handed to the timelock authority
+    DistributionAlreadyComplete,   // Distribution cursor already reached the end of the list
+    FreezeAuthorityConflict,       // Mint has a freeze authority the launch mode wouldn't keep
+}
+
+impl CustomError {
+    /// The `ProgramError::Custom` code this error is returned with, shifted by `ERROR_CODE_OFFSET`
+    pub fn code(self) -> u32 {
+        self as u32 + ERROR_CODE_OFFSET
+    }
+
+    /// Maps a raw `ProgramError::Custom` code back to the matching error, if it is one of ours
+    /// The code is expected to carry `ERROR_CODE_OFFSET`, as returned by the program.
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code.checked_sub(ERROR_CODE_OFFSET)? {
+            6000 => Some(Self::InvalidMintState),
+            6001 => Some(Self::TokenAccountNotEmpty),
+            6002 => Some(Self::MintAuthorityNotRevoked),
+            6003 => Some(Self::TokenAccountOwnerMismatch),
+            6004 => Some(Self::MintAuthorityMismatch),
+            6005 => Some(Self::PreExistingTokensFound),
+            6006 => Some(Self::InvalidAuthorityAccount),
+            6007 => Some(Self::InvalidCreatorShares),
+            6008 => Some(Self::InvalidPdaBump),
+            6009 => Some(Self::DecimalsNotAllowed),
+            6010 => Some(Self::UnsupportedExtension),
+            6011 => Some(Self::UnexpectedBalanceDelta),
+            6012 => Some(Self::InvalidMultisig),
+            6013 => Some(Self::TokenAccountWrongMint),
+            6014 => Some(Self::DuplicateNonce),
+            6015 => Some(Self::DistributionTotalMismatch),
+            6016 => Some(Self::ZeroAmountRecipient),
+            6017 => Some(Self::DisallowedInstructionInTx),
+            6018 => Some(Self::GroupFull),
+            6019 => Some(Self::NotGroupMember),
+            6020 => Some(Self::MetadataUpdateAuthorityNotCleared),
+            6021 => Some(Self::AliasedAccounts),
+            6022 => Some(Self::FreezeAuthorityMismatch),
+            6023 => Some(Self::FreezeAuthorityNotRevoked),
+            6024 => Some(Self::MissingCoSigner),
+            6025 => Some(Self::FreezeFailed),
+            6026 => Some(Self::DecimalsTooLarge),
+            6027 => Some(Self::ChunkExceedsTarget),
+            6028 => Some(Self::MintAlreadyFinalized),
+            6029 => Some(Self::CannotLockEntireSupply),
+            6030 => Some(Self::SupplyStillLocked),
+            6031 => Some(Self::AuthorityTransferFailed),
+            6032 => Some(Self::TokenAccountWrongSize),
+            6033 => Some(Self::MintWrongSize),
+            6034 => Some(Self::AuthorizationMintMismatch),
+            6035 => Some(Self::CannotUseNativeMint),
+            6036 => Some(Self::SupplyBelowMinimum),
+            6037 => Some(Self::CreatorMismatch),
+            6038 => Some(Self::RevocationCpiFailed),
+            6039 => Some(Self::SupplyOverflow),
+            6040 => Some(Self::MetadataMismatch),
+            6041 => Some(Self::MetadataNotFound),
+            6042 => Some(Self::DestinationOwnerNotAllowed),
+            6043 => Some(Self::AllocationPlanSumMismatch),
+            6044 => Some(Self::OracleNotApproved),
+            6045 => Some(Self::AdminMismatch),
+            6046 => Some(Self::RescueExceedsSurplus),
+            6047 => Some(Self::InsufficientRentFunds),
+            6048 => Some(Self::DuplicateDestination),
+            6049 => Some(Self::FreezeAuthorityTampered),
+            6050 => Some(Self::ImpracticalDecimalsSupplyCombo),
+            6051 => Some(Self::ConfidentialConfigInvalid),
+            6052 => Some(Self::GovernanceTransferFailed),
+            6053 => Some(Self::NameTooLong),
+            6054 => Some(Self::SymbolTooLong),
+            6055 => Some(Self::UriTooLong),
+            6056 => Some(Self::MintSupplyNotZero),
+            6057 => Some(Self::ValuationOverflow),
+            6058 => Some(Self::SeedDerivationFailed),
+            6059 => Some(Self::LauncherNotAllowlisted),
+            6060 => Some(Self::SupplyHashMismatch),
+            6061 => Some(Self::CannotCleanupBeforeFinalize),
+            6062 => Some(Self::ExtensionAuthorityNotRevoked),
+            6063 => Some(Self::IncompleteSupplyBeforeRevoke),
+            6064 => Some(Self::CollectionVerificationFailed),
+            6065 => Some(Self::InsufficientForLaunchFee),
+            6066 => Some(Self::TokenStandardDecimalsMismatch),
+            6067 => Some(Self::AuthorityNotSigner),
+            6068 => Some(Self::PayerNotSigner),
+            6069 => Some(Self::ProgramStillUpgradeable),
+            6070 => Some(Self::InvalidMultisigTreasury),
+            6071 => Some(Self::ValidationFailed),
+            6072 => Some(Self::MetadataTooLarge),
+            6073 => Some(Self::ReceiptCorrupted),
+            6074 => Some(Self::RecipientCountMismatch),
+            6075 => Some(Self::TimelockTransferFailed),
+            6076 => Some(Self::DistributionAlreadyComplete),
+            6077 => Some(Self::FreezeAuthorityConflict),
+            _ => None,
+        }
+    }
+}
+
+impl From<CustomError> for ProgramError {
+    fn from(e: CustomError) -> Self {
+        ProgramError::Custom(e.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Codes as numbered in the enum, before `ERROR_CODE_OFFSET` is applied
+    const FIRST_CODE: u32 = CustomError::InvalidMintState as u32;
+    const LAST_CODE: u32 = CustomError::FreezeAuthorityConflict as u32;
+
+    #[test]
+    fn codes_are_shifted_by_the_offset() {
+        let error = CustomError::InvalidMintState;
+        assert_eq!(error.code(), 6000 + ERROR_CODE_OFFSET);
+        assert_eq!(ProgramError::from(error), ProgramError::Custom(6000 + ERROR_CODE_OFFSET));
+    }
+
+    #[test]
+    fn from_code_round_trips_every_error() {
+        for code in FIRST_CODE..=LAST_CODE {
+            let error = CustomError::from_code(code + ERROR_CODE_OFFSET)
+                .unwrap_or_else(|| panic!("no error for code {}", code));
+            assert_eq!(error as u32, code);
+            assert_eq!(error.code(), code + ERROR_CODE_OFFSET);
+        }
+    }
+
+    #[test]
+    fn from_code_rejects_codes_outside_the_range() {
+        assert!(CustomError::from_code(FIRST_CODE + ERROR_CODE_OFFSET - 1).is_none());
+        assert!(CustomError::from_code(LAST_CODE + ERROR_CODE_OFFSET + 1).is_none());
+        // Codes below the offset belong to another program and must not underflow
+        assert!(CustomError::from_code(0).is_none());
+        assert!(CustomError::from_code(u32::MAX).is_none());
+    }
+}
@@ -0,0 +1,1886 @@
+// Instruction definitions for the fixed supply token program
+// Instruction data is Borsh-encoded: a one-byte variant tag followed by the variant's arguments.
+
+use std::io;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    program::MAX_RETURN_DATA, program_error::ProgramError, program_option::COption, pubkey::Pubkey,
+};
+use spl_token_2022::instruction::AuthorityType;
+
+use crate::{
+    state::{
+        ConfigSettings, DistributionEntry, VestingSchedule, MAX_DISTRIBUTION_RECIPIENTS,
+        MAX_LIST_RECIPIENTS,
+    },
+    MAX_DECIMALS,
+};
+
+/// Arguments for the fixed supply initialization
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct InitializeArgs {
+    /// Decimals to launch with instead of the default, must be in `ALLOWED_DECIMALS`
+    pub decimals: Option<u8>,
+    /// Deliver the supply into a frozen destination by thawing it with the freeze authority
+    pub thaw: Option<ThawArgs>,
+    /// Record the launch in a receipt PDA derived from the mint and a client-supplied nonce
+    pub receipt: Option<ReceiptArgs>,
+    /// Reject the launch if the transaction invokes any program outside the launch whitelist
+    pub verify_transaction: bool,
+    /// Second key that must co-sign the launch, distinct from the mint authority
+    pub co_signer: Option<Pubkey>,
+    /// Freeze the destination with the freeze authority once the supply has been minted into it
+    pub freeze_destination: bool,
+    /// Emit a `LaunchEvent` through an `EmitEvent` self-invocation for event-parsing indexers
+    pub event: Option<EventArgs>,
+    /// Key whose Ed25519 signature over `launch_authorization_message(mint)` must be verified by
+    /// the instruction right before the launch, binding the authorization to this mint
+    pub authorizer: Option<Pubkey>,
+    /// Optional launch steps as `CAPABILITY_*` bits, unknown bits must be zero
+    /// Takes the place of four formerly reserved bytes, so zeroed data keeps decoding the same.
+    pub capabilities: u32,
+}
+
+/// Prefix of the message an `InitializeArgs::authorizer` signs, followed by the mint pubkey
+pub const LAUNCH_AUTHORIZATION_PREFIX: &[u8] = b"fixed-supply:authorize-launch:";
+
+/// `InitializeArgs::capabilities` bit: revoke the mint's freeze authority once the supply is
+/// minted, which needs the freeze authority to sign
+pub const CAPABILITY_REVOKE_FREEZE: u32 = 1 << 0;
+/// `InitializeArgs::capabilities` bit: create the payer's associated token account as the
+/// destination before minting into it
+pub const CAPABILITY_CREATE_ATA: u32 = 1 << 1;
+/// `InitializeArgs::capabilities` bit: mint into a token account owned by a protocol, i.e. an
+/// `ALLOWED_DESTINATION_PROGRAMS` program or an account one of them owns, instead of the payer
+pub const CAPABILITY_PROTOCOL_DESTINATION: u32 = 1 << 2;
+/// `InitializeArgs::capabilities` bit: trust the token program's revocation and skip re-reading
+/// the mint afterwards, saving compute at the cost of the final safety check
+pub const CAPABILITY_SKIP_FINAL_VERIFICATION: u32 = 1 << 4;
+/// `InitializeArgs::capabilities` bit: only launch while this program is immutable, i.e. its
+/// ProgramData account has no upgrade authority, so the launch logic can't change afterwards
+pub const CAPABILITY_IMMUTABLE_PROGRAM: u32 = 1 << 7;
+/// Every capability bit this version of the program understands
+pub const KNOWN_CAPABILITIES: u32 = CAPABILITY_REVOKE_FREEZE
+    | CAPABILITY_CREATE_ATA
+    | CAPABILITY_PROTOCOL_DESTINATION
+    | CAPABILITY_SKIP_FINAL_VERIFICATION
+    | CAPABILITY_IMMUTABLE_PROGRAM;
+
+/// Options for minting into a frozen destination account in regulated launches
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ThawArgs {
+    /// Freeze the destination again once the supply has been minted into it
+    pub refreeze: bool,
+}
+
+/// Options for writing a launch receipt
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReceiptArgs {
+    /// Nonce making the receipt address unique, a receipt can only be created once per nonce
+    pub nonce: u64,
+    /// Bump of the receipt PDA [RECEIPT_SEED, mint, nonce]
+    pub bump: u8,
+}
+
+/// Options for emitting a launch event
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventArgs {
+    /// Bump of the event authority PDA [EVENT_AUTHORITY_SEED]
+    pub bump: u8,
+}
+
+/// Launch result carried by an `EmitEvent` self-invocation
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LaunchEvent {
+    /// The launched mint
+    pub mint: Pubkey,
+    /// The mint authority that ran the launch
+    pub creator: Pubkey,
+    /// Final supply, in base units
+    pub supply: u64,
+    /// Mint decimals
+    pub decimals: u8,
+}
+
+/// Arguments of the `EmitEvent` self-invocation
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct EmitEventArgs {
+    /// Bump of the event authority PDA [EVENT_AUTHORITY_SEED]
+    pub bump: u8,
+    /// The event being emitted
+    pub event: LaunchEvent,
+}
+
+impl InitializeArgs {
+    /// Whether the given `CAPABILITY_*` bit is set
+    pub fn has_capability(&self, capability: u32) -> bool {
+        self.capabilities & capability != 0
+    }
+
+    /// Whether the launch needs the mint's freeze authority to sign
+    pub fn needs_freeze_authority(&self) -> bool {
+        self.thaw.is_some()
+            || self.freeze_destination
+            || self.has_capability(CAPABILITY_REVOKE_FREEZE)
+    }
+}
+
+/// Maximum number of mints a single `BatchInitialize` may launch, bounded by the compute budget
+pub const MAX_BATCH_MINTS: usize = 4;
+
+/// Arguments for launching several independent mints in one instruction
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BatchInitializeArgs {
+    /// Per-mint arguments, in the same order as the (mint, token account) pairs
+    pub mints: Vec<InitializeArgs>,
+}
+
+/// Maximum number of mints a single `BatchRevoke` may lock down, bounded by the compute budget
+pub const MAX_BATCH_REVOKE_MINTS: u8 = 8;
+
+/// Arguments for revoking the mint authority of several mints in one instruction
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BatchRevokeArgs {
+    /// Number of (mint, mint authority) pairs passed after the token program
+    pub mints: u8,
+}
+
+/// Maximum number of authorities a single `RevokeAuthorities` sweep may revoke, one per mint
+/// authority type
+pub const MAX_REVOKED_AUTHORITIES: usize = 13;
+
+/// Arguments for revoking several authorities of one mint in one instruction
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RevokeAuthoritiesArgs {
+    /// Authority types in the Token-2022 encoding, in revocation order and without duplicates
+    /// The account authority types (2 and 3) are rejected since they don't apply to mints.
+    pub authority_types: Vec<u8>,
+}
+
+/// Basis points denominator, 10000 bps = 100%
+pub const MAX_BASIS_POINTS: u16 = 10_000;
+
+/// Arguments for splitting the supply between a treasury and a liquidity account
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TreasuryAndLiquidityArgs {
+    /// Share of the total supply minted to the treasury, in basis points (exclusive of 0 and 10000)
+    pub treasury_bps: u16,
+    /// Seed a liquidity pool through the configured AMM program once the supply is finalized
+    pub pool: Option<PoolArgs>,
+}
+
+/// Options for the pool initialization CPI of a treasury and liquidity launch
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PoolArgs {
+    /// Instruction data forwarded unchanged to the AMM program
+    pub data: Vec<u8>,
+}
+
+/// Maximum number of buckets in an `AllocationPlan`, bounded by the compute budget
+pub const MAX_ALLOCATION_BUCKETS: usize = 8;
+
+/// One named share of an `AllocationPlan`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocationBucket {
+    /// Short tag logged with the bucket's mint, e.g. `*b"team\0\0\0\0"`
+    pub label: [u8; 8],
+    /// Share of the total supply, in basis points
+    pub bps: u16,
+}
+
+/// Self-documenting split of the total supply into labelled buckets
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AllocationPlan {
+    /// Buckets in the same order as their destination accounts, shares must sum to 10000
+    pub buckets: Vec<AllocationBucket>,
+}
+
+/// Maximum number of creators Metaplex accepts in a metadata account
+pub const MAX_CREATORS: usize = 5;
+
+/// Maximum token name length Metaplex accepts, in bytes
+pub const MAX_NAME_LENGTH: usize = 32;
+
+/// Maximum token symbol length Metaplex accepts, in bytes
+pub const MAX_SYMBOL_LENGTH: usize = 10;
+
+/// Maximum metadata URI length Metaplex accepts, in bytes
+pub const MAX_URI_LENGTH: usize = 200;
+
+/// Metaplex token standard a token's metadata is declared as
+/// Metaplex infers the standard from the mint's decimals, so the declaration must agree with them.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenStandard {
+    /// A one of one token with a master edition
+    NonFungible,
+    /// A semi-fungible token without decimals, e.g. an in-game item
+    FungibleAsset,
+    /// A regular fungible token with decimals
+    Fungible,
+}
+
+impl TokenStandard {
+    /// Whether a mint with `decimals` can carry this token standard
+    pub fn allows_decimals(&self, decimals: u8) -> bool {
+        match self {
+            Self::NonFungible | Self::FungibleAsset => decimals == 0,
+            Self::Fungible => decimals > 0,
+        }
+    }
+}
+
+/// Arguments for creating the Metaplex metadata account of the mint
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CreateMetadataArgs {
+    /// Token name, at most `MAX_NAME_LENGTH` bytes
+    pub name: String,
+    /// Token symbol, at most `MAX_SYMBOL_LENGTH` bytes
+    pub symbol: String,
+    /// URI of the off-chain metadata JSON, at most `MAX_URI_LENGTH` bytes
+    pub uri: String,
+    /// Royalty charged on secondary sales, in basis points
+    pub seller_fee_basis_points: u16,
+    /// Optional royalty recipients as (address, share) pairs, shares must sum to 100
+    pub creators: Option<Vec<(Pubkey, u8)>>,
+    /// Whether the metadata can still be updated by the update authority
+    pub is_mutable: bool,
+    /// Mint of a Metaplex collection to verify the token into, signed by its update authority
+    pub collection: Option<Pubkey>,
+    /// Token standard the metadata is meant for, checked against the mint's decimals
+    pub token_standard: Option<TokenStandard>,
+}
+
+/// Arguments for creating a distribution list account
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CreateDistributionListArgs {
+    /// Number of recipients the list will hold
+    pub capacity: u16,
+    /// Bump of the distribution list PDA [DISTRIBUTION_SEED, mint]
+    pub bump: u8,
+}
+
+/// Arguments for appending entries to a distribution list
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WriteDistributionListArgs {
+    /// Index of the first entry being written, must equal the number already written
+    pub offset: u16,
+    /// Entries to append
+    pub entries: Vec<DistributionEntry>,
+}
+
+/// Same as `WriteDistributionListArgs` with the entries in the compact packed layout
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WritePackedDistributionListArgs {
+    /// Index of the first entry being written, must equal the number already written
+    pub offset: u16,
+    /// Entries to append
+    pub entries: PackedDistributionEntries,
+}
+
+/// Distribution entries in a compact layout for tooling that builds instruction data by hand
+///
+/// Byte layout, with no padding:
+/// - `[0]` number of entries N as a `u8`, at most `MAX_DISTRIBUTION_RECIPIENTS`
+/// - `[1 + 40i .. 33 + 40i]` destination token account of entry i
+/// - `[33 + 40i .. 41 + 40i]` amount of entry i in base units, little-endian `u64`
+///
+/// The count is bounds-checked before any entry is read, so an oversized count is rejected
+/// without allocating, and data ending before the N-th entry is rejected as truncated.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackedDistributionEntries(pub Vec<DistributionEntry>);
+
+impl BorshSerialize for PackedDistributionEntries {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        let count = u8::try_from(self.0.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "too many entries"))?;
+        writer.write_all(&[count])?;
+        for entry in &self.0 {
+            writer.write_all(entry.destination.as_ref())?;
+            writer.write_all(&entry.amount.to_le_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for PackedDistributionEntries {
+    fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let mut count = [0u8; 1];
+        reader.read_exact(&mut count)?;
+        if u16::from(count[0]) > MAX_DISTRIBUTION_RECIPIENTS {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "too many entries"));
+        }
+
+        let mut entries = Vec::with_capacity(count[0] as usize);
+        for _ in 0..count[0] {
+            let mut destination = [0u8; 32];
+            let mut amount = [0u8; 8];
+            reader.read_exact(&mut destination)?;
+            reader.read_exact(&mut amount)?;
+            entries.push(DistributionEntry {
+                destination: Pubkey::new_from_array(destination),
+                amount: u64::from_le_bytes(amount),
+            });
+        }
+        Ok(Self(entries))
+    }
+}
+
+/// Arguments for minting the supply to a stored distribution list
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct DistributeArgs {
+    /// Accept entries with a zero amount, which are otherwise rejected as a likely mistake
+    pub allow_zero_amounts: bool,
+    /// Number of destination accounts the client passes, checked against the accounts before
+    /// anything else so a list and account slice that drifted apart fail early
+    pub num_recipients: u16,
+}
+
+/// Maximum number of recipients one `DistributeChunk` mints to, bounded by the compute budget
+/// since each one costs a `mint_to` CPI
+pub const MAX_DISTRIBUTION_CHUNK: usize = 10;
+
+/// Arguments for minting the next chunk of a stored distribution list
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DistributeChunkArgs {
+    /// Index of the first list entry this chunk mints to, must equal the stored cursor
+    pub start_index: u16,
+    /// Bump of the distribution cursor PDA [DISTRIBUTION_CURSOR_SEED, mint]
+    pub cursor_bump: u8,
+    /// Accept entries with a zero amount, only checked by the chunk that starts the distribution
+    pub allow_zero_amounts: bool,
+}
+
+/// Maximum number of wallets a single `DistributeToOwners` may mint to, bounded by the compute
+/// budget and transaction size since each one needs its own associated token account
+pub const MAX_OWNER_RECIPIENTS: usize = 6;
+
+/// One wallet and the amount minted to its associated token account
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OwnerAllocation {
+    /// Wallet owning the associated token account that receives the tokens
+    pub owner: Pubkey,
+    /// Amount in base units
+    pub amount: u64,
+}
+
+/// Arguments for minting the supply straight to wallet owners
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DistributeToOwnersArgs {
+    /// Recipients, whose amounts must add up to exactly the total supply
+    pub recipients: Vec<OwnerAllocation>,
+    /// Number of recipients the client passes accounts for, each as an owner and token account pair
+    pub num_recipients: u16,
+}
+
+/// Arguments for turning a Token-2022 mint into a group of related mints
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct InitializeGroupArgs {
+    /// Maximum number of member mints the group can ever hold, must be non-zero
+    pub max_size: u32,
+}
+
+/// Maximum number of additional metadata fields one `WriteAdditionalMetadata` call writes
+pub const MAX_ADDITIONAL_METADATA_FIELDS: usize = 16;
+
+/// Arguments for writing custom fields into a Token-2022 mint's on-mint metadata
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AdditionalMetadataArgs {
+    /// (key, value) pairs to set, keys must be non-empty and distinct
+    pub fields: Vec<(String, String)>,
+}
+
+/// Arguments for creating the mint on behalf of a client
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CreateMintArgs {
+    /// Initial mint authority, which may differ from the payer funding the mint
+    pub mint_authority: Pubkey,
+    /// Decimals to create the mint with instead of the default, must be in `ALLOWED_DECIMALS`
+    pub decimals: Option<u8>,
+    /// Freeze authority for regulated tokens, which can then only be launched in a mode that
+    /// keeps it (see `InitializeArgs::needs_freeze_authority`)
+    pub freeze_authority: Option<Pubkey>,
+    /// Token-2022 only: initialize the default account state extension to `Frozen`, so every
+    /// token account created for the mint starts frozen until the freeze authority thaws it.
+    /// Requires `freeze_authority`.
+    pub default_account_frozen: bool,
+    /// Token-2022 only: initialize the confidential transfer extension with this configuration
+    pub confidential_transfer: Option<ConfidentialTransferConfig>,
+}
+
+/// Confidential transfer mint extension settings for privacy-focused launches
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ConfidentialTransferConfig {
+    /// Key that may later update the configuration and approve accounts, `None` makes the
+    /// configuration permanent
+    pub authority: Option<Pubkey>,
+    /// New confidential accounts can be used without the authority approving them first.
+    /// Without an authority this must be set, or no account could ever be approved.
+    pub auto_approve_new_accounts: bool,
+    /// ElGamal public key of the auditor that can decrypt transfer amounts
+    pub auditor_elgamal_pubkey: Option<[u8; 32]>,
+}
+
+/// Arguments for revoking a single authority of a mint
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RevokeAuthorityArgs {
+    /// Authority type in the Token-2022 encoding, only mint (0) and freeze (1) are accepted
+    pub authority_type: u8,
+}
+
+/// Arguments for a launch whose mint authority is a PDA of this program
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PdaAuthorityArgs {
+    /// Bump of the mint authority PDA [MINT_AUTHORITY_SEED, mint]
+    pub bump: u8,
+    /// Decimals to launch with instead of the default, must be in `ALLOWED_DECIMALS`
+    pub decimals: Option<u8>,
+}
+
+/// Arguments for starting a chunked launch
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct StartChunkedMintArgs {
+    /// Bump of the chunked mint PDA [CHUNKED_MINT_SEED, mint]
+    pub bump: u8,
+}
+
+/// Arguments for a test mode mint
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TestMintArgs {
+    /// Bump of the test mint record PDA [TEST_MINT_SEED, mint]
+    pub bump: u8,
+}
+
+/// Arguments for minting one chunk of a chunked launch
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MintChunkArgs {
+    /// Base units to mint in this chunk, must be non-zero
+    pub amount: u64,
+    /// Bump of the launch stats PDA [LAUNCH_STATS_SEED, mint] to record the chunk in, which is
+    /// created on first use
+    pub stats_bump: Option<u8>,
+}
+
+/// Arguments for a launch that locks part of the supply in a time-locked vault
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LockedLaunchArgs {
+    /// Base units minted to the vault instead of the treasury, must be non-zero
+    pub lock_amount: u64,
+    /// Unix timestamp from which all locked tokens can be released, must be in the future
+    pub unlock_timestamp: i64,
+    /// How the locked tokens unlock up to `unlock_timestamp`, `VestingSchedule::CLIFF` releases
+    /// them all at once
+    pub vesting: VestingSchedule,
+    /// Bump of the supply lock PDA [SUPPLY_LOCK_SEED, mint]
+    pub bump: u8,
+    /// Mint decimals, `None` uses the program default
+    pub decimals: Option<u8>,
+}
+
+/// Arguments for a launch that hands the mint authority to a governance program
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GovernanceLaunchArgs {
+    /// The governance program whose PDA becomes the mint authority, must be `GOVERNANCE_PROGRAM_ID`
+    pub governance_program: Pubkey,
+    /// Bump of the governance authority PDA [GOVERNANCE_SEED, mint] under `governance_program`
+    pub governance_bump: u8,
+    /// Bump of the governance record PDA [GOVERNANCE_RECORD_SEED, mint]
+    pub record_bump: u8,
+    /// Mint decimals, `None` uses the program default
+    pub decimals: Option<u8>,
+}
+
+/// Arguments for a launch that hands the freeze authority to a timelock instead of revoking it
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TimelockFreezeArgs {
+    /// Bump of the timelock authority PDA [TIMELOCK_SEED, mint] under the config's timelock
+    /// program
+    pub timelock_bump: u8,
+    /// Mint decimals, `None` uses the program default
+    pub decimals: Option<u8>,
+}
+
+/// Arguments for recording the supply cap of a finalized mint
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SupplyCapArgs {
+    /// Bump of the supply cap PDA [SUPPLY_CAP_SEED, mint]
+    pub bump: u8,
+}
+
+/// Arguments for the single-instruction `LaunchAll` flow
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct LaunchAllArgs {
+    /// Decimals to create the mint with instead of the default, must be in `ALLOWED_DECIMALS`
+    pub decimals: Option<u8>,
+}
+
+/// Arguments for a launch whose supply is given in whole tokens
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiSupplyArgs {
+    /// Total supply in whole tokens, at least `MIN_SUPPLY`
+    pub ui_supply: u64,
+    /// Decimals of the mint, must be in `ALLOWED_DECIMALS`
+    pub decimals: u8,
+    /// Launch even if the decimals exceed the supply's digit count by more than
+    /// `MAX_DECIMALS_OVER_SUPPLY_DIGITS`
+    pub allow_impractical_decimals: bool,
+}
+
+/// Arguments for a launch into a multisig-owned treasury
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MultisigTreasuryArgs {
+    /// Decimals to launch with instead of the default, must be in `ALLOWED_DECIMALS`
+    pub decimals: Option<u8>,
+}
+
+/// Arguments for a launch whose supply and decimals are derived from a seed
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SeedLaunchArgs {
+    /// Seed fed to `utils::derive_launch_params`
+    pub seed: [u8; 32],
+}
+
+/// Off-chain commitment a finalized mint must match
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SupplyHashArgs {
+    /// Expected `utils::supply_hash` of the mint, its supply and decimals
+    pub supply_hash: [u8; 32],
+}
+
+/// Arguments for adding a key to or removing it from the launcher allowlist
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LauncherArgs {
+    /// The launcher key
+    pub launcher: Pubkey,
+    /// Bump of the launcher allowlist PDA [LAUNCHER_ALLOWLIST_SEED]
+    pub bump: u8,
+}
+
+/// Arguments for rescuing tokens stuck in an account owned by a supply lock PDA
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RescueArgs {
+    /// Amount to transfer out, in base units of the source account's mint
+    pub amount: u64,
+}
+
+/// Arguments for checking who launched a mint
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct VerifyCreatorArgs {
+    /// The creator the launch receipt must name
+    pub creator: Pubkey,
+}
+
+/// Arguments for asking whether a key is the program's admin
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IsAdminArgs {
+    /// The key to check
+    pub candidate: Pubkey,
+}
+
+/// Arguments for setting the admin stored in the program's config PDA
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ConfigAdminArgs {
+    /// The new admin
+    pub admin: Pubkey,
+    /// Bump of the config PDA [CONFIG_SEED]
+    pub bump: u8,
+}
+
+/// Metadata values a mint's Metaplex metadata must carry
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifyMetadataArgs {
+    /// Expected token name
+    pub name: String,
+    /// Expected token symbol
+    pub symbol: String,
+    /// Expected URI of the off-chain metadata JSON
+    pub uri: String,
+}
+
+/// Criteria a mint must meet to count as an immutable token under `CheckPolicy`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct MintPolicy {
+    /// Freeze authority the mint may keep, `None` requires it to be revoked
+    pub freeze_authority: Option<Pubkey>,
+    /// Decimals the mint may use, empty accepts any
+    pub allowed_decimals: Vec<u8>,
+}
+
+/// Arguments for a `ValidateOnly` dry run of a launch
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ValidateOnlyArgs {
+    /// Decimals the launch would use, `None` accepts any of `ALLOWED_DECIMALS`
+    pub decimals: Option<u8>,
+}
+
+/// A launch check `ValidateOnly` runs
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationCheck {
+    AuthoritySigned,
+    PayerSigned,
+    AuthorityIsPayer,
+    TokenProgram,
+    MintInitialized,
+    Decimals,
+    MintSupply,
+    MintAuthority,
+    FreezeAuthority,
+    DestinationProgram,
+    DestinationMint,
+    DestinationOwner,
+    DestinationBalance,
+    DestinationDelegate,
+}
+
+/// A value observed or expected by a failed check
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportValue {
+    /// No value, e.g. a revoked authority or an account that couldn't be read
+    None,
+    Flag(bool),
+    Number(u64),
+    Key(Pubkey),
+}
+
+impl From<COption<Pubkey>> for ReportValue {
+    fn from(key: COption<Pubkey>) -> Self {
+        match key {
+            COption::Some(key) => Self::Key(key),
+            COption::None => Self::None,
+        }
+    }
+}
+
+/// One failed check of a `ValidationReport`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ValidationFailure {
+    pub check: ValidationCheck,
+    pub observed: ReportValue,
+    pub expected: ReportValue,
+}
+
+impl ValidationFailure {
+    /// Largest serialized size of a failure, with both values being keys
+    pub const MAX_LEN: usize = 1 + 33 + 33;
+}
+
+/// Every failed check of a `ValidateOnly` dry run, returned through return data
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// The failed checks, in the order they ran
+    pub failures: Vec<ValidationFailure>,
+    /// Whether further failures were dropped to stay within the return data limit
+    pub truncated: bool,
+}
+
+impl ValidationReport {
+    /// Most failures a report holds while staying within `MAX_RETURN_DATA`
+    pub const MAX_FAILURES: usize = (MAX_RETURN_DATA - 4 - 1) / ValidationFailure::MAX_LEN;
+
+    /// Records `check` as failed with the given values unless it `passed`
+    pub fn require(
+        &mut self,
+        check: ValidationCheck,
+        passed: bool,
+        observed: ReportValue,
+        expected: ReportValue,
+    ) {
+        if passed {
+            return;
+        }
+        if self.failures.len() < Self::MAX_FAILURES {
+            self.failures.push(ValidationFailure { check, observed, expected });
+        } else {
+            self.truncated = true;
+        }
+    }
+}
+
+/// Outcome of a successful `VerifyAndFinalize`, returned through return data in place of the
+/// passing `ValidationReport`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FinalizeReport {
+    /// The checks run before minting, without failures since the launch went ahead
+    pub validation: ValidationReport,
+    /// Supply of the mint after the launch, in base units
+    pub supply: u64,
+    /// Decimals of the mint
+    pub decimals: u8,
+    /// Whether the mint authority is revoked after the launch
+    pub mint_authority_revoked: bool,
+}
+
+/// Arguments for computing a mint's fully diluted value
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ValuationArgs {
+    /// Price of one whole token, in base units of the quote currency (e.g. lamports)
+    pub price_per_token: u64,
+}
+
+/// `CheckPolicy` result bit: the mint authority is revoked
+pub const POLICY_MINT_AUTHORITY_REVOKED: u8 = 1 << 0;
+/// `CheckPolicy` result bit: the freeze authority is revoked or the allowed address
+pub const POLICY_FREEZE_AUTHORITY_ALLOWED: u8 = 1 << 1;
+/// `CheckPolicy` result bit: the decimals are in the allowed set
+pub const POLICY_DECIMALS_ALLOWED: u8 = 1 << 2;
+/// `CheckPolicy` result bit: the supply is nonzero
+pub const POLICY_SUPPLY_NONZERO: u8 = 1 << 3;
+/// `CheckPolicy` result when the mint meets every criterion
+pub const POLICY_PASSED: u8 = POLICY_MINT_AUTHORITY_REVOKED
+    | POLICY_FREEZE_AUTHORITY_ALLOWED
+    | POLICY_DECIMALS_ALLOWED
+    | POLICY_SUPPLY_NONZERO;
+
+/// Instructions supported by the fixed supply token program
+///
+/// Every instruction that launches a supply starts with the launch gate accounts, through which
+/// the program-wide launch policy in the config PDA applies to it before anything is minted:
+/// 0. `[]` The config PDA [CONFIG_SEED], which may not exist yet, enforcing nothing
+/// 1. `[]` (if the config enables the launcher allowlist) The launcher allowlist PDA, which must
+///    list the launching authority
+/// 2. `[writable]` (if the config sets a launch fee) The config's fee treasury, credited with the
+///    fee for every mint launched, payer must be writable
+/// 3. `[]` (if the config sets a launch fee) System program
+/// 4. ..4+N `[]` (if the config sets an oracle program) The oracle's approval account of each
+///    launched mint, in launch order, failing with `OracleNotApproved` for a mint not approved
+///
+/// The fee comes on top of the rent of the accounts the launch creates, a payer that can't cover
+/// both fails with `InsufficientForLaunchFee`. Chunked launches pay with their first chunk.
+///
+/// `client::launch_gate_accounts` builds them from the current config.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub enum TokenInstruction {
+    /// Mints the total supply to the token account and permanently revokes the mint authority
+    ///
+    /// Accounts expected, after the launch gate accounts:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[writable]` The destination token account
+    /// 2. `[signer]` The mint authority
+    /// 3. `[signer]` The transaction payer
+    /// 4. `[]` SPL Token or Token-2022 program
+    /// 5. `[signer]` (if `thaw`, `freeze_destination` or `CAPABILITY_REVOKE_FREEZE` is set) The
+    ///    mint's freeze authority
+    /// 6. `[writable]` (if `receipt` is set) The launch receipt PDA, payer must be writable
+    /// 7. `[]` (if `receipt` is set) System program
+    /// 8. `[]` (if `verify_transaction` is set) Instructions sysvar
+    /// 9. `[signer]` (if `co_signer` is set) The co-signer
+    /// 10. `[]` (if `event` is set) The event authority PDA [EVENT_AUTHORITY_SEED]
+    /// 11. `[]` (if `event` is set) This program, invoked to emit the event
+    /// 12. `[]` (if `CAPABILITY_CREATE_ATA` is set) System program, payer must be writable
+    /// 13. `[]` (if `CAPABILITY_CREATE_ATA` is set) Associated Token Account program
+    /// 14. `[]` (if `authorizer` is set) Instructions sysvar
+    /// 15. `[]` (if `CAPABILITY_PROTOCOL_DESTINATION` is set) The destination's owner
+    /// 16. `[]` (if `CAPABILITY_IMMUTABLE_PROGRAM` is set) This program's ProgramData account
+    /// 17. ..17+N `[]` (optional) Candidate token accounts for the mint, which must all be empty
+    InitializeFixedSupply(InitializeArgs),
+
+    /// Mints the total supply split between a treasury and a liquidity account, then
+    /// permanently revokes the mint authority
+    ///
+    /// The treasury receives `treasury_bps / 10000` of the supply (rounded down) and the
+    /// liquidity account receives the remainder.
+    ///
+    /// Accounts expected, after the launch gate accounts:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[writable]` The treasury token account
+    /// 2. `[writable]` The liquidity token account
+    /// 3. `[signer]` The mint authority
+    /// 4. `[signer]` The transaction payer
+    /// 5. `[]` SPL Token or Token-2022 program
+    /// 6. `[]` (if `pool` is set) The AMM program, which must be `AMM_PROGRAM_ID`
+    /// 7. ..7+N (if `pool` is set) Accounts of the pool instruction, passed through with their
+    ///    writable and signer flags, e.g. the treasury token account as the source of tokens
+    TreasuryAndLiquidity(TreasuryAndLiquidityArgs),
+
+    /// Creates the Metaplex metadata account for the mint
+    ///
+    /// Must run before the supply is finalized, since Metaplex requires the mint authority
+    /// to sign. The mint authority also becomes the metadata update authority. A declared
+    /// `token_standard` that contradicts the mint's decimals fails with
+    /// `TokenStandardDecimalsMismatch`.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The metadata account (PDA of ['metadata', metadata program id, mint])
+    /// 1. `[]` The token mint account
+    /// 2. `[signer]` The mint authority
+    /// 3. `[writable, signer]` The transaction payer
+    /// 4. `[]` System program
+    /// 5. `[]` Metaplex Token Metadata program
+    /// 6. `[signer]` (if `collection` is set) The collection's metadata update authority
+    /// 7. `[]` (if `collection` is set) The collection mint
+    /// 8. `[writable]` (if `collection` is set) The collection's metadata account
+    /// 9. `[]` (if `collection` is set) The collection's master edition account
+    /// 10. `[]` (if `collection` is set) Instructions sysvar
+    CreateMetadata(CreateMetadataArgs),
+
+    /// Read-only query of how many more base units the mint can ever create
+    ///
+    /// Returns a little-endian `u64` via return data: `0` once the mint authority is revoked,
+    /// otherwise `u64::MAX - supply`.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` The token mint account
+    RemainingMintable,
+
+    /// Runs the full `InitializeFixedSupply` flow for several independent mints
+    ///
+    /// Each (mint, token account) pair is launched with the matching entry of `mints`, none of
+    /// which may set `thaw`, `receipt`, `verify_transaction`, `co_signer` or
+    /// `freeze_destination`.
+    /// If any launch fails, the index of the failing mint is logged and the whole
+    /// transaction is rolled back.
+    ///
+    /// Accounts expected, after the launch gate accounts:
+    /// 0. `[signer]` The mint authority of every mint
+    /// 1. `[signer]` The transaction payer
+    /// 2. `[]` SPL Token or Token-2022 program
+    /// 3. ..3+2N `[writable]` N pairs of (token mint account, destination token account)
+    BatchInitialize(BatchInitializeArgs),
+
+    /// Transfers the freeze authority of an already finalized mint to an SPL multisig
+    ///
+    /// The mint authority must already be revoked and stays revoked, so the multisig can
+    /// freeze accounts but never mint.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[signer]` The current freeze authority
+    /// 2. `[]` The SPL multisig account that becomes the new freeze authority
+    /// 3. `[]` SPL Token or Token-2022 program
+    SetFreezeAuthority,
+
+    /// Creates an empty distribution list for a mint that has not been launched yet
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The distribution list PDA [DISTRIBUTION_SEED, mint]
+    /// 1. `[]` The token mint account
+    /// 2. `[signer]` The mint authority
+    /// 3. `[writable, signer]` The transaction payer
+    /// 4. `[]` System program
+    CreateDistributionList(CreateDistributionListArgs),
+
+    /// Appends recipients to a distribution list, possibly across several transactions
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The distribution list PDA
+    /// 1. `[signer]` The mint authority recorded in the list
+    WriteDistributionList(WriteDistributionListArgs),
+
+    /// Mints the total supply to the recipients stored in a fully written distribution list,
+    /// then permanently revokes the mint authority
+    ///
+    /// Lists longer than `MAX_DISTRIBUTION_RECIPIENTS` don't fit one transaction and are minted
+    /// with `DistributeChunk` instead.
+    ///
+    /// Accounts expected, after the launch gate accounts:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[]` The distribution list PDA
+    /// 2. `[signer]` The mint authority
+    /// 3. `[signer]` The transaction payer
+    /// 4. `[]` SPL Token or Token-2022 program
+    /// 5. ..5+N `[writable]` The destination token accounts, in list order
+    Distribute(DistributeArgs),
+
+    /// Initializes the Token-2022 token-group extension on a mint, making it a group that
+    /// related fixed-supply mints can join
+    ///
+    /// The mint's group pointer must point at the mint itself. The mint authority becomes the
+    /// group's update authority, and the payer tops up the mint's rent for the new extension.
+    /// Legacy SPL Token mints are rejected.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The group mint account
+    /// 1. `[signer]` The mint authority
+    /// 2. `[writable, signer]` The transaction payer
+    /// 3. `[]` Token-2022 program
+    /// 4. `[]` System program
+    InitializeGroup(InitializeGroupArgs),
+
+    /// Registers a mint as a member of a group
+    ///
+    /// The member mint's group member pointer must point at the mint itself, and it must not be
+    /// launched yet since Token-2022 requires its mint authority to sign.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The member mint account
+    /// 1. `[writable]` The group mint account
+    /// 2. `[signer]` The member's mint authority, which must also be the group update authority
+    /// 3. `[writable, signer]` The transaction payer
+    /// 4. `[]` Token-2022 program
+    /// 5. `[]` System program
+    AddGroupMember,
+
+    /// Read-only check that a mint is a registered member of an expected group
+    ///
+    /// Fails with `NotGroupMember` unless the mint's group member pointer references the mint
+    /// itself and its member record names the expected group.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` The member mint account
+    /// 1. `[]` The expected group mint account
+    VerifyGroupMembership,
+
+    /// Permanently locks the Metaplex metadata of a finalized mint
+    ///
+    /// Metaplex has no empty update authority, so it is handed to the system program id, which
+    /// can never sign, and the metadata is marked immutable in the same call. Bundle this after
+    /// `InitializeFixedSupply` to finalize supply and metadata in one transaction.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The metadata account (PDA of ['metadata', metadata program id, mint])
+    /// 1. `[]` The token mint account, whose mint authority must already be revoked
+    /// 2. `[signer]` The current metadata update authority
+    /// 3. `[]` Metaplex Token Metadata program
+    LockMetadata,
+
+    /// Creates and initializes a new mint, with no freeze authority unless one is requested
+    ///
+    /// The payer funds the mint but `mint_authority` may be another key, so a setup service can
+    /// create the mint for a client. The launch itself then has to be signed by that authority.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable, signer]` The new token mint account
+    /// 1. `[writable, signer]` The transaction payer
+    /// 2. `[]` SPL Token or Token-2022 program
+    /// 3. `[]` System program
+    CreateMint(CreateMintArgs),
+
+    /// Permanently revokes the mint or freeze authority of a mint, independently of a launch
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[signer]` The current holder of the authority being revoked
+    /// 2. `[]` SPL Token or Token-2022 program
+    RevokeAuthority(RevokeAuthorityArgs),
+
+    /// Same as `InitializeFixedSupply` for a mint whose authority is a PDA of this program,
+    /// which signs the mint and the revocation itself
+    ///
+    /// The PDA is checked against the bump in the instruction data with `create_program_address`
+    /// rather than searched for, failing with `InvalidPdaBump` when they don't derive it.
+    ///
+    /// Accounts expected, after the launch gate accounts:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[writable]` The destination token account, owned by the payer
+    /// 2. `[]` The mint authority PDA [MINT_AUTHORITY_SEED, mint]
+    /// 3. `[signer]` The transaction payer
+    /// 4. `[]` SPL Token or Token-2022 program
+    InitializeWithPdaAuthority(PdaAuthorityArgs),
+
+    /// Starts a launch that mints the total supply in several `MintChunk` calls
+    ///
+    /// Creates the tracking PDA with the total supply for the mint's decimals as its target.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The chunked mint PDA [CHUNKED_MINT_SEED, mint]
+    /// 1. `[]` The token mint account
+    /// 2. `[signer]` The mint authority
+    /// 3. `[writable, signer]` The transaction payer
+    /// 4. `[]` System program
+    StartChunkedMint(StartChunkedMintArgs),
+
+    /// Mints one chunk of a chunked launch, revoking the mint authority once the target is met
+    ///
+    /// Accounts expected, after the launch gate accounts:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[writable]` The chunked mint PDA
+    /// 2. `[writable]` The destination token account for the mint
+    /// 3. `[signer]` The mint authority
+    /// 4. `[signer]` The transaction payer, writable when the stats PDA is created
+    /// 5. `[]` SPL Token or Token-2022 program
+    /// 6. `[writable]` (if `stats_bump` is set) The launch stats PDA [LAUNCH_STATS_SEED, mint]
+    /// 7. `[]` (if `stats_bump` is set) System program
+    MintChunk(MintChunkArgs),
+
+    /// Read-only check of a mint against an immutable token policy
+    ///
+    /// Returns a single byte via return data with one `POLICY_*` bit set per criterion the
+    /// mint meets, `POLICY_PASSED` when it meets all of them. A failing mint is not an error.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` The token mint account
+    CheckPolicy(MintPolicy),
+
+    /// Launches the fixed supply with part of it locked until a given time
+    ///
+    /// Mints the circulating portion to the treasury and `lock_amount` to the vault owned by
+    /// the supply lock PDA, records both amounts and the vesting schedule in the PDA and revokes
+    /// the mint authority.
+    ///
+    /// Accounts expected, after the launch gate accounts:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[writable]` The treasury token account, owned by the payer
+    /// 2. `[writable]` The supply lock PDA [SUPPLY_LOCK_SEED, mint]
+    /// 3. `[writable]` The vault token account, owned by the supply lock PDA
+    /// 4. `[signer]` The mint authority
+    /// 5. `[writable, signer]` The transaction payer, beneficiary of the locked tokens
+    /// 6. `[]` SPL Token or Token-2022 program
+    /// 7. `[]` System program
+    InitializeWithLock(LockedLaunchArgs),
+
+    /// Releases the locked tokens of a supply lock that have vested and weren't released yet to
+    /// the beneficiary
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The supply lock PDA
+    /// 1. `[writable]` The vault token account
+    /// 2. `[]` The token mint account
+    /// 3. `[writable]` The token account receiving the released tokens
+    /// 4. `[signer]` The beneficiary
+    /// 5. `[]` SPL Token or Token-2022 program
+    ReleaseLocked,
+
+    /// Creates the mint and the payer's associated token account, mints the total supply into
+    /// it and revokes the mint authority, all in one instruction
+    ///
+    /// Starts from a funded payer and a fresh mint keypair. The payer is the mint authority for
+    /// the duration of the instruction, and the mint never has a freeze authority.
+    ///
+    /// Accounts expected, after the launch gate accounts:
+    /// 0. `[writable, signer]` The new token mint account
+    /// 1. `[writable]` The payer's associated token account for the mint, not yet created
+    /// 2. `[writable, signer]` The transaction payer
+    /// 3. `[]` SPL Token or Token-2022 program
+    /// 4. `[]` System program
+    /// 5. `[]` Associated Token Account program
+    LaunchAll(LaunchAllArgs),
+
+    /// Carries a `LaunchEvent` in its instruction data so indexers can pick launches up from
+    /// inner instructions. Only accepted as a self-invocation signed by the event authority.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The event authority PDA [EVENT_AUTHORITY_SEED]
+    EmitEvent(EmitEventArgs),
+
+    /// Read-only check that a mint was launched by `creator`, as recorded in its launch receipt
+    ///
+    /// Fails with `CreatorMismatch` when the receipt names another creator, so it can gate a
+    /// "verified creator" badge or run before other instructions in the same transaction.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` The launch receipt PDA
+    /// 1. `[]` The token mint account
+    VerifyCreator(VerifyCreatorArgs),
+
+    /// Mints the total supply to the associated token accounts of a list of wallets, creating
+    /// any that don't exist yet, then permanently revokes the mint authority
+    ///
+    /// Callers only name the wallets, each associated token account is derived on-chain and
+    /// must match the one passed.
+    ///
+    /// Accounts expected, after the launch gate accounts:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[signer]` The mint authority
+    /// 2. `[writable, signer]` The transaction payer, funding new token accounts
+    /// 3. `[]` SPL Token or Token-2022 program
+    /// 4. `[]` System program
+    /// 5. `[]` Associated Token Account program
+    /// 6. ..6+2N N pairs of (`[]` wallet, `[writable]` its associated token account), in
+    ///    recipient order
+    DistributeToOwners(DistributeToOwnersArgs),
+
+    /// Read-only check that a mint's Metaplex metadata has the expected name, symbol and uri
+    ///
+    /// Fails with `MetadataNotFound` when the mint has no metadata account and with
+    /// `MetadataMismatch` when any of the values differ.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` The metadata account (PDA of ['metadata', metadata program id, mint])
+    /// 1. `[]` The token mint account
+    VerifyMetadata(VerifyMetadataArgs),
+
+    /// Mints the total supply split across the labelled buckets of an allocation plan, then
+    /// permanently revokes the mint authority
+    ///
+    /// Each bucket receives its share of the supply rounded down, and the last bucket also
+    /// receives the rounding remainder. The label is logged with every mint.
+    ///
+    /// Accounts expected, after the launch gate accounts:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[signer]` The mint authority
+    /// 2. `[signer]` The transaction payer
+    /// 3. `[]` SPL Token or Token-2022 program
+    /// 4. ..4+N `[writable]` The destination token accounts, in bucket order
+    InitializeWithAllocationPlan(AllocationPlan),
+
+    /// Transfers tokens accidentally sent to an account owned by a supply lock PDA back out,
+    /// signed by the PDA and gated by the config admin
+    ///
+    /// The source may hold any mint. For the lock's own vault only the surplus above the
+    /// still-locked amount can be rescued.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` The supply lock PDA
+    /// 1. `[writable]` The source token account, owned by the supply lock PDA
+    /// 2. `[]` The source token account's mint
+    /// 3. `[writable]` The destination token account
+    /// 4. `[signer]` The config admin
+    /// 5. `[]` SPL Token or Token-2022 program
+    /// 6. `[]` The config PDA [CONFIG_SEED]
+    Rescue(RescueArgs),
+
+    /// Read-only getter returning a mint's `LaunchStats` via return data, Borsh encoded
+    ///
+    /// Accounts expected:
+    /// 0. `[]` The launch stats PDA [LAUNCH_STATS_SEED, mint]
+    GetLaunchStats,
+
+    /// Same as `InitializeFixedSupply` with a supply of `ui_supply` whole tokens instead of the
+    /// default, converted to base units on-chain
+    ///
+    /// Fails with `SupplyOverflow` when `ui_supply * 10^decimals` doesn't fit in a u64.
+    ///
+    /// Accounts expected, after the launch gate accounts:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[writable]` The destination token account, owned by the payer
+    /// 2. `[signer]` The mint authority
+    /// 3. `[signer]` The transaction payer
+    /// 4. `[]` SPL Token or Token-2022 program
+    InitializeWithUiSupply(UiSupplyArgs),
+
+    /// Permanently revokes the mint authority of several mints, e.g. to lock down a team's
+    /// tokens in one transaction
+    ///
+    /// Each revocation is verified before moving to the next mint. If any fails, the index of
+    /// the failing mint is logged and the whole transaction is rolled back.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` SPL Token or Token-2022 program
+    /// 1. ..1+2N N pairs of (`[writable]` token mint account, `[signer]` its mint authority)
+    BatchRevoke(BatchRevokeArgs),
+
+    /// Same as `WriteDistributionList` with the entries in the `PackedDistributionEntries`
+    /// layout, which saves instruction space for large batches
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The distribution list PDA
+    /// 1. `[signer]` The mint authority recorded in the list
+    WritePackedDistributionList(WritePackedDistributionListArgs),
+
+    /// Read-only query of whether `candidate` is the admin stored in the program's config PDA
+    ///
+    /// Returns a single byte via return data, `1` for the admin and `0` for any other key, so
+    /// UIs can show admin controls only to the admin of the deployed program.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` The config PDA [CONFIG_SEED], written by `SetConfigAdmin`
+    IsAdmin(IsAdminArgs),
+
+    /// Mints the total supply to the token account, then hands the mint authority to a PDA of
+    /// the configured `GOVERNANCE_PROGRAM_ID` instead of revoking it
+    ///
+    /// The supply is not fixed afterwards: the governance program can sign for its PDA and
+    /// mint more. The handoff is recorded in a governance record PDA so anyone can look up
+    /// which governance program controls the mint. Any other governance program is rejected
+    /// with `IncorrectProgramId`.
+    ///
+    /// Accounts expected, after the launch gate accounts:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[writable]` The destination token account, owned by the payer
+    /// 2. `[]` The governance authority PDA [GOVERNANCE_SEED, mint] of the governance program
+    /// 3. `[writable]` The governance record PDA [GOVERNANCE_RECORD_SEED, mint]
+    /// 4. `[signer]` The mint authority
+    /// 5. `[writable, signer]` The transaction payer
+    /// 6. `[]` SPL Token or Token-2022 program
+    /// 7. `[]` System program
+    InitializeWithGovernance(GovernanceLaunchArgs),
+
+    /// Records the permanent supply of a mint whose mint authority is revoked in a write-once
+    /// supply cap PDA that other programs can trust
+    ///
+    /// Bundle it after the launch instruction to record the cap in the launch transaction. Fails
+    /// with `MintAuthorityNotRevoked` while the supply can still change, and with
+    /// `AccountAlreadyInitialized` once the cap exists.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The supply cap PDA [SUPPLY_CAP_SEED, mint]
+    /// 1. `[]` The token mint account
+    /// 2. `[writable, signer]` The transaction payer
+    /// 3. `[]` System program
+    RecordSupplyCap(SupplyCapArgs),
+
+    /// Read-only getter returning a mint's `SupplyCap` via return data, Borsh encoded
+    ///
+    /// Accounts expected:
+    /// 0. `[]` The supply cap PDA [SUPPLY_CAP_SEED, mint]
+    GetSupplyCap,
+
+    /// Read-only computation of a mint's fully diluted value at `price_per_token`
+    ///
+    /// Logs the value and returns it as a little-endian `u64` via return data, in the quote
+    /// currency's base units. Fails with `ValuationOverflow` when it doesn't fit in a u64.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` The token mint account
+    GetValuation(ValuationArgs),
+
+    /// Same as `InitializeWithUiSupply` with the supply and decimals derived on-chain from a
+    /// seed by `utils::derive_launch_params`, for launches whose parameters must not be
+    /// hand-picked
+    ///
+    /// The mint has to be created with the derived decimals. Fails with `SeedDerivationFailed`
+    /// when the seed derives degenerate parameters.
+    ///
+    /// Accounts expected, after the launch gate accounts:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[writable]` The destination token account, owned by the payer
+    /// 2. `[signer]` The mint authority
+    /// 3. `[signer]` The transaction payer
+    /// 4. `[]` SPL Token or Token-2022 program
+    InitializeFromSeed(SeedLaunchArgs),
+
+    /// Adds `launcher` to the launcher allowlist, creating the allowlist on first use
+    ///
+    /// Only the config admin can manage the allowlist. Fails with `InvalidArgument` when the key
+    /// is already listed or the list holds `MAX_ALLOWLISTED_LAUNCHERS` keys.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The launcher allowlist PDA [LAUNCHER_ALLOWLIST_SEED]
+    /// 1. `[writable, signer]` The config admin, pays for the allowlist account
+    /// 2. `[]` System program
+    /// 3. `[]` The config PDA [CONFIG_SEED]
+    AddLauncher(LauncherArgs),
+
+    /// Removes `launcher` from the launcher allowlist
+    ///
+    /// Fails with `LauncherNotAllowlisted` when the key isn't listed.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The launcher allowlist PDA [LAUNCHER_ALLOWLIST_SEED]
+    /// 1. `[signer]` The config admin
+    /// 2. `[]` The config PDA [CONFIG_SEED]
+    RemoveLauncher(LauncherArgs),
+
+    /// Read-only check that a finalized mint matches an off-chain commitment to its supply
+    ///
+    /// Bundle it after the launch instruction so the launch only lands when its result hashes to
+    /// `supply_hash` under `utils::supply_hash`. Fails with `MintAuthorityNotRevoked` while the
+    /// supply can still change and with `SupplyHashMismatch` when the hash differs.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` The token mint account
+    VerifySupplyHash(SupplyHashArgs),
+
+    /// Closes the transient PDAs of a finalized launch and returns their rent
+    ///
+    /// Chunked mint, launch stats and distribution list PDAs can be closed once the mint
+    /// authority is revoked, otherwise the instruction fails with `CannotCleanupBeforeFinalize`.
+    /// Receipts, supply caps and the other permanent records are never closed. Chunked mints and
+    /// distribution lists must have been created by the signing authority, and launch stats can
+    /// only be closed together with the mint's chunked mint PDA, the only launch that records them.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` The token mint account
+    /// 1. `[signer]` The authority recorded in the PDAs
+    /// 2. `[writable]` The account receiving the rent
+    /// 3. ..3+N `[writable]` The transient PDAs to close
+    Cleanup,
+
+    /// Permanently revokes several authorities of one mint, e.g. the mint, freeze and transfer
+    /// fee config authorities of a Token-2022 mint with extensions, in one sweep
+    ///
+    /// Each authority is checked against its signer and revoked in order, then all of them are
+    /// verified to be unset. The failing authority type is logged and the whole transaction is
+    /// rolled back if any step fails.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[]` SPL Token or Token-2022 program
+    /// 2. ..2+N `[signer]` The current holder of each authority, in `authority_types` order
+    RevokeAuthorities(RevokeAuthoritiesArgs),
+
+    /// Rehearses a launch on staging: mints the total supply but keeps the mint authority
+    ///
+    /// Records the mint as a test mint in a PDA so `ResetTestMint` can burn the supply again.
+    /// Only `test-mode` builds accept it, any other build rejects it as invalid instruction data.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[writable]` The destination token account, owned by the mint authority
+    /// 2. `[writable]` The test mint record PDA [TEST_MINT_SEED, mint]
+    /// 3. `[signer]` The mint authority
+    /// 4. `[writable, signer]` The transaction payer
+    /// 5. `[]` SPL Token or Token-2022 program
+    /// 6. `[]` System program
+    TestMint(TestMintArgs),
+
+    /// Burns the supply of a test mint and closes its record, leaving a zero supply mint that
+    /// can be launched again
+    ///
+    /// Only `test-mode` builds accept it, any other build rejects it as invalid instruction data.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[writable]` The token account holding the whole test supply, owned by the authority
+    /// 2. `[writable]` The test mint record PDA [TEST_MINT_SEED, mint]
+    /// 3. `[signer]` The mint authority recorded in the PDA
+    /// 4. `[]` SPL Token or Token-2022 program
+    /// 5. `[writable]` The account receiving the record's rent
+    ResetTestMint,
+
+    /// Launches the fixed supply straight into a treasury controlled by an SPL multisig
+    ///
+    /// Mints the total supply into a token account owned by the multisig and revokes the mint
+    /// authority, so no single key ever holds the supply. The multisig must require at least
+    /// `MIN_TREASURY_MULTISIG_SIGNERS` of its distinct signers, otherwise the launch fails with
+    /// `InvalidMultisigTreasury`.
+    ///
+    /// Accounts expected, after the launch gate accounts:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[writable]` The treasury token account, owned by the multisig
+    /// 2. `[]` The SPL multisig account of the mint's token program
+    /// 3. `[signer]` The mint authority
+    /// 4. `[signer]` The transaction payer
+    /// 5. `[]` SPL Token or Token-2022 program
+    InitializeIntoMultisig(MultisigTreasuryArgs),
+
+    /// Dry run of the checks of a plain launch, meant to be simulated
+    ///
+    /// Runs every check instead of stopping at the first failure and returns a Borsh
+    /// `ValidationReport` listing each failed one with its observed and expected values through
+    /// return data. Fails with `ValidationFailed` when the report isn't empty. Nothing is minted.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` The token mint account
+    /// 1. `[]` The destination token account for the mint
+    /// 2. `[signer]` The mint authority
+    /// 3. `[signer]` The transaction payer
+    /// 4. `[]` SPL Token or Token-2022 program
+    ValidateOnly(ValidateOnlyArgs),
+
+    /// Writes additional (key, value) fields into the token-metadata extension of a Token-2022
+    /// mint before it is finalized
+    ///
+    /// The mint's metadata pointer must point at the mint itself and the mint authority must be
+    /// the metadata's update authority. Each field is set with an `update_field` CPI, existing
+    /// keys are overwritten. The payer tops up the mint's rent for the larger metadata, which
+    /// fails with `MetadataTooLarge` when it outgrows what one instruction can reallocate.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[signer]` The mint authority
+    /// 2. `[writable, signer]` The transaction payer
+    /// 3. `[]` Token-2022 program
+    /// 4. `[]` System program
+    WriteAdditionalMetadata(AdditionalMetadataArgs),
+
+    /// Read-only check that a launch receipt still matches the checksum written with it
+    ///
+    /// Fails with `ReceiptCorrupted` when the receipt doesn't decode or its fields don't hash to
+    /// the stored checksum.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` The launch receipt PDA
+    VerifyReceiptIntegrity,
+
+    /// Mints the total supply to the token account, hands the freeze authority to a PDA of the
+    /// configured timelock program, then revokes the mint authority
+    ///
+    /// The supply is fixed as with `InitializeFixedSupply`, but freezes stay possible for
+    /// compliance, only through the timelock program and so only after its delay. The mint must
+    /// have been created with the mint authority as its freeze authority. Fails with
+    /// `TimelockTransferFailed` when the timelock PDA doesn't hold the freeze authority afterwards,
+    /// and with `InvalidArgument` while the config sets no timelock program.
+    ///
+    /// Accounts expected, after the launch gate accounts:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[writable]` The destination token account, owned by the payer
+    /// 2. `[]` The timelock authority PDA [TIMELOCK_SEED, mint] of the config's timelock program
+    /// 3. `[signer]` The mint authority, also the mint's current freeze authority
+    /// 4. `[writable, signer]` The transaction payer
+    /// 5. `[]` SPL Token or Token-2022 program
+    InitializeWithTimelockFreeze(TimelockFreezeArgs),
+
+    /// Mints to the next recipients of a fully written distribution list, resuming from a cursor
+    /// PDA, and revokes the mint authority once the last recipient is minted to
+    ///
+    /// For lists longer than one `Distribute` transaction can reference. The first chunk checks
+    /// the whole list adds up to the total supply and creates the cursor, every chunk must start
+    /// exactly at the cursor so no recipient is skipped or minted to twice. Fails with
+    /// `DistributionAlreadyComplete` once the cursor has reached the end of the list.
+    ///
+    /// Accounts expected, after the launch gate accounts:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[]` The distribution list PDA
+    /// 2. `[writable]` The distribution cursor PDA [DISTRIBUTION_CURSOR_SEED, mint]
+    /// 3. `[signer]` The mint authority
+    /// 4. `[writable, signer]` The transaction payer
+    /// 5. `[]` SPL Token or Token-2022 program
+    /// 6. `[]` System program
+    /// 7. ..7+N `[writable]` The next destination token accounts in list order, at most
+    ///    `MAX_DISTRIBUTION_CHUNK`
+    DistributeChunk(DistributeChunkArgs),
+
+    /// Runs the checks of `ValidateOnly` and, when every one passes, mints the total supply and
+    /// revokes the mint authority in the same instruction
+    ///
+    /// When a check fails nothing is minted: the `ValidationReport` is returned through return
+    /// data and the instruction fails with `ValidationFailed`, exactly like `ValidateOnly`. After
+    /// a successful launch the return data holds a Borsh `FinalizeReport` instead.
+    ///
+    /// Accounts expected, after the launch gate accounts:
+    /// 0. `[writable]` The token mint account
+    /// 1. `[writable]` The destination token account, owned by the payer
+    /// 2. `[signer]` The mint authority
+    /// 3. `[signer]` The transaction payer
+    /// 4. `[]` SPL Token or Token-2022 program
+    VerifyAndFinalize(ValidateOnlyArgs),
+
+    /// Stores the admin `IsAdmin` reports in the program's config PDA, creating it on first use
+    ///
+    /// The program's upgrade authority creates the config, from then on only the stored admin can
+    /// hand the role to another key. Every admin instruction checks its signer against the stored
+    /// admin and fails with `AdminMismatch` for any other key.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The config PDA [CONFIG_SEED]
+    /// 1. `[writable, signer]` The current admin, paying for the config on first use
+    /// 2. `[]` System program
+    /// 3. `[]` This program's ProgramData account, naming the upgrade authority
+    SetConfigAdmin(ConfigAdminArgs),
+
+    /// Replaces the launch policy stored in the program's config PDA
+    ///
+    /// Only the config admin can change the settings, any other signer fails with
+    /// `AdminMismatch`. The new settings apply to every launch from the next instruction on.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The config PDA [CONFIG_SEED]
+    /// 1. `[signer]` The config admin
+    /// 2. `[]` (if `oracle_program` is set) The oracle program, which must be deployed
+    /// 3. `[]` (if `timelock_program` is set) The timelock program, which must be deployed
+    UpdateConfig(ConfigSettings),
+}
+
+impl TokenInstruction {
+    /// Decodes and validates raw instruction data
+    /// Empty data is accepted as a legacy `InitializeFixedSupply` call with default arguments,
+    /// since the original program ignored instruction data entirely.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        if input.is_empty() {
+            return Ok(Self::InitializeFixedSupply(InitializeArgs::default()));
+        }
+
+        let instruction =
+            Self::try_from_slice(input).map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        match &instruction {
+            Self::InitializeFixedSupply(args) => {
+                // Unknown bits stay zero so future versions can safely assign them a meaning
+                if args.capabilities & !KNOWN_CAPABILITIES != 0 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                // A frozen destination could never be thawed once the freeze authority is gone
+                let freezes = args.freeze_destination
+                    || args.thaw.as_ref().is_some_and(|thaw| thaw.refreeze);
+                if freezes && args.has_capability(CAPABILITY_REVOKE_FREEZE) {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                // A created associated token account always belongs to the payer
+                if args.has_capability(CAPABILITY_CREATE_ATA)
+                    && args.has_capability(CAPABILITY_PROTOCOL_DESTINATION)
+                {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+            }
+            // Both tranches must receive a share, otherwise this is a plain initialize
+            Self::TreasuryAndLiquidity(args) => {
+                if args.treasury_bps == 0 || args.treasury_bps >= MAX_BASIS_POINTS {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+            }
+            Self::CreateMetadata(args) => {
+                if args.seller_fee_basis_points > MAX_BASIS_POINTS {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                if let Some(creators) = &args.creators {
+                    if creators.is_empty() || creators.len() > MAX_CREATORS {
+                        return Err(ProgramError::InvalidInstructionData);
+                    }
+                }
+            }
+            Self::RemainingMintable
+            | Self::SetFreezeAuthority
+            | Self::Distribute(_)
+            | Self::AddGroupMember
+            | Self::VerifyGroupMembership
+            | Self::LockMetadata
+            | Self::InitializeWithPdaAuthority(_)
+            | Self::StartChunkedMint(_)
+            | Self::ReleaseLocked
+            | Self::LaunchAll(_)
+            | Self::EmitEvent(_)
+            | Self::VerifyCreator(_)
+            | Self::VerifyMetadata(_)
+            | Self::GetLaunchStats
+            | Self::InitializeWithUiSupply(_)
+            | Self::IsAdmin(_)
+            | Self::InitializeWithGovernance(_)
+            | Self::RecordSupplyCap(_)
+            | Self::GetSupplyCap
+            | Self::GetValuation(_)
+            | Self::InitializeFromSeed(_)
+            | Self::AddLauncher(_)
+            | Self::RemoveLauncher(_)
+            | Self::VerifySupplyHash(_)
+            | Self::Cleanup
+            | Self::InitializeIntoMultisig(_)
+            | Self::ValidateOnly(_)
+            | Self::VerifyReceiptIntegrity
+            | Self::InitializeWithTimelockFreeze(_)
+            | Self::DistributeChunk(_)
+            | Self::VerifyAndFinalize(_)
+            | Self::SetConfigAdmin(_)
+            | Self::UpdateConfig(_) => {}
+            // Test mode must never run in a production build, whatever a client sends
+            Self::TestMint(_) | Self::ResetTestMint => {
+                if !cfg!(feature = "test-mode") {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+            }
+            Self::RevokeAuthorities(args) => {
+                let types = &args.authority_types;
+                if types.is_empty() || types.len() > MAX_REVOKED_AUTHORITIES {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                for (index, authority_type) in types.iter().enumerate() {
+                    decode_authority_type(*authority_type)?;
+                    let duplicate = types[index + 1..].contains(authority_type);
+                    if matches!(authority_type, 2 | 3) || duplicate {
+                        return Err(ProgramError::InvalidInstructionData);
+                    }
+                }
+            }
+            Self::BatchRevoke(args) => {
+                if args.mints == 0 || args.mints > MAX_BATCH_REVOKE_MINTS {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+            }
+            // Frozen accounts could never be thawed without a freeze authority
+            Self::CreateMint(args) => {
+                if args.default_account_frozen && args.freeze_authority.is_none() {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+            }
+            Self::InitializeWithLock(args) => {
+                if args.lock_amount == 0 || !args.vesting.is_valid(args.unlock_timestamp) {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+            }
+            Self::CheckPolicy(policy) => {
+                if policy.allowed_decimals.len() > MAX_DECIMALS as usize + 1 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+            }
+            Self::Rescue(RescueArgs { amount }) | Self::MintChunk(MintChunkArgs { amount, .. }) => {
+                if *amount == 0 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+            }
+            Self::RevokeAuthority(args) => {
+                let authority_type = decode_authority_type(args.authority_type)?;
+                if !matches!(
+                    authority_type,
+                    AuthorityType::MintTokens | AuthorityType::FreezeAccount
+                ) {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+            }
+            Self::InitializeGroup(args) => {
+                if args.max_size == 0 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+            }
+            Self::WriteAdditionalMetadata(args) => {
+                let fields = &args.fields;
+                if fields.is_empty() || fields.len() > MAX_ADDITIONAL_METADATA_FIELDS {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                for (index, (key, _)) in fields.iter().enumerate() {
+                    let duplicate = fields[index + 1..].iter().any(|(other, _)| other == key);
+                    if key.is_empty() || duplicate {
+                        return Err(ProgramError::InvalidInstructionData);
+                    }
+                }
+            }
+            Self::CreateDistributionList(args) => {
+                if args.capacity == 0 || args.capacity > MAX_LIST_RECIPIENTS {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+            }
+            Self::InitializeWithAllocationPlan(plan) => {
+                if plan.buckets.is_empty()
+                    || plan.buckets.len() > MAX_ALLOCATION_BUCKETS
+                    || plan.buckets.iter().any(|bucket| bucket.bps == 0)
+                {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+            }
+            Self::DistributeToOwners(args) => {
+                if args.recipients.is_empty() || args.recipients.len() > MAX_OWNER_RECIPIENTS {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+            }
+            Self::WriteDistributionList(args) => {
+                if args.entries.is_empty() {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+            }
+            Self::WritePackedDistributionList(args) => {
+                if args.entries.0.is_empty() {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+            }
+            Self::BatchInitialize(args) => {
+                if args.mints.is_empty() || args.mints.len() > MAX_BATCH_MINTS {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                // The batch account layout has no slots for any of the optional launch accounts
+                if args.mints.iter().any(|mint_args| {
+                    mint_args.needs_freeze_authority()
+                        || mint_args.receipt.is_some()
+                        || mint_args.verify_transaction
+                        || mint_args.co_signer.is_some()
+                        || mint_args.event.is_some()
+                        || mint_args.authorizer.is_some()
+                        || mint_args.capabilities != 0
+                }) {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+            }
+        }
+
+        Ok(instruction)
+    }
+
+    /// Encodes the instruction into raw instruction data
+    pub fn pack(&self) -> Vec<u8> {
+        borsh::to_vec(self).expect("instruction serialization cannot fail")
+    }
+}
+
+/// Decodes an authority type byte using the Token-2022 wire encoding
+/// Unknown values are rejected instead of being mapped to a default, so a newer client can't
+/// make the program act on an authority it doesn't know about.
+pub fn decode_authority_type(b: u8) -> Result<AuthorityType, ProgramError> {
+    match b {
+        0 => Ok(AuthorityType::MintTokens),
+        1 => Ok(AuthorityType::FreezeAccount),
+        2 => Ok(AuthorityType::AccountOwner),
+        3 => Ok(AuthorityType::CloseAccount),
+        4 => Ok(AuthorityType::TransferFeeConfig),
+        5 => Ok(AuthorityType::WithheldWithdraw),
+        6 => Ok(AuthorityType::CloseMint),
+        7 => Ok(AuthorityType::InterestRate),
+        8 => Ok(AuthorityType::PermanentDelegate),
+        9 => Ok(AuthorityType::ConfidentialTransferMint),
+        10 => Ok(AuthorityType::TransferHookProgramId),
+        11 => Ok(AuthorityType::ConfidentialTransferFeeConfig),
+        12 => Ok(AuthorityType::MetadataPointer),
+        13 => Ok(AuthorityType::GroupPointer),
+        14 => Ok(AuthorityType::GroupMemberPointer),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+// Rough compute costs behind `recommended_compute_units`, with headroom over measured usage
+const BASE_COMPUTE_UNITS: u32 = 10_000; // Decoding, account validation and logging
+const LAUNCH_COMPUTE_UNITS: u32 = 15_000; // Mint, revoke and their post-checks for one mint
+const MINT_TO_COMPUTE_UNITS: u32 = 7_000; // One extra mint_to CPI with its balance check
+const CREATE_MINT_COMPUTE_UNITS: u32 = 10_000; // create_account plus initialize_mint2
+const CREATE_ATA_COMPUTE_UNITS: u32 = 30_000; // Associated Token Account creation CPI
+const REVOKE_COMPUTE_UNITS: u32 = 6_000; // set_authority plus its post-check for one mint
+
+/// Recommended compute unit limit for `instruction`, to pass to
+/// `ComputeBudgetInstruction::set_compute_unit_limit`
+/// Scales with the work the instruction does, e.g. the number of mints in a batch. `Distribute`
+/// reads its recipients from the list account, so it assumes a full list.
+pub fn recommended_compute_units(instruction: &TokenInstruction) -> u32 {
+    let work = match instruction {
+        TokenInstruction::InitializeFixedSupply(_)
+        | TokenInstruction::InitializeWithPdaAuthority(_)
+        | TokenInstruction::InitializeWithUiSupply(_)
+        | TokenInstruction::InitializeWithGovernance(_)
+        | TokenInstruction::InitializeFromSeed(_)
+        | TokenInstruction::TestMint(_)
+        | TokenInstruction::InitializeIntoMultisig(_)
+        | TokenInstruction::InitializeWithTimelockFreeze(_)
+        | TokenInstruction::VerifyAndFinalize(_) => LAUNCH_COMPUTE_UNITS,
+        TokenInstruction::TreasuryAndLiquidity(_) | TokenInstruction::InitializeWithLock(_) => {
+            LAUNCH_COMPUTE_UNITS + MINT_TO_COMPUTE_UNITS
+        }
+        TokenInstruction::BatchInitialize(args) => args.mints.len() as u32 * LAUNCH_COMPUTE_UNITS,
+        TokenInstruction::Distribute(_) => {
+            LAUNCH_COMPUTE_UNITS + MAX_DISTRIBUTION_RECIPIENTS as u32 * MINT_TO_COMPUTE_UNITS
+        }
+        TokenInstruction::DistributeChunk(_) => {
+            LAUNCH_COMPUTE_UNITS + MAX_DISTRIBUTION_CHUNK as u32 * MINT_TO_COMPUTE_UNITS
+        }
+        TokenInstruction::CreateMint(_) => CREATE_MINT_COMPUTE_UNITS,
+        TokenInstruction::LaunchAll(_) => {
+            CREATE_MINT_COMPUTE_UNITS + CREATE_ATA_COMPUTE_UNITS + LAUNCH_COMPUTE_UNITS
+        }
+        TokenInstruction::InitializeWithAllocationPlan(plan) => {
+            LAUNCH_COMPUTE_UNITS + plan.buckets.len() as u32 * MINT_TO_COMPUTE_UNITS
+        }
+        TokenInstruction::DistributeToOwners(args) => {
+            LAUNCH_COMPUTE_UNITS
+                + args.recipients.len() as u32 * (CREATE_ATA_COMPUTE_UNITS + MINT_TO_COMPUTE_UNITS)
+        }
+        TokenInstruction::MintChunk(_) => LAUNCH_COMPUTE_UNITS,
+        TokenInstruction::BatchRevoke(args) => args.mints as u32 * REVOKE_COMPUTE_UNITS,
+        TokenInstruction::RevokeAuthorities(args) => {
+            args.authority_types.len() as u32 * REVOKE_COMPUTE_UNITS
+        }
+        _ => 0,
+    };
+    BASE_COMPUTE_UNITS + work
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unpack_rejects_non_zero_reserved_bytes() {
+        let mut data = TokenInstruction::InitializeFixedSupply(InitializeArgs::default()).pack();
+        // The capabilities are the trailing four formerly reserved bytes, little endian
+        let len = data.len();
+        assert_eq!(
+            TokenInstruction::unpack(&data),
+            Ok(TokenInstruction::InitializeFixedSupply(InitializeArgs::default()))
+        );
+        for bit in (0..32).filter(|bit| KNOWN_CAPABILITIES & (1 << bit) == 0) {
+            data[len - 4..].copy_from_slice(&(1u32 << bit).to_le_bytes());
+            assert_eq!(TokenInstruction::unpack(&data), Err(ProgramError::InvalidInstructionData));
+        }
+    }
+
+    #[test]
+    fn each_capability_bit_toggles_on_its_own() {
+        for bit in (0..32).map(|bit| 1u32 << bit).filter(|bit| KNOWN_CAPABILITIES & bit != 0) {
+            let args = InitializeArgs { capabilities: bit, ..InitializeArgs::default() };
+            let instruction = TokenInstruction::InitializeFixedSupply(args.clone());
+            assert_eq!(TokenInstruction::unpack(&instruction.pack()), Ok(instruction));
+            for other in (0..32).map(|bit| 1u32 << bit) {
+                assert_eq!(args.has_capability(other), other == bit);
+            }
+            assert_eq!(args.needs_freeze_authority(), bit == CAPABILITY_REVOKE_FREEZE);
+        }
+    }
+
+    #[test]
+    fn unpack_rejects_conflicting_capabilities() {
+        let args = InitializeArgs {
+            capabilities: CAPABILITY_CREATE_ATA | CAPABILITY_PROTOCOL_DESTINATION,
+            ..InitializeArgs::default()
+        };
+        let data = TokenInstruction::InitializeFixedSupply(args).pack();
+        assert_eq!(TokenInstruction::unpack(&data), Err(ProgramError::InvalidInstructionData));
+
+        let args = InitializeArgs {
+            capabilities: CAPABILITY_REVOKE_FREEZE,
+            freeze_destination: true,
+            ..InitializeArgs::default()
+        };
+        let data = TokenInstruction::InitializeFixedSupply(args).pack();
+        assert_eq!(TokenInstruction::unpack(&data), Err(ProgramError::InvalidInstructionData));
+    }
+
+    // Packed entries laid out by hand as documented on `PackedDistributionEntries`
+    fn packed_bytes(entries: &[(Pubkey, u64)]) -> Vec<u8> {
+        let mut data = vec![entries.len() as u8];
+        for (destination, amount) in entries {
+            data.extend_from_slice(destination.as_ref());
+            data.extend_from_slice(&amount.to_le_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn packed_entries_decode_the_documented_layout() {
+        let entries = [(Pubkey::new_unique(), 1), (Pubkey::new_unique(), u64::MAX)];
+        let data = packed_bytes(&entries);
+        assert_eq!(data.len(), 1 + 2 * DistributionEntry::LEN);
+
+        let decoded = PackedDistributionEntries::try_from_slice(&data).unwrap();
+        let expected: Vec<_> = entries
+            .iter()
+            .map(|(destination, amount)| DistributionEntry {
+                destination: *destination,
+                amount: *amount,
+            })
+            .collect();
+        assert_eq!(decoded.0, expected);
+        assert_eq!(borsh::to_vec(&decoded).unwrap(), data);
+        assert_eq!(PackedDistributionEntries::try_from_slice(&[0]).unwrap().0, vec![]);
+    }
+
+    #[test]
+    fn packed_entries_reject_truncated_data() {
+        let data = packed_bytes(&[(Pubkey::new_unique(), 1), (Pubkey::new_unique(), 2)]);
+        for len in [0, 1, 40, data.len() - 1] {
+            assert!(PackedDistributionEntries::try_from_slice(&data[..len]).is_err());
+        }
+    }
+
+    #[test]
+    fn packed_entries_reject_oversized_data() {
+        let entries = vec![(Pubkey::new_unique(), 1); MAX_DISTRIBUTION_RECIPIENTS as usize + 1];
+        assert!(PackedDistributionEntries::try_from_slice(&packed_bytes(&entries)).is_err());
+        // A count past the limit is rejected before any entry is read
+        assert!(PackedDistributionEntries::try_from_slice(&[u8::MAX]).is_err());
+
+        // Bytes past the last entry don't belong to the instruction
+        let args = WritePackedDistributionListArgs {
+            offset: 0,
+            entries: PackedDistributionEntries(vec![DistributionEntry::default()]),
+        };
+        let mut data = TokenInstruction::WritePackedDistributionList(args).pack();
+        data.push(0);
+        assert_eq!(TokenInstruction::unpack(&data), Err(ProgramError::InvalidInstructionData));
+    }
+
+    #[test]
+    fn decode_authority_type_matches_the_token_2022_encoding() {
+        for b in 0..=14 {
+            let authority_type = decode_authority_type(b).unwrap();
+            // `SetAuthority` data is the instruction tag followed by the authority type byte
+            let instruction = spl_token_2022::instruction::set_authority(
+                &spl_token_2022::id(),
+                &Pubkey::new_unique(),
+                None,
+                authority_type,
+                &Pubkey::new_unique(),
+                &[],
+            )
+            .unwrap();
+            assert_eq!(instruction.data[1], b);
+        }
+    }
+
+    #[test]
+    fn recommended_compute_units_grow_with_the_recipient_count() {
+        let distribute_to = |recipients: usize| {
+            let recipient = OwnerAllocation { owner: Pubkey::new_unique(), amount: 1 };
+            let args = DistributeToOwnersArgs {
+                recipients: vec![recipient; recipients],
+                num_recipients: recipients as u16,
+            };
+            recommended_compute_units(&TokenInstruction::DistributeToOwners(args))
+        };
+        let launch = TokenInstruction::InitializeFixedSupply(InitializeArgs::default());
+
+        assert!(distribute_to(1) > recommended_compute_units(&launch));
+        assert!(distribute_to(2) > distribute_to(1));
+        assert!(distribute_to(10) > distribute_to(2));
+        assert_eq!(distribute_to(10) - distribute_to(9), distribute_to(2) - distribute_to(1));
+    }
+
+    #[test]
+    fn decode_authority_type_rejects_unknown_values() {
+        for b in [15, 16, u8::MAX] {
+            assert_eq!(decode_authority_type(b), Err(ProgramError::InvalidInstructionData));
+        }
+    }
+}
@@ -2,160 +2,90 @@
 // This program creates a token with a fixed total supply and permanently revokes minting authority
 // to ensure no additional tokens can ever be created after the initial mint.
 
-use solana_program::{
-    account_info::{next_account_info, AccountInfo},
-    entrypoint, entrypoint::ProgramResult,
-    program_error::ProgramError,
-    program_pack::Pack,
-    pubkey::Pubkey,
-    program_option::COption,
-};
-use spl_token::{
-    instruction::{mint_to, set_authority},
-    state::{Mint, Account as TokenAccount},
-    instruction::AuthorityType,
-};
+#[macro_use]
+mod macros;
 
-// Token configuration constants
-// Total supply: 500 million tokens
-const TOTAL_SUPPLY: u64 = 500_000_000;
-// Token decimals: 9 (standard for most Solana tokens)
-const DECIMALS: u8 = 9;
+pub mod client;
+pub mod error;
+pub mod instruction;
+pub mod pda;
+pub mod processor;
+pub mod state;
+pub mod utils;
 
-/// Custom error codes for specific validation failures
-#[derive(Debug, Copy, Clone)]
-pub enum CustomError {
-    InvalidMintState = 6000,        // Mint account is not in the expected initial state
-    TokenAccountNotEmpty,           // Token account already contains tokens or has delegates
-    MintAuthorityNotRevoked,       // Mint authority was not successfully revoked
-    TokenAccountOwnerMismatch,     // Token account owner doesn't match the payer
-    MintAuthorityMismatch,         // Mint authority doesn't match the payer
-}
+use solana_program::{entrypoint, pubkey, pubkey::Pubkey};
 
-impl From<CustomError> for ProgramError {
-    fn from(e: CustomError) -> Self {
-        ProgramError::Custom(e as u32)
-    }
-}
+pub use processor::process_instruction;
 
-/// Main program instruction processor
-/// This function performs the following operations:
-/// 1. Validates all input accounts and their states
-/// 2. Mints the total supply to the specified token account
-/// 3. Permanently revokes the mint authority to prevent future minting
-/// 4. Verifies the mint authority was successfully revoked
-pub fn process_instruction(
-    _program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    _instruction_data: &[u8],
-) -> ProgramResult {
-    // Calculate total supply with decimals (500M * 10^9)
-    let total_supply_with_decimals = TOTAL_SUPPLY
-        .checked_mul(10u64.pow(DECIMALS as u32))
-        .ok_or(ProgramError::InvalidArgument)?;
-
-    // Extract required accounts from the instruction
-    let accounts_iter = &mut accounts.iter();
-    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
-    let token_account = next_account_info(accounts_iter)?;     // The destination token account
-    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
-    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
-    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
-
-    // Verify that required signers have signed the transaction
-    if !mint_authority.is_signer || !payer.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
-    }
-
-    // Ensure mint authority and payer are the same account for security
-    if mint_authority.key != payer.key {
-        return Err(CustomError::MintAuthorityMismatch.into());
-    }
-
-    // Verify that mint and token accounts are owned by the SPL Token program
-    if *mint_account.owner != spl_token::id() || *token_account.owner != spl_token::id() {
-        return Err(ProgramError::IllegalOwner);
-    }
+// Version of this program recorded in every launch receipt, bump it with each release so a
+// receipt shows which program version launched the token
+pub const PROGRAM_VERSION: u32 = 1;
 
-    // Ensure accounts are writable for the operations we need to perform
-    if !mint_account.is_writable || !token_account.is_writable {
-        return Err(ProgramError::InvalidAccountData);
-    }
+/// Returns the program version recorded in the launch receipts this build writes
+pub fn program_version() -> u32 {
+    PROGRAM_VERSION
+}
 
-    // Validate mint account state before minting
-    // The mint must be initialized with correct decimals, zero supply, no freeze authority,
-    // and the mint authority must match the provided authority
-    let mint_data = Mint::unpack(&mint_account.data.borrow())?;
-    if !(mint_data.is_initialized
-        && mint_data.decimals == DECIMALS
-        && mint_data.supply == 0
-        && mint_data.freeze_authority.is_none()
-        && mint_data.mint_authority == COption::Some(*mint_authority.key))
-    {
-        return Err(CustomError::InvalidMintState.into());
-    }
-    
-    // Validate token account state before minting
-    // The token account must be owned by the payer, associated with the correct mint,
-    // have zero balance, and no delegates or close authority
-    let token_data = TokenAccount::unpack(&token_account.data.borrow())?;
-    if token_data.owner != *payer.key {
-        return Err(CustomError::TokenAccountOwnerMismatch.into());
-    }
-    if !(token_data.mint == *mint_account.key
-        && token_data.amount == 0
-        && token_data.delegate.is_none()
-        && token_data.close_authority.is_none())
-    {
-        return Err(CustomError::TokenAccountNotEmpty.into());
-    }
+// Token configuration constants
+// Total supply: 500 million tokens
+pub const TOTAL_SUPPLY: u64 = 500_000_000;
+// Token decimals: 9 (standard for most Solana tokens)
+pub const DECIMALS: u8 = 9;
+// Smallest total supply a launch may use, in whole tokens, adjust to match platform policy
+pub const MIN_SUPPLY: u64 = 1;
+// Compile-time guard: the default supply in base units (TOTAL_SUPPLY * 10^DECIMALS) must fit
+// in a u64 and meet MIN_SUPPLY, so editing the constants above into an invalid supply fails the
+// build instead of every launch
+const _: () = {
+    let factor = match 10u64.checked_pow(DECIMALS as u32) {
+        Some(factor) => factor,
+        None => panic!("10^DECIMALS overflows u64"),
+    };
+    assert!(
+        TOTAL_SUPPLY.checked_mul(factor).is_some(),
+        "TOTAL_SUPPLY * 10^DECIMALS overflows u64"
+    );
+    assert!(TOTAL_SUPPLY >= MIN_SUPPLY, "TOTAL_SUPPLY is below MIN_SUPPLY");
+};
+// Largest decimals SPL amounts can represent: one whole token (10^decimals) must fit in a u64
+pub const MAX_DECIMALS: u8 = 19;
+// Decimals a launch may configure instead of the default, adjust to match platform policy
+pub const ALLOWED_DECIMALS: &[u8] = &[0, 2, 6, 9];
+// How many more decimals than whole-token supply digits a custom supply may use before it is
+// rejected as impractical (e.g. 1 token with 9 decimals), adjust to match platform policy
+pub const MAX_DECIMALS_OVER_SUPPLY_DIGITS: u8 = 6;
+// Fewest signatures a multisig treasury may require, a 1-of-N multisig is a single key in disguise
+pub const MIN_TREASURY_MULTISIG_SIGNERS: u8 = 2;
+// Log verbosity: 1 logs milestones only, 2 also logs every validation check with the observed
+// and expected values, which costs extra compute units
+pub const LOG_LEVEL: u8 = 1;
+// AMM program a treasury and liquidity launch may seed a pool with (Raydium AMM v4 by default)
+pub const AMM_PROGRAM_ID: Pubkey = pubkey!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8");
+// Programs whose accounts a protocol-destination launch may mint into (the AMM above and SPL
+// Token Swap by default), adjust to match platform policy
+pub const ALLOWED_DESTINATION_PROGRAMS: &[Pubkey] = &[
+    AMM_PROGRAM_ID,
+    pubkey!("SwapsVeCiPHMUAtzQWZw7RjsKjgCjhwU55QGu4U1Szw"),
+];
+// Governance program whose PDA a governance launch may hand the mint authority to (SPL Governance
+// by default), replace it with the governance program the platform trusts
+pub const GOVERNANCE_PROGRAM_ID: Pubkey = pubkey!("GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw");
+
+// Added to every custom error code so they don't collide with the codes of a program that
+// composes this one over CPI, 0 keeps the documented 6000-based codes
+pub const ERROR_CODE_OFFSET: u32 = 0;
 
-    // Step 1: Mint the total supply to the token account
-    // This creates all tokens that will ever exist for this mint
-    solana_program::program::invoke(
-        &mint_to(
-            token_program.key,
-            mint_account.key,
-            token_account.key,
-            mint_authority.key,
-            &[],
-            total_supply_with_decimals,
-        )?,
-        &[
-            mint_account.clone(),
-            token_account.clone(),
-            mint_authority.clone(),
-            token_program.clone(),
-        ],
-    )?;
+// Program entrypoint - required for all Solana programs
+entrypoint!(process_instruction);
 
-    // Step 2: Permanently revoke the mint authority
-    // This ensures no additional tokens can ever be minted, making the supply truly fixed
-    solana_program::program::invoke(
-        &set_authority(
-            token_program.key,
-            mint_account.key,
-            None,  // Set authority to None (revoked)
-            AuthorityType::MintTokens,
-            mint_authority.key,
-            &[mint_authority.key],
-        )?,
-        &[
-            mint_account.clone(),
-            mint_authority.clone(),
-            token_program.clone(),
-        ],
-    )?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // Step 3: Final verification - ensure mint authority was successfully revoked
-    // This is a critical security check to confirm the token supply is now permanently fixed
-    let final_mint_data = Mint::unpack(&mint_account.data.borrow())?;
-    if final_mint_data.mint_authority.is_some() {
-        return Err(CustomError::MintAuthorityNotRevoked.into());
+    #[test]
+    fn default_supply_in_base_units_fits_in_u64() {
+        // 500 million tokens with 9 decimals, checked against overflow at compile time above
+        let base_units = TOTAL_SUPPLY.checked_mul(10u64.pow(DECIMALS as u32));
+        assert_eq!(base_units, Some(500_000_000_000_000_000));
     }
-
-    Ok(())
 }
-
-// Program entrypoint - required for all Solana programs
-entrypoint!(process_instruction);
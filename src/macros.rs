@@ -0,0 +1,21 @@
+// Assertion helpers shared by the processor
+
+/// Returns early with `$err` converted into the function's error type unless `$cond` holds
+/// Keeps each failure point of a validation chain on a single line.
+macro_rules! require {
+    ($cond:expr, $err:expr) => {
+        if !$cond {
+            return Err($err.into());
+        }
+    };
+}
+
+/// Logs a diagnostic line only when `LOG_LEVEL` is verbose (2 or higher)
+/// Meant for describing each validation check with its observed and expected values.
+macro_rules! verbose {
+    ($($arg:tt)+) => {
+        if $crate::LOG_LEVEL >= 2 {
+            solana_program::msg!($($arg)+);
+        }
+    };
+}
@@ -0,0 +1,133 @@
+// Program derived address helpers
+// Bumps are supplied by the client in instruction data and checked with `create_program_address`,
+// which is far cheaper on-chain than searching for the bump with `find_program_address`.
+
+use solana_program::{bpf_loader_upgradeable, entrypoint::ProgramResult, pubkey::Pubkey};
+
+use crate::{
+    error::CustomError,
+    processor::ASSOCIATED_TOKEN_PROGRAM_ID,
+    state::{
+        CHUNKED_MINT_SEED, CONFIG_SEED, DISTRIBUTION_CURSOR_SEED, DISTRIBUTION_SEED,
+        EVENT_AUTHORITY_SEED, GOVERNANCE_RECORD_SEED, GOVERNANCE_SEED, LAUNCHER_ALLOWLIST_SEED,
+        LAUNCH_STATS_SEED, MINT_AUTHORITY_SEED, RECEIPT_SEED, SUPPLY_CAP_SEED, SUPPLY_LOCK_SEED,
+        TEST_MINT_SEED, TIMELOCK_SEED,
+    },
+};
+
+/// Verifies that `seeds` plus the caller-supplied `bump` derive exactly `expected`
+pub fn verify_pda(
+    expected: &Pubkey,
+    seeds: &[&[u8]],
+    bump: u8,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    let bump_seed = [bump];
+    let mut seeds_with_bump = seeds.to_vec();
+    seeds_with_bump.push(&bump_seed);
+
+    match Pubkey::create_program_address(&seeds_with_bump, program_id) {
+        Ok(derived) if derived == *expected => Ok(()),
+        _ => Err(CustomError::InvalidPdaBump.into()),
+    }
+}
+
+/// Derives the launch receipt address and canonical bump for a mint and nonce (client side)
+pub fn find_receipt_address(program_id: &Pubkey, mint: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RECEIPT_SEED, mint.as_ref(), &nonce.to_le_bytes()], program_id)
+}
+
+/// Derives the distribution list address and canonical bump for a mint (client side)
+pub fn find_distribution_list_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DISTRIBUTION_SEED, mint.as_ref()], program_id)
+}
+
+/// Derives the program-derived mint authority address and canonical bump for a mint (client side)
+/// The program re-derives it from the supplied bump with `create_program_address`.
+pub fn find_mint_authority_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_AUTHORITY_SEED, mint.as_ref()], program_id)
+}
+
+/// Derives the chunked mint tracking address and canonical bump for a mint (client side)
+pub fn find_chunked_mint_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CHUNKED_MINT_SEED, mint.as_ref()], program_id)
+}
+
+/// Derives the distribution cursor address and canonical bump for a mint (client side)
+pub fn find_distribution_cursor_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DISTRIBUTION_CURSOR_SEED, mint.as_ref()], program_id)
+}
+
+/// Derives the launch stats address and canonical bump for a mint (client side)
+pub fn find_launch_stats_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LAUNCH_STATS_SEED, mint.as_ref()], program_id)
+}
+
+/// Derives the supply lock address and canonical bump for a mint (client side)
+/// The lock PDA is also the owner of the vault token account.
+pub fn find_supply_lock_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SUPPLY_LOCK_SEED, mint.as_ref()], program_id)
+}
+
+/// Derives the governance authority address and canonical bump of `governance_program` for a
+/// mint (client side)
+pub fn find_governance_authority_address(
+    governance_program: &Pubkey,
+    mint: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GOVERNANCE_SEED, mint.as_ref()], governance_program)
+}
+
+/// Derives the governance record address and canonical bump for a mint (client side)
+pub fn find_governance_record_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GOVERNANCE_RECORD_SEED, mint.as_ref()], program_id)
+}
+
+/// Derives the timelock freeze authority address and canonical bump of `timelock_program` for a
+/// mint (client side)
+pub fn find_timelock_authority_address(timelock_program: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TIMELOCK_SEED, mint.as_ref()], timelock_program)
+}
+
+/// Derives the supply cap address and canonical bump for a mint (client side)
+pub fn find_supply_cap_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SUPPLY_CAP_SEED, mint.as_ref()], program_id)
+}
+
+/// Derives the launcher allowlist address and canonical bump of the program (client side)
+pub fn find_launcher_allowlist_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LAUNCHER_ALLOWLIST_SEED], program_id)
+}
+
+/// Derives the program's config address and canonical bump (client side)
+pub fn find_config_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], program_id)
+}
+
+/// Derives the ProgramData account of an upgradeable program, which names its upgrade authority
+pub fn find_program_data_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id())
+}
+
+/// Derives a mint's test mint record address and canonical bump (client side)
+pub fn find_test_mint_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TEST_MINT_SEED, mint.as_ref()], program_id)
+}
+
+/// Derives the event authority address and canonical bump of the program (client side)
+pub fn find_event_authority_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], program_id)
+}
+
+/// Derives the associated token account of `wallet` for `mint` under `token_program`
+pub fn find_associated_token_address(
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
@@ -0,0 +1,5241 @@
+// Instruction processing logic for the fixed supply token program
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    compute_units::sol_remaining_compute_units,
+    ed25519_program,
+    entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE},
+    instruction::{AccountMeta, Instruction},
+    msg,
+    program::set_return_data,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey,
+    pubkey::Pubkey,
+    program_option::COption,
+    system_instruction, system_program,
+    sysvar::{self, clock::Clock, rent::Rent, Sysvar},
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_token_metadata::{
+    accounts::{MasterEdition, Metadata},
+    instructions::{
+        CreateMetadataAccountV3Cpi, CreateMetadataAccountV3CpiAccounts,
+        CreateMetadataAccountV3InstructionArgs, UpdateMetadataAccountV2Cpi,
+        UpdateMetadataAccountV2CpiAccounts, UpdateMetadataAccountV2InstructionArgs,
+        VerifyCollectionV1Cpi, VerifyCollectionV1CpiAccounts,
+    },
+    types::{Collection, Creator, DataV2},
+};
+use spl_token_2022::{
+    extension::{
+        confidential_transfer::{instruction::initialize_mint, ConfidentialTransferMint},
+        confidential_transfer_fee::ConfidentialTransferFeeConfig,
+        default_account_state::instruction::initialize_default_account_state,
+        group_member_pointer::GroupMemberPointer, group_pointer::GroupPointer,
+        interest_bearing_mint::InterestBearingConfig, metadata_pointer::MetadataPointer,
+        mint_close_authority::MintCloseAuthority, permanent_delegate::PermanentDelegate,
+        transfer_fee::TransferFeeConfig, transfer_hook::TransferHook, BaseStateWithExtensions,
+        ExtensionType, StateWithExtensions, StateWithExtensionsMut,
+    },
+    instruction::{
+        freeze_account, initialize_mint2, mint_to, set_authority, thaw_account, transfer_checked,
+        AuthorityType, MAX_SIGNERS,
+    },
+    solana_zk_token_sdk::zk_token_elgamal::pod::ElGamalPubkey,
+    state::{Account as TokenAccount, AccountState, Mint, Multisig},
+};
+use spl_token_group_interface::{
+    instruction::{initialize_group, initialize_member},
+    state::{TokenGroup, TokenGroupMember},
+};
+use spl_token_metadata_interface::{
+    instruction::update_field,
+    state::{Field, TokenMetadata},
+};
+
+use crate::{
+    error::CustomError,
+    instruction::{
+        decode_authority_type, recommended_compute_units, AdditionalMetadataArgs, AllocationPlan,
+        BatchInitializeArgs,
+        BatchRevokeArgs, ConfidentialTransferConfig, ConfigAdminArgs, CreateDistributionListArgs,
+        CreateMetadataArgs, CreateMintArgs, DistributeArgs, DistributeChunkArgs,
+        DistributeToOwnersArgs, EmitEventArgs, FinalizeReport, GovernanceLaunchArgs, InitializeArgs,
+        InitializeGroupArgs, IsAdminArgs, LaunchAllArgs, LaunchEvent, LauncherArgs,
+        LockedLaunchArgs, MintChunkArgs, MintPolicy, MultisigTreasuryArgs, PdaAuthorityArgs,
+        ReportValue, RescueArgs, RevokeAuthoritiesArgs, RevokeAuthorityArgs, SeedLaunchArgs,
+        StartChunkedMintArgs, SupplyCapArgs, SupplyHashArgs, TimelockFreezeArgs, TokenInstruction,
+        TreasuryAndLiquidityArgs, UiSupplyArgs, ValidateOnlyArgs, ValidationCheck, ValidationReport,
+        ValuationArgs, VerifyCreatorArgs, VerifyMetadataArgs, WriteDistributionListArgs,
+        CAPABILITY_CREATE_ATA, CAPABILITY_IMMUTABLE_PROGRAM, CAPABILITY_PROTOCOL_DESTINATION,
+        CAPABILITY_REVOKE_FREEZE, CAPABILITY_SKIP_FINAL_VERIFICATION, LAUNCH_AUTHORIZATION_PREFIX,
+        MAX_BASIS_POINTS, MAX_DISTRIBUTION_CHUNK, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH,
+        MAX_URI_LENGTH, POLICY_DECIMALS_ALLOWED, POLICY_FREEZE_AUTHORITY_ALLOWED,
+        POLICY_MINT_AUTHORITY_REVOKED, POLICY_SUPPLY_NONZERO,
+    },
+    pda::{
+        find_associated_token_address, find_config_address, find_program_data_address, verify_pda,
+    },
+    state::{
+        AccountType, ChunkedMint, ConfigSettings, DistributionCursor, DistributionEntry,
+        DistributionListHeader, GovernanceRecord, LaunchReceipt, LaunchStats, LauncherAllowlist,
+        OracleApproval, ProgramConfig, SupplyCap, SupplyLock, CHUNKED_MINT_SEED, CONFIG_SEED,
+        DISTRIBUTION_CURSOR_SEED, DISTRIBUTION_SEED, EVENT_AUTHORITY_SEED, GOVERNANCE_RECORD_SEED,
+        GOVERNANCE_SEED, LAUNCHER_ALLOWLIST_SEED, LAUNCH_STATS_SEED, MAX_ALLOWLISTED_LAUNCHERS,
+        MINT_AUTHORITY_SEED, RECEIPT_SEED, SUPPLY_CAP_SEED, SUPPLY_LOCK_SEED, TIMELOCK_SEED,
+    },
+    utils::{
+        derive_launch_params, format_result_line, format_ui_amount, fully_diluted_value,
+        supply_hash, token2022_mint_len,
+    },
+    ALLOWED_DECIMALS, ALLOWED_DESTINATION_PROGRAMS, AMM_PROGRAM_ID, DECIMALS, GOVERNANCE_PROGRAM_ID,
+    MAX_DECIMALS, MAX_DECIMALS_OVER_SUPPLY_DIGITS, MIN_SUPPLY, MIN_TREASURY_MULTISIG_SIGNERS,
+    PROGRAM_VERSION, TOTAL_SUPPLY,
+};
+#[cfg(feature = "test-mode")]
+use crate::{
+    instruction::TestMintArgs,
+    state::{TestMint, TEST_MINT_SEED},
+};
+
+use std::slice;
+
+/// Token account extensions that minting is known to work with
+/// Destination accounts carrying any other extension are rejected rather than minted into blindly.
+pub const SUPPORTED_ACCOUNT_EXTENSIONS: &[ExtensionType] = &[
+    ExtensionType::ImmutableOwner,
+    ExtensionType::TransferFeeAmount,
+    ExtensionType::MemoTransfer,
+    ExtensionType::NonTransferableAccount,
+    ExtensionType::TransferHookAccount,
+    ExtensionType::CpiGuard,
+];
+
+/// Associated Token Account program, allowed alongside verified launches
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// Compute Budget program, allowed alongside verified launches so priority fees can be set
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
+    pubkey!("ComputeBudget111111111111111111111111111111");
+
+/// Main program instruction processor
+/// Decodes the instruction data and dispatches to the matching handler
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction = TokenInstruction::unpack(instruction_data)?;
+    // Multi-CPI instructions can run out of compute under the default limit, so say so up front
+    if let TokenInstruction::LaunchAll(_)
+    | TokenInstruction::BatchInitialize(_)
+    | TokenInstruction::BatchRevoke(_)
+    | TokenInstruction::Distribute(_)
+    | TokenInstruction::DistributeToOwners(_) = &instruction
+    {
+        warn_if_low_compute(recommended_compute_units(&instruction));
+    }
+
+    match instruction {
+        TokenInstruction::InitializeFixedSupply(args) => {
+            process_initialize_fixed_supply(program_id, accounts, args)
+        }
+        TokenInstruction::TreasuryAndLiquidity(args) => {
+            process_treasury_and_liquidity(program_id, accounts, args)
+        }
+        TokenInstruction::CreateMetadata(args) => process_create_metadata(accounts, args),
+        TokenInstruction::RemainingMintable => process_remaining_mintable(accounts),
+        TokenInstruction::BatchInitialize(args) => {
+            process_batch_initialize(program_id, accounts, args)
+        }
+        TokenInstruction::SetFreezeAuthority => process_set_freeze_authority(accounts),
+        TokenInstruction::CreateDistributionList(args) => {
+            process_create_distribution_list(program_id, accounts, args)
+        }
+        TokenInstruction::WriteDistributionList(args) => {
+            process_write_distribution_list(program_id, accounts, args)
+        }
+        TokenInstruction::Distribute(args) => process_distribute(program_id, accounts, args),
+        TokenInstruction::InitializeGroup(args) => process_initialize_group(accounts, args),
+        TokenInstruction::AddGroupMember => process_add_group_member(accounts),
+        TokenInstruction::VerifyGroupMembership => process_verify_group_membership(accounts),
+        TokenInstruction::LockMetadata => process_lock_metadata(accounts),
+        TokenInstruction::CreateMint(args) => process_create_mint(accounts, args),
+        TokenInstruction::RevokeAuthority(args) => process_revoke_authority(accounts, args),
+        TokenInstruction::InitializeWithPdaAuthority(args) => {
+            process_initialize_with_pda_authority(program_id, accounts, args)
+        }
+        TokenInstruction::StartChunkedMint(args) => {
+            process_start_chunked_mint(program_id, accounts, args)
+        }
+        TokenInstruction::MintChunk(args) => process_mint_chunk(program_id, accounts, args),
+        TokenInstruction::CheckPolicy(policy) => process_check_policy(accounts, policy),
+        TokenInstruction::InitializeWithLock(args) => {
+            process_initialize_with_lock(program_id, accounts, args)
+        }
+        TokenInstruction::ReleaseLocked => process_release_locked(program_id, accounts),
+        TokenInstruction::LaunchAll(args) => process_launch_all(program_id, accounts, args),
+        TokenInstruction::EmitEvent(args) => process_emit_event(program_id, accounts, args),
+        TokenInstruction::VerifyCreator(args) => {
+            process_verify_creator(program_id, accounts, args)
+        }
+        TokenInstruction::DistributeToOwners(args) => {
+            process_distribute_to_owners(program_id, accounts, args)
+        }
+        TokenInstruction::VerifyMetadata(args) => process_verify_metadata(accounts, args),
+        TokenInstruction::InitializeWithAllocationPlan(plan) => {
+            process_initialize_with_allocation_plan(program_id, accounts, plan)
+        }
+        TokenInstruction::Rescue(args) => process_rescue(program_id, accounts, args),
+        TokenInstruction::GetLaunchStats => process_get_launch_stats(program_id, accounts),
+        TokenInstruction::InitializeWithUiSupply(args) => {
+            process_initialize_with_ui_supply(program_id, accounts, args)
+        }
+        TokenInstruction::BatchRevoke(args) => process_batch_revoke(accounts, args),
+        TokenInstruction::WritePackedDistributionList(args) => process_write_distribution_list(
+            program_id,
+            accounts,
+            WriteDistributionListArgs { offset: args.offset, entries: args.entries.0 },
+        ),
+        TokenInstruction::IsAdmin(args) => process_is_admin(program_id, accounts, args),
+        TokenInstruction::InitializeWithGovernance(args) => {
+            process_initialize_with_governance(program_id, accounts, args)
+        }
+        TokenInstruction::RecordSupplyCap(args) => {
+            process_record_supply_cap(program_id, accounts, args)
+        }
+        TokenInstruction::GetSupplyCap => process_get_supply_cap(program_id, accounts),
+        TokenInstruction::GetValuation(args) => process_get_valuation(accounts, args),
+        TokenInstruction::InitializeFromSeed(args) => {
+            process_initialize_from_seed(program_id, accounts, args)
+        }
+        TokenInstruction::AddLauncher(args) => process_add_launcher(program_id, accounts, args),
+        TokenInstruction::RemoveLauncher(args) => {
+            process_remove_launcher(program_id, accounts, args)
+        }
+        TokenInstruction::VerifySupplyHash(args) => process_verify_supply_hash(accounts, args),
+        TokenInstruction::Cleanup => process_cleanup(program_id, accounts),
+        TokenInstruction::RevokeAuthorities(args) => process_revoke_authorities(accounts, args),
+        TokenInstruction::InitializeIntoMultisig(args) => {
+            process_initialize_into_multisig(program_id, accounts, args)
+        }
+        TokenInstruction::ValidateOnly(args) => process_validate_only(accounts, args),
+        TokenInstruction::WriteAdditionalMetadata(args) => {
+            process_write_additional_metadata(accounts, args)
+        }
+        TokenInstruction::VerifyReceiptIntegrity => {
+            process_verify_receipt_integrity(program_id, accounts)
+        }
+        TokenInstruction::InitializeWithTimelockFreeze(args) => {
+            process_initialize_with_timelock_freeze(program_id, accounts, args)
+        }
+        TokenInstruction::DistributeChunk(args) => {
+            process_distribute_chunk(program_id, accounts, args)
+        }
+        TokenInstruction::VerifyAndFinalize(args) => {
+            process_verify_and_finalize(program_id, accounts, args)
+        }
+        TokenInstruction::SetConfigAdmin(args) => {
+            process_set_config_admin(program_id, accounts, args)
+        }
+        TokenInstruction::UpdateConfig(settings) => {
+            process_update_config(program_id, accounts, settings)
+        }
+        #[cfg(feature = "test-mode")]
+        TokenInstruction::TestMint(args) => process_test_mint(program_id, accounts, args),
+        #[cfg(feature = "test-mode")]
+        TokenInstruction::ResetTestMint => process_reset_test_mint(program_id, accounts),
+        // Already rejected by `unpack`, test mode isn't compiled into this build
+        #[cfg(not(feature = "test-mode"))]
+        TokenInstruction::TestMint(_) | TokenInstruction::ResetTestMint => {
+            Err(ProgramError::InvalidInstructionData)
+        }
+    }
+}
+
+/// Processes the `CreateMint` instruction
+/// Creates a fresh mint funded by the payer, with a possibly different initial mint authority
+pub fn process_create_mint(accounts: &[AccountInfo], args: CreateMintArgs) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let mint_account = next_account_info(accounts_iter)?;      // The new token mint account
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+    let system_program = next_account_info(accounts_iter)?;    // System program
+
+    if !mint_account.is_signer || !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if !is_token_program(token_program.key) || *system_program.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    require_executable(&[token_program, system_program])?;
+    ensure_distinct(&[mint_account, payer])?;
+
+    // The authority has to be a key someone can sign with, or the launch could never run
+    if args.mint_authority == Pubkey::default() || is_reserved_authority_key(&args.mint_authority)
+    {
+        return Err(CustomError::InvalidAuthorityAccount.into());
+    }
+    if let Some(freeze_authority) = &args.freeze_authority {
+        require!(
+            *freeze_authority != Pubkey::default() && !is_reserved_authority_key(freeze_authority),
+            CustomError::InvalidAuthorityAccount
+        );
+    }
+    let decimals = create_mint_decimals(args.decimals)?;
+    // The default account state is a Token-2022 extension, legacy SPL Token has no equivalent
+    if args.default_account_frozen || args.confidential_transfer.is_some() {
+        require!(*token_program.key == spl_token_2022::id(), ProgramError::IncorrectProgramId);
+    }
+    if let Some(config) = &args.confidential_transfer {
+        validate_confidential_config(config)?;
+    }
+    let confidential_transfer = args.confidential_transfer.as_ref();
+    require_rent_funds(
+        payer,
+        &[mint_len(args.default_account_frozen, confidential_transfer.is_some())?],
+    )?;
+
+    create_and_initialize_mint(
+        mint_account,
+        payer,
+        token_program,
+        system_program,
+        &args.mint_authority,
+        args.freeze_authority.as_ref(),
+        decimals,
+        args.default_account_frozen,
+        confidential_transfer,
+    )
+}
+
+// Rejects confidential transfer settings that would leave the extension unusable
+fn validate_confidential_config(config: &ConfidentialTransferConfig) -> ProgramResult {
+    if let Some(authority) = &config.authority {
+        require!(
+            *authority != Pubkey::default() && !is_reserved_authority_key(authority),
+            CustomError::ConfidentialConfigInvalid
+        );
+    } else {
+        // Nobody could approve accounts, so they could never use confidential transfers
+        require!(config.auto_approve_new_accounts, CustomError::ConfidentialConfigInvalid);
+    }
+    // The all-zero key is how the extension encodes "no auditor"
+    require!(
+        config.auditor_elgamal_pubkey != Some([0; 32]),
+        CustomError::ConfidentialConfigInvalid
+    );
+    Ok(())
+}
+
+/// Processes the `LaunchAll` instruction
+/// Runs the whole simple launch in one invocation: create the mint, create the payer's
+/// associated token account, mint the total supply into it and revoke the mint authority
+pub fn process_launch_all(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: LaunchAllArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let gate = LaunchGate::parse(program_id, accounts_iter, 1)?;
+    let mint_account = next_account_info(accounts_iter)?;      // The new token mint account
+    let token_account = next_account_info(accounts_iter)?;     // The payer's associated account
+    let payer = next_account_info(accounts_iter)?;             // Payer and mint authority (signs)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+    let system_program = next_account_info(accounts_iter)?;    // System program
+    let ata_program = next_account_info(accounts_iter)?;       // Associated Token Account program
+
+    require!(mint_account.is_signer, ProgramError::MissingRequiredSignature);
+    validate_authority(payer, payer)?;
+    gate.authorize(payer.key, &[mint_account.key])?;
+    require!(
+        is_token_program(token_program.key)
+            && *system_program.key == system_program::id()
+            && *ata_program.key == ASSOCIATED_TOKEN_PROGRAM_ID,
+        ProgramError::IncorrectProgramId
+    );
+    require_executable(&[token_program, system_program, ata_program])?;
+    ensure_distinct(&[mint_account, token_account, payer])?;
+
+    let decimals = create_mint_decimals(args.decimals)?;
+    let total_supply_with_decimals = total_supply_with_decimals(decimals)?;
+    let new_account_lens = [mint_len(false, false)?, associated_account_len(token_program.key)?];
+    require_rent_funds(payer, &new_account_lens)?;
+    gate.charge(payer, 1, &new_account_lens)?;
+
+    create_and_initialize_mint(
+        mint_account,
+        payer,
+        token_program,
+        system_program,
+        payer.key,
+        None,
+        decimals,
+        false,
+        None,
+    )?;
+
+    create_associated_token_account(
+        payer,
+        payer,
+        token_account,
+        mint_account,
+        system_program,
+        token_program,
+        ata_program,
+        false,
+    )?;
+
+    // Same checks as every other launch, even though both accounts were created just now
+    validate_mint(mint_account, payer, Some(decimals), None)?;
+    validate_destination(token_account, mint_account, Some(payer.key))?;
+
+    mint_supply(
+        token_program,
+        mint_account,
+        token_account,
+        payer,
+        total_supply_with_decimals,
+        decimals,
+    )?;
+    revoke_mint_authority(token_program, mint_account, payer, total_supply_with_decimals)
+}
+
+// Logs a hint when fewer compute units remain than the instruction is expected to need
+// The instruction still runs, the hint only explains a later out-of-compute failure
+fn warn_if_low_compute(recommended: u32) {
+    let remaining = sol_remaining_compute_units();
+    if remaining < recommended as u64 {
+        msg!(
+            "Only {} compute units left, {} recommended: raise the limit with \
+             ComputeBudgetInstruction::set_compute_unit_limit",
+            remaining,
+            recommended
+        );
+    }
+}
+
+// Space of a mint this program creates, with room for the requested extensions
+fn mint_len(default_account_frozen: bool, confidential: bool) -> Result<usize, ProgramError> {
+    let mut extensions = Vec::new();
+    if default_account_frozen {
+        extensions.push(ExtensionType::DefaultAccountState);
+    }
+    if confidential {
+        extensions.push(ExtensionType::ConfidentialTransferMint);
+    }
+    token2022_mint_len(&extensions)
+}
+
+// Space of an associated token account the ATA program creates, which on Token-2022 carries
+// the immutable owner extension
+fn associated_account_len(token_program: &Pubkey) -> Result<usize, ProgramError> {
+    if *token_program == spl_token_2022::id() {
+        ExtensionType::try_calculate_account_len::<TokenAccount>(&[ExtensionType::ImmutableOwner])
+    } else {
+        Ok(TokenAccount::LEN)
+    }
+}
+
+// Total rent-exempt minimum of accounts of the given sizes
+fn rent_for(account_lens: &[usize]) -> Result<u64, ProgramError> {
+    let rent = Rent::get()?;
+    account_lens
+        .iter()
+        .try_fold(0u64, |total, len| total.checked_add(rent.minimum_balance(*len)))
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+// Fails early, logging the shortfall, when the payer can't fund the rent of every account of
+// the given sizes, rather than deep inside a system program CPI
+fn require_rent_funds(payer: &AccountInfo, account_lens: &[usize]) -> ProgramResult {
+    let required = rent_for(account_lens)?;
+    let available = payer.lamports();
+    if available < required {
+        msg!(
+            "Payer {} has {} lamports, {} short of the {} needed for rent",
+            payer.key,
+            available,
+            required - available,
+            required
+        );
+        return Err(CustomError::InsufficientRentFunds.into());
+    }
+    Ok(())
+}
+
+// Transfers `fee` lamports from the payer to the treasury, after checking the payer keeps enough
+// for the rent of the accounts of the given sizes the launch still creates
+fn pay_launch_fee<'a>(
+    payer: &AccountInfo<'a>,
+    treasury: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    fee: u64,
+    account_lens: &[usize],
+) -> ProgramResult {
+    require!(*system_program.key == system_program::id(), ProgramError::IncorrectProgramId);
+    require_executable(&[system_program])?;
+    require!(payer.is_writable && treasury.is_writable, ProgramError::InvalidAccountData);
+
+    let required = rent_for(account_lens)?
+        .checked_add(fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let available = payer.lamports();
+    if available < required {
+        msg!(
+            "Payer {} has {} lamports, {} short of the {} fee and rent",
+            payer.key,
+            available,
+            required - available,
+            required
+        );
+        return Err(CustomError::InsufficientForLaunchFee.into());
+    }
+
+    solana_program::program::invoke(
+        &system_instruction::transfer(payer.key, treasury.key, fee),
+        &[payer.clone(), treasury.clone(), system_program.clone()],
+    )?;
+    msg!("Paid a launch fee of {} lamports to {}", fee, treasury.key);
+    Ok(())
+}
+
+// Creates the wallet's associated token account for the mint, funded by the payer
+// The ATA program derives and checks the token account address itself. With `idempotent` an
+// existing account is left as is, otherwise it makes the CPI fail.
+#[allow(clippy::too_many_arguments)]
+fn create_associated_token_account<'a>(
+    payer: &AccountInfo<'a>,
+    wallet: &AccountInfo<'a>,
+    token_account: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    ata_program: &AccountInfo<'a>,
+    idempotent: bool,
+) -> ProgramResult {
+    require!(
+        *system_program.key == system_program::id()
+            && *ata_program.key == ASSOCIATED_TOKEN_PROGRAM_ID,
+        ProgramError::IncorrectProgramId
+    );
+    require_executable(&[system_program, ata_program])?;
+    solana_program::program::invoke(
+        &Instruction {
+            program_id: ASSOCIATED_TOKEN_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(*payer.key, true),
+                AccountMeta::new(*token_account.key, false),
+                AccountMeta::new_readonly(*wallet.key, false),
+                AccountMeta::new_readonly(*mint_account.key, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(*token_program.key, false),
+            ],
+            // `CreateIdempotent` or `Create`
+            data: vec![u8::from(idempotent)],
+        },
+        &[
+            payer.clone(),
+            token_account.clone(),
+            wallet.clone(),
+            mint_account.clone(),
+            system_program.clone(),
+            token_program.clone(),
+            ata_program.clone(),
+        ],
+    )
+}
+
+// Resolves the decimals for a mint this program creates
+// Out-of-range values fail before the CPI with our own error, SPL itself accepts any u8
+fn create_mint_decimals(decimals: Option<u8>) -> Result<u8, ProgramError> {
+    if let Some(decimals) = decimals {
+        require!(decimals <= MAX_DECIMALS, CustomError::DecimalsTooLarge);
+    }
+    configured_decimals(decimals)
+}
+
+// Creates the mint account funded by the payer and initializes it with the given authorities
+#[allow(clippy::too_many_arguments)]
+fn create_and_initialize_mint<'a>(
+    mint_account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    mint_authority: &Pubkey,
+    freeze_authority: Option<&Pubkey>,
+    decimals: u8,
+    default_account_frozen: bool,
+    confidential_transfer: Option<&ConfidentialTransferConfig>,
+) -> ProgramResult {
+    let space = mint_len(default_account_frozen, confidential_transfer.is_some())?;
+    solana_program::program::invoke(
+        &system_instruction::create_account(
+            payer.key,
+            mint_account.key,
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            token_program.key,
+        ),
+        &[payer.clone(), mint_account.clone(), system_program.clone()],
+    )?;
+    // Extensions have to be initialized before the mint itself
+    if default_account_frozen {
+        solana_program::program::invoke(
+            &initialize_default_account_state(
+                token_program.key,
+                mint_account.key,
+                &AccountState::Frozen,
+            )?,
+            &[mint_account.clone(), token_program.clone()],
+        )?;
+    }
+    if let Some(config) = confidential_transfer {
+        solana_program::program::invoke(
+            &initialize_mint(
+                token_program.key,
+                mint_account.key,
+                config.authority,
+                config.auto_approve_new_accounts,
+                config.auditor_elgamal_pubkey.map(ElGamalPubkey),
+            )?,
+            &[mint_account.clone(), token_program.clone()],
+        )?;
+        // The extension must have landed in the space reserved for it
+        let mut data = mint_account.try_borrow_mut_data()?;
+        let mint_state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data)?;
+        require!(
+            mint_state.get_extension::<ConfidentialTransferMint>().is_ok(),
+            CustomError::ConfidentialConfigInvalid
+        );
+    }
+    solana_program::program::invoke(
+        &initialize_mint2(
+            token_program.key,
+            mint_account.key,
+            mint_authority,
+            freeze_authority,
+            decimals,
+        )?,
+        &[mint_account.clone(), token_program.clone()],
+    )
+}
+
+/// Processes the `InitializeFixedSupply` instruction
+/// This function performs the following operations:
+/// 1. Validates all input accounts and their states
+/// 2. Mints the total supply to the specified token account
+/// 3. Permanently revokes the mint authority to prevent future minting
+/// 4. Verifies the mint authority was successfully revoked
+pub fn process_initialize_fixed_supply(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: InitializeArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let gate = LaunchGate::parse(program_id, accounts_iter, 1)?;
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let token_account = next_account_info(accounts_iter)?;     // The destination token account
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+    let freeze_authority = match args.needs_freeze_authority() {
+        true => Some(next_account_info(accounts_iter)?),       // The freeze authority (must sign)
+        false => None,
+    };
+    let receipt = match &args.receipt {
+        Some(receipt) => Some((
+            receipt,
+            next_account_info(accounts_iter)?,                 // The launch receipt PDA
+            next_account_info(accounts_iter)?,                 // System program
+        )),
+        None => None,
+    };
+    let instructions_sysvar = match args.verify_transaction {
+        true => Some(next_account_info(accounts_iter)?),       // Instructions sysvar
+        false => None,
+    };
+    let co_signer = match &args.co_signer {
+        Some(expected) => Some((expected, next_account_info(accounts_iter)?)), // The co-signer
+        None => None,
+    };
+    let event = match &args.event {
+        Some(event) => Some((
+            event,
+            next_account_info(accounts_iter)?,                 // The event authority PDA
+            next_account_info(accounts_iter)?,                 // This program
+        )),
+        None => None,
+    };
+    let ata_programs = match args.has_capability(CAPABILITY_CREATE_ATA) {
+        true => Some((
+            next_account_info(accounts_iter)?,                 // System program
+            next_account_info(accounts_iter)?,                 // Associated Token Account program
+        )),
+        false => None,
+    };
+    let authorization = match &args.authorizer {
+        Some(authorizer) => Some((authorizer, next_account_info(accounts_iter)?)), // Instructions
+        None => None,
+    };
+    let destination_owner = match args.has_capability(CAPABILITY_PROTOCOL_DESTINATION) {
+        true => Some(next_account_info(accounts_iter)?),       // The destination's owner
+        false => None,
+    };
+    let program_data = match args.has_capability(CAPABILITY_IMMUTABLE_PROGRAM) {
+        true => Some(next_account_info(accounts_iter)?),       // This program's ProgramData
+        false => None,
+    };
+
+    // The launch's own program and sysvar accounts can't double as the mint or destination
+    let mut programs = Vec::new();
+    if let Some((_, _, system_program)) = receipt {
+        programs.push(("system program", system_program));
+    }
+    if let Some(instructions_sysvar) = instructions_sysvar {
+        programs.push(("instructions sysvar", instructions_sysvar));
+    }
+    if let Some((system_program, ata_program)) = ata_programs {
+        programs.push(("system program", system_program));
+        programs.push(("associated token program", ata_program));
+    }
+    if let Some((_, instructions_sysvar)) = authorization {
+        programs.push(("instructions sysvar", instructions_sysvar));
+    }
+    if let Some((_, system_program)) = gate.fee_accounts {
+        programs.push(("system program", system_program));
+    }
+    ensure_not_program_accounts(
+        &[("mint", mint_account), ("token account", token_account)],
+        &programs,
+    )?;
+
+    // Four-eyes control: a second, distinct key has to approve the launch
+    if let Some((expected, co_signer)) = co_signer {
+        require!(
+            co_signer.key == expected && co_signer.is_signer,
+            CustomError::MissingCoSigner
+        );
+        ensure_distinct(&[mint_authority, co_signer])?;
+    }
+
+    if let Some(instructions_sysvar) = instructions_sysvar {
+        verify_transaction_programs(program_id, instructions_sysvar)?;
+    }
+
+    if let Some((authorizer, instructions_sysvar)) = authorization {
+        verify_launch_authorization(instructions_sysvar, authorizer, mint_account.key)?;
+    }
+
+    gate.authorize(mint_authority.key, &[mint_account.key])?;
+
+    if let Some(program_data) = program_data {
+        verify_program_immutable(program_id, program_data)?;
+    }
+
+    if let Some((event, event_authority, this_program)) = event {
+        require!(this_program.key == program_id, ProgramError::IncorrectProgramId);
+        verify_pda(event_authority.key, &[EVENT_AUTHORITY_SEED], event.bump, program_id)?;
+    }
+
+    // Refuse to reuse a nonce before doing any work, so retries can't create duplicate receipts
+    if let Some((receipt, receipt_account, _)) = receipt {
+        verify_pda(
+            receipt_account.key,
+            &[RECEIPT_SEED, mint_account.key.as_ref(), &receipt.nonce.to_le_bytes()],
+            receipt.bump,
+            program_id,
+        )?;
+        if receipt_account.owner == program_id || !receipt_account.data_is_empty() {
+            return Err(CustomError::DuplicateNonce.into());
+        }
+    }
+
+    // Check up front that the payer can fund every account this launch creates
+    let mut new_account_lens = Vec::new();
+    if ata_programs.is_some() {
+        new_account_lens.push(associated_account_len(token_program.key)?);
+    }
+    if receipt.is_some() {
+        new_account_lens.push(LaunchReceipt::LEN);
+    }
+    require_rent_funds(payer, &new_account_lens)?;
+
+    // The fee comes on top of the rent, so the payer has to cover both before anything moves
+    gate.charge(payer, 1, &new_account_lens)?;
+
+    // The destination is created here and then validated like any other
+    if let Some((system_program, ata_program)) = ata_programs {
+        validate_token_program(token_program, mint_account)?;
+        create_associated_token_account(
+            payer,
+            payer,
+            token_account,
+            mint_account,
+            system_program,
+            token_program,
+            ata_program,
+            false,
+        )?;
+    }
+
+    let launch = LaunchAccounts {
+        mint_account,
+        token_account,
+        mint_authority,
+        payer,
+        token_program,
+    };
+    launch_fixed_supply(
+        &launch,
+        freeze_authority,
+        destination_owner,
+        &args,
+        accounts_iter.as_slice(),
+    )?;
+
+    if let Some((receipt, receipt_account, system_program)) = receipt {
+        let final_mint_data = unpack_mint(mint_account)?;
+        let mut record = LaunchReceipt {
+            account_type: AccountType::LaunchReceipt,
+            mint: *mint_account.key,
+            creator: *mint_authority.key,
+            supply: final_mint_data.supply,
+            decimals: final_mint_data.decimals,
+            nonce: receipt.nonce,
+            launched_at: Clock::get()?.unix_timestamp,
+            program_version: PROGRAM_VERSION,
+            checksum: [0; 32],
+        };
+        record.checksum = record.compute_checksum();
+        create_pda_account(
+            payer,
+            receipt_account,
+            system_program,
+            program_id,
+            LaunchReceipt::LEN,
+            &[
+                RECEIPT_SEED,
+                mint_account.key.as_ref(),
+                &receipt.nonce.to_le_bytes(),
+                &[receipt.bump],
+            ],
+        )?;
+        record.serialize(&mut &mut receipt_account.data.borrow_mut()[..])?;
+    }
+
+    if let Some((event, event_authority, this_program)) = event {
+        let final_mint_data = unpack_mint(mint_account)?;
+        let launch_event = LaunchEvent {
+            mint: *mint_account.key,
+            creator: *mint_authority.key,
+            supply: final_mint_data.supply,
+            decimals: final_mint_data.decimals,
+        };
+        emit_launch_event(this_program, event_authority, event.bump, launch_event)?;
+    }
+
+    Ok(())
+}
+
+// Self-invokes `EmitEvent` with the launch result, signed by the event authority PDA so that
+// indexers can tell it apart from an `EmitEvent` sent by anyone else
+fn emit_launch_event<'a>(
+    this_program: &AccountInfo<'a>,
+    event_authority: &AccountInfo<'a>,
+    bump: u8,
+    event: LaunchEvent,
+) -> ProgramResult {
+    let instruction = Instruction {
+        program_id: *this_program.key,
+        accounts: vec![AccountMeta::new_readonly(*event_authority.key, true)],
+        data: TokenInstruction::EmitEvent(EmitEventArgs { bump, event }).pack(),
+    };
+    solana_program::program::invoke_signed(
+        &instruction,
+        &[event_authority.clone(), this_program.clone()],
+        &[&[EVENT_AUTHORITY_SEED, &[bump]]],
+    )
+}
+
+/// Processes the `EmitEvent` instruction
+/// The event lives in the instruction data, so this only checks where it came from
+pub fn process_emit_event(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: EmitEventArgs,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let event_authority = next_account_info(accounts_iter)?;   // The event authority PDA (signs)
+
+    // Only this program can sign for the event authority, so events can't be spoofed
+    require!(event_authority.is_signer, ProgramError::MissingRequiredSignature);
+    verify_pda(event_authority.key, &[EVENT_AUTHORITY_SEED], args.bump, program_id)?;
+    msg!(
+        "Launch event: mint={} supply={} decimals={}",
+        args.event.mint,
+        args.event.supply,
+        args.event.decimals
+    );
+
+    Ok(())
+}
+
+/// Processes the `VerifyCreator` instruction
+/// Checks the creator recorded in a launch receipt without modifying any state
+pub fn process_verify_creator(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: VerifyCreatorArgs,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let receipt_account = next_account_info(accounts_iter)?;   // The launch receipt PDA
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+
+    // Only this program writes receipts, so a tagged receipt it owns is authentic
+    require!(receipt_account.owner == program_id, ProgramError::IllegalOwner);
+    let receipt = LaunchReceipt::try_from_slice(&receipt_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    require!(
+        receipt.account_type == AccountType::LaunchReceipt && receipt.mint == *mint_account.key,
+        ProgramError::InvalidAccountData
+    );
+
+    verbose!("checking creator: got {} expected {}", receipt.creator, args.creator);
+    require!(receipt.creator == args.creator, CustomError::CreatorMismatch);
+
+    Ok(())
+}
+
+/// Processes the `VerifyReceiptIntegrity` instruction
+/// Recomputes a launch receipt's checksum without modifying any state
+pub fn process_verify_receipt_integrity(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let receipt_account = next_account_info(accounts_iter)?;   // The launch receipt PDA
+
+    require!(receipt_account.owner == program_id, ProgramError::IllegalOwner);
+    let data = receipt_account.data.borrow();
+    let account_type = AccountType::deserialize(&mut &data[..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    require!(account_type == AccountType::LaunchReceipt, ProgramError::InvalidAccountData);
+
+    // A tagged receipt that no longer decodes was only partially written
+    let receipt = LaunchReceipt::try_from_slice(&data).map_err(|_| {
+        msg!("Receipt {} doesn't decode", receipt_account.key);
+        ProgramError::from(CustomError::ReceiptCorrupted)
+    })?;
+    let checksum = receipt.compute_checksum();
+    verbose!("checking receipt checksum: got {:?} expected {:?}", receipt.checksum, checksum);
+    if receipt.checksum != checksum {
+        msg!("Receipt {} doesn't match its checksum", receipt_account.key);
+        return Err(CustomError::ReceiptCorrupted.into());
+    }
+
+    Ok(())
+}
+
+/// Processes the `VerifyMetadata` instruction
+/// Compares a mint's Metaplex metadata against expected values without modifying any state
+pub fn process_verify_metadata(
+    accounts: &[AccountInfo],
+    args: VerifyMetadataArgs,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let metadata_account = next_account_info(accounts_iter)?;  // The metadata PDA
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+
+    if *metadata_account.key != Metadata::find_pda(mint_account.key).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    // The address is the right one, so anything Metaplex doesn't own there means no metadata
+    if *metadata_account.owner != mpl_token_metadata::ID || metadata_account.data_is_empty() {
+        msg!("No metadata account for mint {}", mint_account.key);
+        return Err(CustomError::MetadataNotFound.into());
+    }
+    let metadata = Metadata::from_bytes(&metadata_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    require!(metadata.mint == *mint_account.key, ProgramError::InvalidAccountData);
+
+    // Metaplex pads the strings with zero bytes up to their maximum length
+    for (field, actual, expected) in [
+        ("name", &metadata.name, &args.name),
+        ("symbol", &metadata.symbol, &args.symbol),
+        ("uri", &metadata.uri, &args.uri),
+    ] {
+        let actual = actual.trim_end_matches('\0');
+        verbose!("checking metadata {}: got {} expected {}", field, actual, expected);
+        if actual != expected {
+            msg!("Metadata {} is {:?}, expected {:?}", field, actual, expected);
+            return Err(CustomError::MetadataMismatch.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Processes the `VerifySupplyHash` instruction
+/// Checks the finalized mint against the caller's commitment to its supply and decimals
+pub fn process_verify_supply_hash(accounts: &[AccountInfo], args: SupplyHashArgs) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+
+    let mint_data = unpack_mint(mint_account)?;
+    require!(mint_data.mint_authority.is_none(), CustomError::MintAuthorityNotRevoked);
+
+    let actual = supply_hash(mint_account.key, mint_data.supply, mint_data.decimals);
+    if actual != args.supply_hash {
+        msg!(
+            "Supply hash of {} ({} base units, {} decimals) doesn't match the commitment",
+            mint_account.key,
+            mint_data.supply,
+            mint_data.decimals
+        );
+        return Err(CustomError::SupplyHashMismatch.into());
+    }
+
+    Ok(())
+}
+
+/// Processes the `BatchInitialize` instruction
+/// Launches every (mint, token account) pair in order, stopping at the first failure
+pub fn process_batch_initialize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: BatchInitializeArgs,
+) -> ProgramResult {
+    // Extract the shared accounts, followed by exactly one (mint, token account) pair per mint
+    let accounts_iter = &mut accounts.iter();
+    let gate = LaunchGate::parse(program_id, accounts_iter, args.mints.len())?;
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+    let pairs = accounts_iter.as_slice();
+    if pairs.len() != args.mints.len() * 2 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let mints: Vec<&Pubkey> = pairs.chunks_exact(2).map(|pair| pair[0].key).collect();
+    gate.authorize(mint_authority.key, &mints)?;
+    gate.charge(payer, args.mints.len() as u64, &[])?;
+
+    for (index, (pair, mint_args)) in pairs.chunks_exact(2).zip(&args.mints).enumerate() {
+        let launch = LaunchAccounts {
+            mint_account: &pair[0],
+            token_account: &pair[1],
+            mint_authority,
+            payer,
+            token_program,
+        };
+        if let Err(e) = launch_fixed_supply(&launch, None, None, mint_args, &[]) {
+            msg!("Batch launch failed at mint index {}", index);
+            return Err(e);
+        }
+    }
+
+    Ok(())
+}
+
+// Accounts taking part in a single-destination launch
+struct LaunchAccounts<'a, 'b> {
+    mint_account: &'b AccountInfo<'a>,
+    token_account: &'b AccountInfo<'a>,
+    mint_authority: &'b AccountInfo<'a>,
+    payer: &'b AccountInfo<'a>,
+    token_program: &'b AccountInfo<'a>,
+}
+
+// Validates, mints the total supply and revokes the mint authority for a single mint
+// `freeze_authority` must be provided when `args.needs_freeze_authority()`, a
+// `destination_owner` replaces the payer as the required destination owner, and any
+// `candidates` are token accounts for the mint that must hold no pre-existing tokens
+fn launch_fixed_supply<'a>(
+    launch: &LaunchAccounts<'a, '_>,
+    freeze_authority: Option<&AccountInfo<'a>>,
+    destination_owner: Option<&AccountInfo<'a>>,
+    args: &InitializeArgs,
+    candidates: &[AccountInfo<'a>],
+) -> ProgramResult {
+    let LaunchAccounts {
+        mint_account,
+        token_account,
+        mint_authority,
+        payer,
+        token_program,
+    } = *launch;
+
+    ensure_distinct(&[mint_account, token_account, mint_authority])?;
+    ensure_not_program_accounts(
+        &[("mint", mint_account), ("token account", token_account)],
+        &[("token program", token_program)],
+    )?;
+
+    let decimals = configured_decimals(args.decimals)?;
+    let total_supply_with_decimals = total_supply_with_decimals(decimals)?;
+
+    // The thaw and freeze flows keep the mint's freeze authority, which must sign for them
+    let freeze_authority = match (args.needs_freeze_authority(), freeze_authority) {
+        (true, Some(freeze_authority)) => {
+            if !freeze_authority.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            Some(freeze_authority)
+        }
+        (true, None) => return Err(ProgramError::NotEnoughAccountKeys),
+        (false, _) => None,
+    };
+
+    validate_authority(mint_authority, payer)?;
+    validate_token_program(token_program, mint_account)?;
+    if !mint_account.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let mint_data = validate_mint(
+        mint_account,
+        mint_authority,
+        Some(decimals),
+        freeze_authority.map(|account| account.key),
+    )?;
+    let expected_owner = match destination_owner {
+        Some(destination_owner) => {
+            validate_protocol_owner(destination_owner)?;
+            destination_owner.key
+        }
+        None => payer.key,
+    };
+    let token_data = validate_destination(token_account, mint_account, Some(expected_owner))?;
+
+    // Optional audit of an externally-created mint: any remaining accounts are candidate
+    // token accounts for this mint, and their combined balance must equal the mint supply (zero)
+    let mut candidate_total: u64 = 0;
+    for candidate in candidates {
+        if candidate.owner != mint_account.owner {
+            return Err(ProgramError::IllegalOwner);
+        }
+        let candidate_data = unpack_token_account(candidate)?;
+        if candidate_data.mint != *mint_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        candidate_total = candidate_total
+            .checked_add(candidate_data.amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+    if candidate_total != mint_data.supply {
+        return Err(CustomError::PreExistingTokensFound.into());
+    }
+
+    // Regulated launches deliver into a frozen account, which has to be thawed first
+    if let Some(freeze_authority) = freeze_authority {
+        if args.thaw.is_some() && token_data.is_frozen() {
+            solana_program::program::invoke(
+                &thaw_account(
+                    token_program.key,
+                    token_account.key,
+                    mint_account.key,
+                    freeze_authority.key,
+                    &[],
+                )?,
+                &[
+                    token_account.clone(),
+                    mint_account.clone(),
+                    freeze_authority.clone(),
+                    token_program.clone(),
+                ],
+            )?;
+        }
+    }
+
+    // Step 1: Mint the total supply to the token account
+    // This creates all tokens that will ever exist for this mint
+    mint_supply(
+        token_program,
+        mint_account,
+        token_account,
+        mint_authority,
+        total_supply_with_decimals,
+        decimals,
+    )?;
+
+    // Optionally freeze the destination (again) until the freeze authority releases it
+    let freeze = args.freeze_destination || args.thaw.as_ref().is_some_and(|thaw| thaw.refreeze);
+    if let (true, Some(freeze_authority)) = (freeze, freeze_authority) {
+        solana_program::program::invoke(
+            &freeze_account(
+                token_program.key,
+                token_account.key,
+                mint_account.key,
+                freeze_authority.key,
+                &[],
+            )?,
+            &[
+                token_account.clone(),
+                mint_account.clone(),
+                freeze_authority.clone(),
+                token_program.clone(),
+            ],
+        )?;
+        require!(unpack_token_account(token_account)?.is_frozen(), CustomError::FreezeFailed);
+    }
+
+    // Optionally give up the freeze authority as well, so no holder can ever be frozen
+    if let (true, Some(freeze_authority)) =
+        (args.has_capability(CAPABILITY_REVOKE_FREEZE), freeze_authority)
+    {
+        revoke_freeze_authority(token_program, mint_account, freeze_authority)?;
+    }
+
+    // Step 2: Permanently revoke the mint authority and verify the revocation, unless the
+    // caller opted out of the verification
+    if args.has_capability(CAPABILITY_SKIP_FINAL_VERIFICATION) {
+        revoke_mint_authority_unverified(
+            token_program,
+            mint_account,
+            mint_authority,
+            total_supply_with_decimals,
+            decimals,
+        )?;
+    } else {
+        revoke_mint_authority(
+            token_program,
+            mint_account,
+            mint_authority,
+            total_supply_with_decimals,
+        )?;
+    }
+
+    // A preserved freeze authority must come out of all the CPIs above exactly as it went in
+    if let (false, Some(freeze_authority)) =
+        (args.has_capability(CAPABILITY_REVOKE_FREEZE), freeze_authority)
+    {
+        let final_freeze_authority = unpack_mint(mint_account)?.freeze_authority;
+        verbose!(
+            "checking preserved freeze authority: got {:?} expected {}",
+            final_freeze_authority,
+            freeze_authority.key
+        );
+        require!(
+            final_freeze_authority == COption::Some(*freeze_authority.key),
+            CustomError::FreezeAuthorityTampered
+        );
+    }
+
+    Ok(())
+}
+
+/// Processes the `InitializeWithPdaAuthority` instruction
+/// Launches a mint whose authority is this program's PDA, signing the CPIs with its seeds
+pub fn process_initialize_with_pda_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: PdaAuthorityArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let gate = LaunchGate::parse(program_id, accounts_iter, 1)?;
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let token_account = next_account_info(accounts_iter)?;     // The destination token account
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority PDA
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+
+    require!(payer.is_signer, ProgramError::MissingRequiredSignature);
+    require!(!is_reserved_authority_key(payer.key), CustomError::InvalidAuthorityAccount);
+    gate.authorize(payer.key, &[mint_account.key])?;
+    gate.charge(payer, 1, &[])?;
+    verify_pda(
+        mint_authority.key,
+        &[MINT_AUTHORITY_SEED, mint_account.key.as_ref()],
+        args.bump,
+        program_id,
+    )?;
+    ensure_distinct(&[mint_account, token_account, mint_authority, payer])?;
+
+    let decimals = configured_decimals(args.decimals)?;
+    let total_supply_with_decimals = total_supply_with_decimals(decimals)?;
+
+    validate_token_program(token_program, mint_account)?;
+    require!(mint_account.is_writable, ProgramError::InvalidAccountData);
+    validate_mint(mint_account, mint_authority, Some(decimals), None)?;
+    validate_destination(token_account, mint_account, Some(payer.key))?;
+
+    let signer_seeds: &[&[u8]] = &[MINT_AUTHORITY_SEED, mint_account.key.as_ref(), &[args.bump]];
+    mint_supply_signed(
+        token_program,
+        mint_account,
+        token_account,
+        mint_authority,
+        total_supply_with_decimals,
+        decimals,
+        &[signer_seeds],
+    )?;
+    revoke_mint_authority_signed(
+        token_program,
+        mint_account,
+        mint_authority,
+        total_supply_with_decimals,
+        &[signer_seeds],
+    )
+}
+
+/// Processes the `InitializeWithUiSupply` instruction
+/// Launches a supply given in whole tokens, computing the base units on-chain
+pub fn process_initialize_with_ui_supply(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: UiSupplyArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let gate = LaunchGate::parse(program_id, accounts_iter, 1)?;
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let token_account = next_account_info(accounts_iter)?;     // The destination token account
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+
+    ensure_distinct(&[mint_account, token_account, mint_authority])?;
+    ensure_not_program_accounts(
+        &[("mint", mint_account), ("token account", token_account)],
+        &[("token program", token_program)],
+    )?;
+    validate_authority(mint_authority, payer)?;
+    gate.authorize(mint_authority.key, &[mint_account.key])?;
+    gate.charge(payer, 1, &[])?;
+
+    let decimals = configured_decimals(Some(args.decimals))?;
+    verbose!("checking supply: got {} expected at least {}", args.ui_supply, MIN_SUPPLY);
+    require!(args.ui_supply >= MIN_SUPPLY, CustomError::SupplyBelowMinimum);
+    // A tiny supply split into many decimals is almost always a configuration mistake
+    let supply_digits = args.ui_supply.checked_ilog10().map_or(1, |log| log as u8 + 1);
+    verbose!("checking decimals: got {} for {} supply digits", decimals, supply_digits);
+    if decimals > supply_digits.saturating_add(MAX_DECIMALS_OVER_SUPPLY_DIGITS)
+        && !args.allow_impractical_decimals
+    {
+        msg!("{} decimals are impractical for a supply of {}", decimals, args.ui_supply);
+        return Err(CustomError::ImpracticalDecimalsSupplyCombo.into());
+    }
+    let supply = supply_with_decimals(args.ui_supply, decimals).ok_or(CustomError::SupplyOverflow)?;
+
+    validate_token_program(token_program, mint_account)?;
+    require!(mint_account.is_writable, ProgramError::InvalidAccountData);
+    validate_mint(mint_account, mint_authority, Some(decimals), None)?;
+    validate_destination(token_account, mint_account, Some(payer.key))?;
+
+    mint_supply(token_program, mint_account, token_account, mint_authority, supply, decimals)?;
+    revoke_mint_authority(token_program, mint_account, mint_authority, supply)
+}
+
+/// Processes the `InitializeIntoMultisig` instruction
+/// Launches the total supply into a multisig-owned treasury, leaving it under multisig control
+pub fn process_initialize_into_multisig(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: MultisigTreasuryArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let gate = LaunchGate::parse(program_id, accounts_iter, 1)?;
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let token_account = next_account_info(accounts_iter)?;     // The treasury token account
+    let multisig_account = next_account_info(accounts_iter)?;  // The treasury's SPL multisig
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+
+    ensure_distinct(&[mint_account, token_account, multisig_account, mint_authority])?;
+    validate_authority(mint_authority, payer)?;
+    gate.authorize(mint_authority.key, &[mint_account.key])?;
+    gate.charge(payer, 1, &[])?;
+
+    let decimals = configured_decimals(args.decimals)?;
+    let supply = total_supply_with_decimals(decimals)?;
+
+    validate_token_program(token_program, mint_account)?;
+    require!(mint_account.is_writable, ProgramError::InvalidAccountData);
+    let multisig = validate_multisig(multisig_account, token_program)?;
+    validate_multisig_treasury(&multisig)?;
+    validate_mint(mint_account, mint_authority, Some(decimals), None)?;
+    let treasury = validate_destination(token_account, mint_account, None)?;
+    verbose!("checking treasury owner: got {} expected {}", treasury.owner, multisig_account.key);
+    if treasury.owner != *multisig_account.key {
+        msg!("Treasury {} is owned by {}, not the multisig", token_account.key, treasury.owner);
+        return Err(CustomError::InvalidMultisigTreasury.into());
+    }
+
+    mint_supply(token_program, mint_account, token_account, mint_authority, supply, decimals)?;
+    revoke_mint_authority(token_program, mint_account, mint_authority, supply)?;
+    msg!(
+        "Supply of {} is held by a {} of {} multisig",
+        mint_account.key,
+        multisig.m,
+        multisig.n
+    );
+
+    Ok(())
+}
+
+/// Processes the `InitializeFromSeed` instruction
+/// Derives the supply and decimals from the seed, then launches them like `InitializeWithUiSupply`
+pub fn process_initialize_from_seed(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SeedLaunchArgs,
+) -> ProgramResult {
+    let (ui_supply, decimals) =
+        derive_launch_params(&args.seed).ok_or(CustomError::SeedDerivationFailed)?;
+    msg!("Seed derived a supply of {} tokens with {} decimals", ui_supply, decimals);
+
+    let args = UiSupplyArgs {
+        ui_supply,
+        decimals,
+        allow_impractical_decimals: false,
+    };
+    process_initialize_with_ui_supply(program_id, accounts, args)
+}
+
+/// Processes the `InitializeWithLock` instruction
+/// Splits the supply into a circulating tranche and a tranche locked in a PDA-owned vault
+pub fn process_initialize_with_lock(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: LockedLaunchArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let gate = LaunchGate::parse(program_id, accounts_iter, 1)?;
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let treasury_account = next_account_info(accounts_iter)?;  // Receives the circulating supply
+    let lock_account = next_account_info(accounts_iter)?;      // The supply lock PDA
+    let vault_account = next_account_info(accounts_iter)?;     // Receives the locked supply
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+    let system_program = next_account_info(accounts_iter)?;    // System program
+
+    ensure_distinct(&[
+        mint_account,
+        treasury_account,
+        lock_account,
+        vault_account,
+        mint_authority,
+    ])?;
+    validate_authority(mint_authority, payer)?;
+    gate.authorize(mint_authority.key, &[mint_account.key])?;
+    gate.charge(payer, 1, &[])?;
+    validate_token_program(token_program, mint_account)?;
+    require!(mint_account.is_writable, ProgramError::InvalidAccountData);
+
+    let decimals = configured_decimals(args.decimals)?;
+    let total_supply_with_decimals = total_supply_with_decimals(decimals)?;
+    require!(
+        args.lock_amount < total_supply_with_decimals,
+        CustomError::CannotLockEntireSupply
+    );
+    let circulating_amount = total_supply_with_decimals - args.lock_amount;
+    require!(
+        args.unlock_timestamp > Clock::get()?.unix_timestamp,
+        ProgramError::InvalidArgument
+    );
+
+    verify_pda(
+        lock_account.key,
+        &[SUPPLY_LOCK_SEED, mint_account.key.as_ref()],
+        args.bump,
+        program_id,
+    )?;
+    if lock_account.owner == program_id || !lock_account.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    validate_mint(mint_account, mint_authority, Some(decimals), None)?;
+    validate_destination(treasury_account, mint_account, Some(payer.key))?;
+    // Only the lock PDA can move tokens out of the vault
+    validate_destination(vault_account, mint_account, Some(lock_account.key))?;
+
+    create_pda_account(
+        payer,
+        lock_account,
+        system_program,
+        program_id,
+        SupplyLock::LEN,
+        &[SUPPLY_LOCK_SEED, mint_account.key.as_ref(), &[args.bump]],
+    )?;
+
+    mint_supply(
+        token_program,
+        mint_account,
+        treasury_account,
+        mint_authority,
+        circulating_amount,
+        decimals,
+    )?;
+    mint_supply(
+        token_program,
+        mint_account,
+        vault_account,
+        mint_authority,
+        args.lock_amount,
+        decimals,
+    )?;
+    revoke_mint_authority(token_program, mint_account, mint_authority, total_supply_with_decimals)?;
+
+    let lock = SupplyLock {
+        account_type: AccountType::SupplyLock,
+        mint: *mint_account.key,
+        vault: *vault_account.key,
+        beneficiary: *payer.key,
+        circulating_amount,
+        locked_amount: args.lock_amount,
+        unlock_timestamp: args.unlock_timestamp,
+        vesting: args.vesting,
+        released_amount: 0,
+        bump: args.bump,
+    };
+    lock.serialize(&mut &mut lock_account.data.borrow_mut()[..])?;
+    msg!(
+        "Locked {} of {} base units until {}",
+        args.lock_amount,
+        total_supply_with_decimals,
+        args.unlock_timestamp
+    );
+
+    Ok(())
+}
+
+/// Processes the `InitializeWithGovernance` instruction
+/// Mints the total supply, then hands the mint authority to a governance program's PDA and
+/// records the handoff
+pub fn process_initialize_with_governance(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: GovernanceLaunchArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let gate = LaunchGate::parse(program_id, accounts_iter, 1)?;
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let token_account = next_account_info(accounts_iter)?;     // The destination token account
+    let governance = next_account_info(accounts_iter)?;        // The governance authority PDA
+    let record_account = next_account_info(accounts_iter)?;    // The governance record PDA
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+    let system_program = next_account_info(accounts_iter)?;    // System program
+
+    ensure_distinct(&[mint_account, token_account, governance, record_account, mint_authority])?;
+    validate_authority(mint_authority, payer)?;
+    gate.authorize(mint_authority.key, &[mint_account.key])?;
+    gate.charge(payer, 1, &[])?;
+    validate_token_program(token_program, mint_account)?;
+    require!(mint_account.is_writable, ProgramError::InvalidAccountData);
+
+    let decimals = configured_decimals(args.decimals)?;
+    let total_supply_with_decimals = total_supply_with_decimals(decimals)?;
+
+    // Unlimited minting may only go to the governance program this build trusts
+    verbose!(
+        "checking governance program: got {} expected {}",
+        args.governance_program,
+        GOVERNANCE_PROGRAM_ID
+    );
+    require!(args.governance_program == GOVERNANCE_PROGRAM_ID, ProgramError::IncorrectProgramId);
+
+    // The new authority must be the governance program's PDA for this very mint
+    verify_pda(
+        governance.key,
+        &[GOVERNANCE_SEED, mint_account.key.as_ref()],
+        args.governance_bump,
+        &args.governance_program,
+    )?;
+    verify_pda(
+        record_account.key,
+        &[GOVERNANCE_RECORD_SEED, mint_account.key.as_ref()],
+        args.record_bump,
+        program_id,
+    )?;
+    if record_account.owner == program_id || !record_account.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    validate_mint(mint_account, mint_authority, Some(decimals), None)?;
+    validate_destination(token_account, mint_account, Some(payer.key))?;
+
+    mint_supply(
+        token_program,
+        mint_account,
+        token_account,
+        mint_authority,
+        total_supply_with_decimals,
+        decimals,
+    )?;
+    solana_program::program::invoke(
+        &set_authority(
+            token_program.key,
+            mint_account.key,
+            Some(governance.key),
+            AuthorityType::MintTokens,
+            mint_authority.key,
+            &[],
+        )?,
+        &[mint_account.clone(), mint_authority.clone(), token_program.clone()],
+    )?;
+
+    // Only the governance PDA may be able to mint from here on
+    let final_mint_data = unpack_mint(mint_account)?;
+    verbose!(
+        "checking new mint authority: got {:?} expected {}",
+        final_mint_data.mint_authority,
+        governance.key
+    );
+    require!(
+        final_mint_data.mint_authority == COption::Some(*governance.key),
+        CustomError::GovernanceTransferFailed
+    );
+
+    create_pda_account(
+        payer,
+        record_account,
+        system_program,
+        program_id,
+        GovernanceRecord::LEN,
+        &[GOVERNANCE_RECORD_SEED, mint_account.key.as_ref(), &[args.record_bump]],
+    )?;
+    let record = GovernanceRecord {
+        account_type: AccountType::GovernanceRecord,
+        mint: *mint_account.key,
+        governance_program: args.governance_program,
+        governance_authority: *governance.key,
+        supply: final_mint_data.supply,
+        recorded_at: Clock::get()?.unix_timestamp,
+    };
+    record.serialize(&mut &mut record_account.data.borrow_mut()[..])?;
+    msg!(
+        "Mint authority handed to {} of governance program {}",
+        governance.key,
+        args.governance_program
+    );
+
+    Ok(())
+}
+
+/// Processes the `InitializeWithTimelockFreeze` instruction
+/// Mints the total supply, hands the freeze authority to the timelock program's PDA, then revokes
+/// the mint authority
+pub fn process_initialize_with_timelock_freeze(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: TimelockFreezeArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let gate = LaunchGate::parse(program_id, accounts_iter, 1)?;
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let token_account = next_account_info(accounts_iter)?;     // The destination token account
+    let timelock = next_account_info(accounts_iter)?;          // The timelock authority PDA
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+
+    ensure_distinct(&[mint_account, token_account, timelock, mint_authority])?;
+    validate_authority(mint_authority, payer)?;
+    gate.authorize(mint_authority.key, &[mint_account.key])?;
+    gate.charge(payer, 1, &[])?;
+    validate_token_program(token_program, mint_account)?;
+    require!(mint_account.is_writable, ProgramError::InvalidAccountData);
+
+    let decimals = configured_decimals(args.decimals)?;
+    let total_supply_with_decimals = total_supply_with_decimals(decimals)?;
+
+    // Freezes must go through the configured timelock program's PDA for this very mint
+    if !gate.settings.has_timelock_program() {
+        msg!("No timelock program is configured");
+        return Err(ProgramError::InvalidArgument);
+    }
+    verify_pda(
+        timelock.key,
+        &[TIMELOCK_SEED, mint_account.key.as_ref()],
+        args.timelock_bump,
+        &gate.settings.timelock_program,
+    )?;
+
+    // The signer must still hold the freeze authority to be able to hand it over
+    validate_mint(mint_account, mint_authority, Some(decimals), Some(mint_authority.key))?;
+    validate_destination(token_account, mint_account, Some(payer.key))?;
+
+    mint_supply(
+        token_program,
+        mint_account,
+        token_account,
+        mint_authority,
+        total_supply_with_decimals,
+        decimals,
+    )?;
+    solana_program::program::invoke(
+        &set_authority(
+            token_program.key,
+            mint_account.key,
+            Some(timelock.key),
+            AuthorityType::FreezeAccount,
+            mint_authority.key,
+            &[],
+        )?,
+        &[mint_account.clone(), mint_authority.clone(), token_program.clone()],
+    )?;
+    revoke_mint_authority(token_program, mint_account, mint_authority, total_supply_with_decimals)?;
+
+    // Only the timelock PDA may be able to freeze from here on
+    let final_mint_data = unpack_mint(mint_account)?;
+    verbose!(
+        "checking new freeze authority: got {:?} expected {}",
+        final_mint_data.freeze_authority,
+        timelock.key
+    );
+    require!(
+        final_mint_data.freeze_authority == COption::Some(*timelock.key),
+        CustomError::TimelockTransferFailed
+    );
+    msg!("Freeze authority handed to timelock {}", timelock.key);
+
+    Ok(())
+}
+
+/// Processes the `ReleaseLocked` instruction
+/// Moves the vested, unreleased part of the locked tranche from the vault to the beneficiary
+pub fn process_release_locked(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let lock_account = next_account_info(accounts_iter)?;      // The supply lock PDA
+    let vault_account = next_account_info(accounts_iter)?;     // The vault token account
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let destination = next_account_info(accounts_iter)?;       // Receives the released tokens
+    let beneficiary = next_account_info(accounts_iter)?;       // The beneficiary (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+
+    require!(beneficiary.is_signer, ProgramError::MissingRequiredSignature);
+    require!(lock_account.owner == program_id, ProgramError::IllegalOwner);
+    require!(
+        lock_account.is_writable && vault_account.is_writable && destination.is_writable,
+        ProgramError::InvalidAccountData
+    );
+    ensure_distinct(&[lock_account, vault_account, mint_account, destination])?;
+    validate_token_program(token_program, mint_account)?;
+
+    let mut lock = SupplyLock::try_from_slice(&lock_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    require!(
+        lock.account_type == AccountType::SupplyLock
+            && lock.mint == *mint_account.key
+            && lock.vault == *vault_account.key
+            && lock.unreleased_amount() > 0,
+        ProgramError::InvalidAccountData
+    );
+    require!(lock.beneficiary == *beneficiary.key, CustomError::InvalidAuthorityAccount);
+    let amount = lock.releasable_amount(Clock::get()?.unix_timestamp);
+    verbose!("releasable amount: {} of {}", amount, lock.unreleased_amount());
+    require!(amount > 0, CustomError::SupplyStillLocked);
+
+    let destination_data = unpack_token_account(destination)?;
+    require!(destination.owner == mint_account.owner, ProgramError::IllegalOwner);
+    require!(destination_data.mint == *mint_account.key, CustomError::TokenAccountWrongMint);
+    let decimals = unpack_mint(mint_account)?.decimals;
+
+    let signer_seeds: &[&[u8]] = &[SUPPLY_LOCK_SEED, mint_account.key.as_ref(), &[lock.bump]];
+    solana_program::program::invoke_signed(
+        &transfer_checked(
+            token_program.key,
+            vault_account.key,
+            mint_account.key,
+            destination.key,
+            lock_account.key,
+            &[],
+            amount,
+            decimals,
+        )?,
+        &[
+            vault_account.clone(),
+            mint_account.clone(),
+            destination.clone(),
+            lock_account.clone(),
+            token_program.clone(),
+        ],
+        &[signer_seeds],
+    )?;
+
+    lock.released_amount += amount;
+    lock.serialize(&mut &mut lock_account.data.borrow_mut()[..])?;
+    msg!("Released {} locked base units to {}", amount, destination.key);
+
+    Ok(())
+}
+
+/// Processes the `GetLaunchStats` instruction
+/// Returns the Borsh-encoded launch stats via return data without modifying any state
+pub fn process_get_launch_stats(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let stats_account = next_account_info(accounts_iter)?;     // The launch stats PDA
+
+    let stats = read_launch_stats(program_id, stats_account)?;
+    set_return_data(&borsh::to_vec(&stats)?);
+
+    Ok(())
+}
+
+/// Processes the `RecordSupplyCap` instruction
+/// Writes the supply of a finalized mint into its write-once supply cap PDA
+pub fn process_record_supply_cap(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SupplyCapArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let cap_account = next_account_info(accounts_iter)?;       // The supply cap PDA
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let system_program = next_account_info(accounts_iter)?;    // System program
+
+    require!(payer.is_signer, ProgramError::MissingRequiredSignature);
+    verify_pda(
+        cap_account.key,
+        &[SUPPLY_CAP_SEED, mint_account.key.as_ref()],
+        args.bump,
+        program_id,
+    )?;
+    // Write-once: the cap can't be rewritten, even with the same value
+    if cap_account.owner == program_id || !cap_account.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    // The supply is only a trustworthy cap once nobody can mint anymore
+    let mint_data = unpack_mint(mint_account)?;
+    require!(mint_data.is_initialized, CustomError::InvalidMintState);
+    require!(mint_data.mint_authority.is_none(), CustomError::MintAuthorityNotRevoked);
+
+    create_pda_account(
+        payer,
+        cap_account,
+        system_program,
+        program_id,
+        SupplyCap::LEN,
+        &[SUPPLY_CAP_SEED, mint_account.key.as_ref(), &[args.bump]],
+    )?;
+    let cap = SupplyCap {
+        account_type: AccountType::SupplyCap,
+        mint: *mint_account.key,
+        supply: mint_data.supply,
+        decimals: mint_data.decimals,
+        recorded_at: Clock::get()?.unix_timestamp,
+    };
+    cap.serialize(&mut &mut cap_account.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+/// Processes the `GetSupplyCap` instruction
+/// Returns the mint's recorded supply cap through return data
+pub fn process_get_supply_cap(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let cap_account = next_account_info(accounts_iter)?;       // The supply cap PDA
+
+    require!(cap_account.owner == program_id, ProgramError::IllegalOwner);
+    let cap = SupplyCap::try_from_slice(&cap_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    require!(cap.account_type == AccountType::SupplyCap, ProgramError::InvalidAccountData);
+    set_return_data(&borsh::to_vec(&cap)?);
+
+    Ok(())
+}
+
+/// Processes the `Rescue` instruction
+/// Moves tokens stuck in an account controlled by a supply lock PDA out on the admin's behalf
+pub fn process_rescue(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: RescueArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let lock_account = next_account_info(accounts_iter)?;      // The supply lock PDA
+    let source = next_account_info(accounts_iter)?;            // The stuck token account
+    let mint_account = next_account_info(accounts_iter)?;      // The source account's mint
+    let destination = next_account_info(accounts_iter)?;       // Receives the rescued tokens
+    let admin = next_account_info(accounts_iter)?;             // The config admin (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+    let config_account = next_account_info(accounts_iter)?;    // The config PDA
+
+    verify_config_admin(program_id, config_account, admin)?;
+    require!(
+        source.is_writable && destination.is_writable,
+        ProgramError::InvalidAccountData
+    );
+    ensure_distinct(&[lock_account, source, mint_account, destination])?;
+    validate_token_program(token_program, mint_account)?;
+
+    // Only a tagged lock this program owns can sign, and the source must be one of its accounts
+    require!(lock_account.owner == program_id, ProgramError::IllegalOwner);
+    let lock = SupplyLock::try_from_slice(&lock_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    require!(lock.account_type == AccountType::SupplyLock, ProgramError::InvalidAccountData);
+    let source_data = unpack_token_account(source)?;
+    require!(source.owner == mint_account.owner, ProgramError::IllegalOwner);
+    require!(source_data.mint == *mint_account.key, CustomError::TokenAccountWrongMint);
+    require!(source_data.owner == *lock_account.key, CustomError::TokenAccountOwnerMismatch);
+
+    // Tokens still waiting in the vault for the beneficiary are never rescuable
+    let protected = match *source.key == lock.vault {
+        true => lock.unreleased_amount(),
+        false => 0,
+    };
+    let surplus = source_data.amount.saturating_sub(protected);
+    verbose!("checking rescue amount: got {} surplus {}", args.amount, surplus);
+    require!(args.amount <= surplus, CustomError::RescueExceedsSurplus);
+
+    let destination_data = unpack_token_account(destination)?;
+    require!(destination.owner == mint_account.owner, ProgramError::IllegalOwner);
+    require!(destination_data.mint == *mint_account.key, CustomError::TokenAccountWrongMint);
+    let decimals = unpack_mint(mint_account)?.decimals;
+
+    let signer_seeds: &[&[u8]] = &[SUPPLY_LOCK_SEED, lock.mint.as_ref(), &[lock.bump]];
+    solana_program::program::invoke_signed(
+        &transfer_checked(
+            token_program.key,
+            source.key,
+            mint_account.key,
+            destination.key,
+            lock_account.key,
+            &[],
+            args.amount,
+            decimals,
+        )?,
+        &[
+            source.clone(),
+            mint_account.clone(),
+            destination.clone(),
+            lock_account.clone(),
+            token_program.clone(),
+        ],
+        &[signer_seeds],
+    )?;
+    msg!("Rescued {} base units from {} to {}", args.amount, source.key, destination.key);
+
+    Ok(())
+}
+
+/// Processes the `StartChunkedMint` instruction
+/// Creates the tracking account for a launch minted in several chunks
+pub fn process_start_chunked_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: StartChunkedMintArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let chunked_account = next_account_info(accounts_iter)?;   // The chunked mint PDA
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let system_program = next_account_info(accounts_iter)?;    // System program
+
+    validate_authority(mint_authority, payer)?;
+    let mint_data = validate_mint(mint_account, mint_authority, None, None)?;
+
+    verify_pda(
+        chunked_account.key,
+        &[CHUNKED_MINT_SEED, mint_account.key.as_ref()],
+        args.bump,
+        program_id,
+    )?;
+    if chunked_account.owner == program_id || !chunked_account.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    create_pda_account(
+        payer,
+        chunked_account,
+        system_program,
+        program_id,
+        ChunkedMint::LEN,
+        &[CHUNKED_MINT_SEED, mint_account.key.as_ref(), &[args.bump]],
+    )?;
+
+    let chunked = ChunkedMint {
+        account_type: AccountType::ChunkedMint,
+        mint: *mint_account.key,
+        authority: *mint_authority.key,
+        target_total: total_supply_with_decimals(mint_data.decimals)?,
+        cumulative_minted: 0,
+    };
+    chunked.serialize(&mut &mut chunked_account.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+/// Processes the `TestMint` instruction
+/// Mints the total supply without revoking the mint authority and flags the mint as a test mint
+#[cfg(feature = "test-mode")]
+pub fn process_test_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: TestMintArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let token_account = next_account_info(accounts_iter)?;     // The destination token account
+    let test_account = next_account_info(accounts_iter)?;      // The test mint record PDA
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+    let system_program = next_account_info(accounts_iter)?;    // System program
+
+    ensure_distinct(&[mint_account, token_account, test_account, mint_authority])?;
+    validate_authority(mint_authority, payer)?;
+    validate_token_program(token_program, mint_account)?;
+    require!(mint_account.is_writable, ProgramError::InvalidAccountData);
+    let mint_data = validate_mint(mint_account, mint_authority, None, None)?;
+    // The authority has to hold the test supply itself to be able to burn it on reset
+    validate_destination(token_account, mint_account, Some(mint_authority.key))?;
+
+    verify_pda(
+        test_account.key,
+        &[TEST_MINT_SEED, mint_account.key.as_ref()],
+        args.bump,
+        program_id,
+    )?;
+    if test_account.owner == program_id || !test_account.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    require_rent_funds(payer, &[TestMint::LEN])?;
+    create_pda_account(
+        payer,
+        test_account,
+        system_program,
+        program_id,
+        TestMint::LEN,
+        &[TEST_MINT_SEED, mint_account.key.as_ref(), &[args.bump]],
+    )?;
+
+    let supply = total_supply_with_decimals(mint_data.decimals)?;
+    mint_supply(
+        token_program,
+        mint_account,
+        token_account,
+        mint_authority,
+        supply,
+        mint_data.decimals,
+    )?;
+
+    let record = TestMint {
+        account_type: AccountType::TestMint,
+        mint: *mint_account.key,
+        authority: *mint_authority.key,
+        supply,
+        minted_at: Clock::get()?.unix_timestamp,
+    };
+    record.serialize(&mut &mut test_account.data.borrow_mut()[..])?;
+    msg!(
+        "Test mode: minted {} base units of {}, mint authority {} kept for a reset",
+        supply,
+        mint_account.key,
+        mint_authority.key
+    );
+
+    Ok(())
+}
+
+/// Processes the `ResetTestMint` instruction
+/// Burns the whole supply of a test mint and closes its record so the mint can launch again
+#[cfg(feature = "test-mode")]
+pub fn process_reset_test_mint(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let token_account = next_account_info(accounts_iter)?;     // The token account to burn from
+    let test_account = next_account_info(accounts_iter)?;      // The test mint record PDA
+    let authority = next_account_info(accounts_iter)?;         // The mint authority (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+    let receiver = next_account_info(accounts_iter)?;          // Receives the rent
+
+    ensure_distinct(&[mint_account, token_account, test_account, authority, receiver])?;
+    require!(authority.is_signer, ProgramError::MissingRequiredSignature);
+    validate_token_program(token_program, mint_account)?;
+    require!(
+        mint_account.is_writable
+            && token_account.is_writable
+            && test_account.is_writable
+            && receiver.is_writable,
+        ProgramError::InvalidAccountData
+    );
+
+    require!(test_account.owner == program_id, ProgramError::IllegalOwner);
+    let record = TestMint::try_from_slice(&test_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    require!(
+        record.account_type == AccountType::TestMint && record.mint == *mint_account.key,
+        ProgramError::InvalidAccountData
+    );
+    require!(record.authority == *authority.key, CustomError::MintAuthorityMismatch);
+
+    // A test mint keeps its authority, a revoked one means a real launch happened since
+    let mint_data = unpack_mint(mint_account)?;
+    require!(
+        mint_data.mint_authority == COption::Some(*authority.key),
+        CustomError::MintAuthorityMismatch
+    );
+
+    // The whole supply has to come back, otherwise the reset mint wouldn't start from zero
+    require!(token_account.owner == mint_account.owner, ProgramError::IllegalOwner);
+    let token_data = unpack_token_account(token_account)?;
+    require!(token_data.mint == *mint_account.key, CustomError::TokenAccountWrongMint);
+    require!(token_data.owner == *authority.key, CustomError::TokenAccountOwnerMismatch);
+    if token_data.amount != mint_data.supply {
+        msg!(
+            "Token account holds {} of the {} base units in circulation",
+            token_data.amount,
+            mint_data.supply
+        );
+        return Err(CustomError::InvalidMintState.into());
+    }
+
+    solana_program::program::invoke(
+        &spl_token_2022::instruction::burn_checked(
+            token_program.key,
+            token_account.key,
+            mint_account.key,
+            authority.key,
+            &[],
+            mint_data.supply,
+            mint_data.decimals,
+        )?,
+        &[
+            token_account.clone(),
+            mint_account.clone(),
+            authority.clone(),
+            token_program.clone(),
+        ],
+    )?;
+    require!(unpack_mint(mint_account)?.supply == 0, CustomError::InvalidMintState);
+
+    close_program_account(test_account, receiver)?;
+    msg!("Test mode: burned {} base units and reset {}", mint_data.supply, mint_account.key);
+
+    Ok(())
+}
+
+/// Processes the `MintChunk` instruction
+/// Mints one chunk without ever exceeding the target, and finalizes the mint on the last one
+pub fn process_mint_chunk(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: MintChunkArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let gate = LaunchGate::parse(program_id, accounts_iter, 1)?;
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let chunked_account = next_account_info(accounts_iter)?;   // The chunked mint PDA
+    let token_account = next_account_info(accounts_iter)?;     // The destination token account
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+    let stats = match args.stats_bump {
+        Some(bump) => Some((
+            bump,
+            next_account_info(accounts_iter)?,                 // The launch stats PDA
+            next_account_info(accounts_iter)?,                 // System program
+        )),
+        None => None,
+    };
+
+    ensure_distinct(&[mint_account, chunked_account, token_account, mint_authority])?;
+    validate_authority(mint_authority, payer)?;
+    gate.authorize(mint_authority.key, &[mint_account.key])?;
+    validate_token_program(token_program, mint_account)?;
+    require!(
+        mint_account.is_writable && chunked_account.is_writable && token_account.is_writable,
+        ProgramError::InvalidAccountData
+    );
+
+    require!(chunked_account.owner == program_id, ProgramError::IllegalOwner);
+    let mut chunked = ChunkedMint::try_from_slice(&chunked_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    require!(
+        chunked.account_type == AccountType::ChunkedMint && chunked.mint == *mint_account.key,
+        ProgramError::InvalidAccountData
+    );
+    require!(chunked.authority == *mint_authority.key, CustomError::MintAuthorityMismatch);
+
+    // Nothing may have been minted outside of the chunks
+    let mint_data = unpack_mint(mint_account)?;
+    require!(
+        mint_data.mint_authority.is_some() || mint_data.supply == 0,
+        CustomError::MintAlreadyFinalized
+    );
+    require!(
+        mint_data.mint_authority == COption::Some(*mint_authority.key)
+            && mint_data.supply == chunked.cumulative_minted,
+        CustomError::InvalidMintState
+    );
+    // The launch pays once, with its first chunk
+    if chunked.cumulative_minted == 0 {
+        gate.charge(payer, 1, &[])?;
+    }
+
+    let cumulative_minted = chunked
+        .cumulative_minted
+        .checked_add(args.amount)
+        .filter(|total| *total <= chunked.target_total)
+        .ok_or(CustomError::ChunkExceedsTarget)?;
+
+    let token_data = unpack_token_account(token_account)?;
+    require!(token_account.owner == mint_account.owner, ProgramError::IllegalOwner);
+    require!(token_data.mint == *mint_account.key, CustomError::TokenAccountWrongMint);
+
+    mint_supply(
+        token_program,
+        mint_account,
+        token_account,
+        mint_authority,
+        args.amount,
+        mint_data.decimals,
+    )?;
+
+    chunked.cumulative_minted = cumulative_minted;
+    chunked.serialize(&mut &mut chunked_account.data.borrow_mut()[..])?;
+
+    let finalized = cumulative_minted == chunked.target_total;
+    if finalized {
+        revoke_mint_authority(token_program, mint_account, mint_authority, chunked.target_total)?;
+    }
+
+    // Updated in the same instruction as the mint, so the totals can't drift from it
+    if let Some((bump, stats_account, system_program)) = stats {
+        let mut stats = load_or_create_launch_stats(
+            program_id,
+            stats_account,
+            payer,
+            system_program,
+            mint_account.key,
+            bump,
+        )?;
+        stats.mint_operations = stats
+            .mint_operations
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        stats.total_minted = stats
+            .total_minted
+            .checked_add(args.amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        if token_data.amount == 0 {
+            stats.recipients = stats
+                .recipients
+                .checked_add(1)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+        stats.finalized = finalized;
+        stats.serialize(&mut &mut stats_account.data.borrow_mut()[..])?;
+    }
+
+    Ok(())
+}
+
+/// Processes the `TreasuryAndLiquidity` instruction
+/// Splits the total supply between a treasury and a liquidity account by basis points,
+/// then permanently revokes the mint authority exactly like `InitializeFixedSupply`
+pub fn process_treasury_and_liquidity(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: TreasuryAndLiquidityArgs,
+) -> ProgramResult {
+    let total_supply_with_decimals = total_supply_with_decimals(DECIMALS)?;
+
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let gate = LaunchGate::parse(program_id, accounts_iter, 1)?;
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let treasury_account = next_account_info(accounts_iter)?;  // Receives the treasury share
+    let liquidity_account = next_account_info(accounts_iter)?; // Receives the remainder
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+    let pool = match &args.pool {
+        Some(pool) => Some((
+            pool,
+            next_account_info(accounts_iter)?,                 // The AMM program
+            accounts_iter.as_slice(),                          // Pool instruction accounts
+        )),
+        None => None,
+    };
+
+    // The two tranches must land in different accounts
+    if treasury_account.key == liquidity_account.key {
+        return Err(ProgramError::InvalidArgument);
+    }
+    ensure_distinct(&[mint_account, treasury_account, liquidity_account, mint_authority])?;
+
+    validate_authority(mint_authority, payer)?;
+    gate.authorize(mint_authority.key, &[mint_account.key])?;
+    gate.charge(payer, 1, &[])?;
+    validate_token_program(token_program, mint_account)?;
+    if !mint_account.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    validate_mint(mint_account, mint_authority, Some(DECIMALS), None)?;
+    validate_destination(treasury_account, mint_account, Some(payer.key))?;
+    validate_destination(liquidity_account, mint_account, Some(payer.key))?;
+
+    // Treasury share is rounded down, the liquidity account receives everything left over
+    // so the two tranches always add up to the exact total supply
+    let treasury_amount = (total_supply_with_decimals as u128)
+        .checked_mul(args.treasury_bps as u128)
+        .map(|v| v / MAX_BASIS_POINTS as u128)
+        .and_then(|v| u64::try_from(v).ok())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let liquidity_amount = total_supply_with_decimals
+        .checked_sub(treasury_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    mint_supply(
+        token_program,
+        mint_account,
+        treasury_account,
+        mint_authority,
+        treasury_amount,
+        DECIMALS,
+    )?;
+    mint_supply(
+        token_program,
+        mint_account,
+        liquidity_account,
+        mint_authority,
+        liquidity_amount,
+        DECIMALS,
+    )?;
+
+    revoke_mint_authority(token_program, mint_account, mint_authority, total_supply_with_decimals)?;
+
+    // The pool is only created once the supply is fixed, so it can never be diluted
+    if let Some((pool, amm_program, pool_accounts)) = pool {
+        initialize_pool(amm_program, pool_accounts, &pool.data)?;
+    }
+
+    Ok(())
+}
+
+// Forwards opaque pool initialization data to the configured AMM program
+// The AMM id is pinned, since the CPI carries the authority's signature
+fn initialize_pool<'a>(
+    amm_program: &AccountInfo<'a>,
+    pool_accounts: &[AccountInfo<'a>],
+    data: &[u8],
+) -> ProgramResult {
+    require!(*amm_program.key == AMM_PROGRAM_ID, ProgramError::IncorrectProgramId);
+    require_executable(&[amm_program])?;
+
+    let instruction = Instruction {
+        program_id: *amm_program.key,
+        accounts: pool_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: *account.key,
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: data.to_vec(),
+    };
+    let mut infos = pool_accounts.to_vec();
+    infos.push(amm_program.clone());
+    solana_program::program::invoke(&instruction, &infos)
+}
+
+/// Processes the `CreateMetadata` instruction
+/// Validates the mint and royalty configuration, then creates the Metaplex metadata account
+pub fn process_create_metadata(
+    accounts: &[AccountInfo],
+    args: CreateMetadataArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let metadata_account = next_account_info(accounts_iter)?;  // The metadata PDA
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let system_program = next_account_info(accounts_iter)?;    // System program
+    let metadata_program = next_account_info(accounts_iter)?;  // Metaplex Token Metadata program
+    let collection = match args.collection {
+        Some(_) => Some((
+            next_account_info(accounts_iter)?,                 // Collection authority (signs)
+            next_account_info(accounts_iter)?,                 // The collection mint
+            next_account_info(accounts_iter)?,                 // The collection metadata
+            next_account_info(accounts_iter)?,                 // The collection master edition
+            next_account_info(accounts_iter)?,                 // Instructions sysvar
+        )),
+        None => None,
+    };
+
+    validate_authority(mint_authority, payer)?;
+    let mint_data = validate_mint(mint_account, mint_authority, None, None)?;
+
+    // Make sure we are talking to the real Metaplex program and its canonical metadata PDA
+    if *metadata_program.key != mpl_token_metadata::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    require_executable(&[metadata_program])?;
+    if *metadata_account.key != Metadata::find_pda(mint_account.key).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Metaplex rejects oversized strings with an opaque error, so name the offending field here.
+    // Borsh already rejected any string that isn't valid UTF-8 while decoding the instruction.
+    verbose!("checking name length: got {} expected at most {}", args.name.len(), MAX_NAME_LENGTH);
+    require!(args.name.len() <= MAX_NAME_LENGTH, CustomError::NameTooLong);
+    verbose!(
+        "checking symbol length: got {} expected at most {}",
+        args.symbol.len(),
+        MAX_SYMBOL_LENGTH
+    );
+    require!(args.symbol.len() <= MAX_SYMBOL_LENGTH, CustomError::SymbolTooLong);
+    verbose!("checking uri length: got {} expected at most {}", args.uri.len(), MAX_URI_LENGTH);
+    require!(args.uri.len() <= MAX_URI_LENGTH, CustomError::UriTooLong);
+
+    // Metaplex records the standard implied by the decimals, a contradicting one would be ignored
+    if let Some(standard) = args.token_standard {
+        verbose!(
+            "checking token standard: got {:?} with {} decimals",
+            standard,
+            mint_data.decimals
+        );
+        if !standard.allows_decimals(mint_data.decimals) {
+            msg!("Token standard {:?} can't have {} decimals", standard, mint_data.decimals);
+            return Err(CustomError::TokenStandardDecimalsMismatch.into());
+        }
+    }
+
+    // Royalty shares must account for exactly 100% of the seller fee
+    let creators = match args.creators {
+        Some(creators) => {
+            let total_share = creators
+                .iter()
+                .try_fold(0u8, |total, (_, share)| total.checked_add(*share));
+            if total_share != Some(100) {
+                return Err(CustomError::InvalidCreatorShares.into());
+            }
+            // Only the signing authority can be marked as a verified creator
+            Some(
+                creators
+                    .into_iter()
+                    .map(|(address, share)| Creator {
+                        address,
+                        verified: address == *mint_authority.key,
+                        share,
+                    })
+                    .collect(),
+            )
+        }
+        None => None,
+    };
+
+    CreateMetadataAccountV3Cpi::new(
+        metadata_program,
+        CreateMetadataAccountV3CpiAccounts {
+            metadata: metadata_account,
+            mint: mint_account,
+            mint_authority,
+            payer,
+            update_authority: (mint_authority, true),
+            system_program,
+            rent: None,
+        },
+        CreateMetadataAccountV3InstructionArgs {
+            data: DataV2 {
+                name: args.name,
+                symbol: args.symbol,
+                uri: args.uri,
+                seller_fee_basis_points: args.seller_fee_basis_points,
+                creators,
+                // Metaplex only accepts the collection unverified, `verify_collection` flips it
+                collection: args.collection.map(|key| Collection { verified: false, key }),
+                uses: None,
+            },
+            is_mutable: args.is_mutable,
+            collection_details: None,
+        },
+    )
+    .invoke()?;
+
+    if let Some((authority, collection_mint, parent, master_edition, instructions)) = collection {
+        verify_collection(
+            metadata_program,
+            metadata_account,
+            system_program,
+            authority,
+            collection_mint,
+            parent,
+            master_edition,
+            instructions,
+        )?;
+    }
+
+    Ok(())
+}
+
+// Verifies a token's freshly created metadata into its collection, signed by the collection's
+// update authority, and checks that Metaplex marked the collection as verified
+#[allow(clippy::too_many_arguments)]
+fn verify_collection<'a>(
+    metadata_program: &AccountInfo<'a>,
+    metadata_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    collection_mint: &AccountInfo<'a>,
+    collection_metadata: &AccountInfo<'a>,
+    collection_master_edition: &AccountInfo<'a>,
+    instructions_sysvar: &AccountInfo<'a>,
+) -> ProgramResult {
+    require!(authority.is_signer, ProgramError::MissingRequiredSignature);
+    if *collection_metadata.key != Metadata::find_pda(collection_mint.key).0
+        || *collection_master_edition.key != MasterEdition::find_pda(collection_mint.key).0
+    {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !sysvar::instructions::check_id(instructions_sysvar.key) {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Metaplex would reject a foreign signer too, but with an error that doesn't say why
+    require!(*collection_metadata.owner == mpl_token_metadata::ID, ProgramError::IllegalOwner);
+    let parent = Metadata::from_bytes(&collection_metadata.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    verbose!(
+        "checking collection authority: got {} expected {}",
+        authority.key,
+        parent.update_authority
+    );
+    if parent.mint != *collection_mint.key || parent.update_authority != *authority.key {
+        msg!("{} is not the update authority of collection {}", authority.key, collection_mint.key);
+        return Err(CustomError::CollectionVerificationFailed.into());
+    }
+
+    VerifyCollectionV1Cpi::new(
+        metadata_program,
+        VerifyCollectionV1CpiAccounts {
+            authority,
+            delegate_record: None,
+            metadata: metadata_account,
+            collection_mint,
+            collection_metadata: Some(collection_metadata),
+            collection_master_edition: Some(collection_master_edition),
+            system_program,
+            sysvar_instructions: instructions_sysvar,
+        },
+    )
+    .invoke()?;
+
+    let metadata = Metadata::from_bytes(&metadata_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    let verified = metadata
+        .collection
+        .is_some_and(|collection| collection.verified && collection.key == *collection_mint.key);
+    if !verified {
+        msg!("Metaplex did not verify the token into collection {}", collection_mint.key);
+        return Err(CustomError::CollectionVerificationFailed.into());
+    }
+    Ok(())
+}
+
+/// Processes the `LockMetadata` instruction
+/// Clears the metadata update authority of a finalized mint and makes its metadata immutable
+pub fn process_lock_metadata(accounts: &[AccountInfo]) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let metadata_account = next_account_info(accounts_iter)?;  // The metadata PDA
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let update_authority = next_account_info(accounts_iter)?;  // Metadata update authority (signs)
+    let metadata_program = next_account_info(accounts_iter)?;  // Metaplex Token Metadata program
+
+    if !update_authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if *metadata_program.key != mpl_token_metadata::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    require_executable(&[metadata_program])?;
+    if *metadata_account.key != Metadata::find_pda(mint_account.key).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Only lock metadata once the supply itself is locked
+    let mint_data = unpack_mint(mint_account)?;
+    if !(mint_data.is_initialized && mint_data.mint_authority.is_none()) {
+        return Err(CustomError::InvalidMintState.into());
+    }
+
+    UpdateMetadataAccountV2Cpi::new(
+        metadata_program,
+        UpdateMetadataAccountV2CpiAccounts {
+            metadata: metadata_account,
+            update_authority,
+        },
+        UpdateMetadataAccountV2InstructionArgs {
+            data: None,
+            new_update_authority: Some(system_program::id()),
+            primary_sale_happened: None,
+            is_mutable: Some(false),
+        },
+    )
+    .invoke()?;
+
+    // Confirm Metaplex applied both changes
+    let metadata = Metadata::from_bytes(&metadata_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if metadata.update_authority != system_program::id() || metadata.is_mutable {
+        return Err(CustomError::MetadataUpdateAuthorityNotCleared.into());
+    }
+
+    Ok(())
+}
+
+/// Processes the `RemainingMintable` instruction
+/// Reports the remaining mintable amount through return data without modifying any account
+pub fn process_remaining_mintable(accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+
+    let mint_data = unpack_mint(mint_account)?;
+
+    // A revoked authority means the supply is permanently fixed
+    let remaining = if mint_data.mint_authority.is_none() {
+        0
+    } else {
+        u64::MAX - mint_data.supply
+    };
+    set_return_data(&remaining.to_le_bytes());
+
+    Ok(())
+}
+
+/// Processes the `IsAdmin` instruction
+/// Reports whether the candidate is the admin stored in the config PDA through return data
+pub fn process_is_admin(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: IsAdminArgs,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let config_account = next_account_info(accounts_iter)?;    // The config PDA
+
+    let config = read_program_config(program_id, config_account)?;
+    set_return_data(&[u8::from(args.candidate == config.admin)]);
+
+    Ok(())
+}
+
+/// Processes the `SetConfigAdmin` instruction
+/// Stores a new admin in the config PDA, creating it on first use
+pub fn process_set_config_admin(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: ConfigAdminArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let config_account = next_account_info(accounts_iter)?;    // The config PDA
+    let admin = next_account_info(accounts_iter)?;             // The current admin (must sign)
+    let system_program = next_account_info(accounts_iter)?;    // System program
+    let program_data = next_account_info(accounts_iter)?;      // This program's ProgramData
+
+    require!(admin.is_signer, ProgramError::MissingRequiredSignature);
+    require!(config_account.is_writable, ProgramError::InvalidAccountData);
+    require!(args.admin != Pubkey::default(), CustomError::InvalidAuthorityAccount);
+    verify_pda(config_account.key, &[CONFIG_SEED], args.bump, program_id)?;
+
+    // The upgrade authority bootstraps the config, the stored admin owns the role afterwards
+    let settings = if config_account.owner != program_id {
+        let upgrade_authority = read_upgrade_authority(program_id, program_data)?;
+        verbose!("checking admin: got {} expected {:?}", admin.key, upgrade_authority);
+        require!(upgrade_authority == Some(*admin.key), CustomError::AdminMismatch);
+        create_pda_account(
+            admin,
+            config_account,
+            system_program,
+            program_id,
+            ProgramConfig::LEN,
+            &[CONFIG_SEED, &[args.bump]],
+        )?;
+        ConfigSettings::default()
+    } else {
+        verify_config_admin(program_id, config_account, admin)?;
+        read_program_config(program_id, config_account)?.settings
+    };
+
+    let config = ProgramConfig {
+        account_type: AccountType::ProgramConfig,
+        admin: args.admin,
+        settings,
+    };
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    msg!("Config admin set to {}", args.admin);
+
+    Ok(())
+}
+
+/// Processes the `UpdateConfig` instruction
+/// Replaces the launch policy in the config PDA, keeping the admin
+pub fn process_update_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    settings: ConfigSettings,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let config_account = next_account_info(accounts_iter)?;    // The config PDA
+    let admin = next_account_info(accounts_iter)?;             // The admin (must sign)
+    let oracle_program = match settings.requires_oracle_approval() {
+        true => Some(next_account_info(accounts_iter)?),       // The oracle program
+        false => None,
+    };
+    let timelock_program = match settings.has_timelock_program() {
+        true => Some(next_account_info(accounts_iter)?),       // The timelock program
+        false => None,
+    };
+
+    verify_config_admin(program_id, config_account, admin)?;
+    require!(config_account.is_writable, ProgramError::InvalidAccountData);
+    // Approvals can only come from an oracle that is actually deployed
+    if let Some(oracle_program) = oracle_program {
+        require!(*oracle_program.key == settings.oracle_program, ProgramError::InvalidArgument);
+        require_executable(&[oracle_program])?;
+    }
+    // Likewise the timelock, or freeze authorities would go to PDAs nothing can sign for
+    if let Some(timelock_program) = timelock_program {
+        require!(
+            *timelock_program.key == settings.timelock_program,
+            ProgramError::InvalidArgument
+        );
+        require_executable(&[timelock_program])?;
+    }
+    // A fee with nowhere to go would fail every launch
+    require!(
+        !settings.charges_launch_fee() || settings.fee_treasury != Pubkey::default(),
+        ProgramError::InvalidArgument
+    );
+
+    let mut config = read_program_config(program_id, config_account)?;
+    config.settings = settings;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    msg!("Config updated: {:?}", config.settings);
+
+    Ok(())
+}
+
+/// Processes the `AddLauncher` instruction
+/// Appends a key to the launcher allowlist, creating the allowlist PDA on first use
+pub fn process_add_launcher(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: LauncherArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let allowlist_account = next_account_info(accounts_iter)?; // The launcher allowlist PDA
+    let admin = next_account_info(accounts_iter)?;             // The admin (must sign)
+    let system_program = next_account_info(accounts_iter)?;    // System program
+    let config_account = next_account_info(accounts_iter)?;    // The config PDA
+
+    verify_config_admin(program_id, config_account, admin)?;
+    require!(allowlist_account.is_writable, ProgramError::InvalidAccountData);
+    verify_pda(allowlist_account.key, &[LAUNCHER_ALLOWLIST_SEED], args.bump, program_id)?;
+
+    let mut allowlist = if allowlist_account.owner != program_id {
+        create_pda_account(
+            admin,
+            allowlist_account,
+            system_program,
+            program_id,
+            LauncherAllowlist::LEN,
+            &[LAUNCHER_ALLOWLIST_SEED, &[args.bump]],
+        )?;
+        LauncherAllowlist { account_type: AccountType::LauncherAllowlist, launchers: Vec::new() }
+    } else {
+        read_launcher_allowlist(program_id, allowlist_account)?
+    };
+
+    if allowlist.launchers.contains(&args.launcher) {
+        msg!("Launcher {} is already allowlisted", args.launcher);
+        return Err(ProgramError::InvalidArgument);
+    }
+    if allowlist.launchers.len() >= MAX_ALLOWLISTED_LAUNCHERS {
+        msg!("Launcher allowlist is full at {} keys", MAX_ALLOWLISTED_LAUNCHERS);
+        return Err(ProgramError::InvalidArgument);
+    }
+    allowlist.launchers.push(args.launcher);
+    allowlist.serialize(&mut &mut allowlist_account.data.borrow_mut()[..])?;
+    msg!("Allowlisted launcher {}", args.launcher);
+
+    Ok(())
+}
+
+/// Processes the `RemoveLauncher` instruction
+/// Drops a key from the launcher allowlist
+pub fn process_remove_launcher(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: LauncherArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let allowlist_account = next_account_info(accounts_iter)?; // The launcher allowlist PDA
+    let admin = next_account_info(accounts_iter)?;             // The admin (must sign)
+    let config_account = next_account_info(accounts_iter)?;    // The config PDA
+
+    verify_config_admin(program_id, config_account, admin)?;
+    require!(allowlist_account.is_writable, ProgramError::InvalidAccountData);
+    verify_pda(allowlist_account.key, &[LAUNCHER_ALLOWLIST_SEED], args.bump, program_id)?;
+
+    let mut allowlist = read_launcher_allowlist(program_id, allowlist_account)?;
+    let index = allowlist
+        .launchers
+        .iter()
+        .position(|launcher| *launcher == args.launcher)
+        .ok_or(CustomError::LauncherNotAllowlisted)?;
+    allowlist.launchers.remove(index);
+    // The shorter list leaves stale bytes past its end, which readers ignore
+    allowlist.serialize(&mut &mut allowlist_account.data.borrow_mut()[..])?;
+    msg!("Removed launcher {} from the allowlist", args.launcher);
+
+    Ok(())
+}
+
+/// Processes the `Cleanup` instruction
+/// Closes the transient PDAs of a finalized launch, refunding their rent to the receiver
+pub fn process_cleanup(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let authority = next_account_info(accounts_iter)?;         // The launch authority (must sign)
+    let receiver = next_account_info(accounts_iter)?;          // Receives the rent
+    let transient = accounts_iter.as_slice();                  // The PDAs to close
+
+    require!(authority.is_signer, ProgramError::MissingRequiredSignature);
+    require!(!transient.is_empty(), ProgramError::NotEnoughAccountKeys);
+    require!(receiver.is_writable, ProgramError::InvalidAccountData);
+    let mut distinct: Vec<&AccountInfo> = vec![mint_account, receiver];
+    distinct.extend(transient);
+    ensure_distinct(&distinct)?;
+
+    // The PDAs still drive an unfinished launch, closing them would strand it
+    let mint_data = unpack_mint(mint_account)?;
+    if mint_data.mint_authority.is_some() {
+        msg!("Mint {} still has a mint authority", mint_account.key);
+        return Err(CustomError::CannotCleanupBeforeFinalize.into());
+    }
+
+    let mut closes_chunked_mint = false;
+    let mut closes_stats = false;
+    for account in transient {
+        require!(account.owner == program_id, ProgramError::IllegalOwner);
+        require!(account.is_writable, ProgramError::InvalidAccountData);
+        let account_type = AccountType::deserialize(&mut &account.data.borrow()[..])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        let (mint, creator) = match account_type {
+            AccountType::ChunkedMint => {
+                let chunked = ChunkedMint::try_from_slice(&account.data.borrow())
+                    .map_err(|_| ProgramError::InvalidAccountData)?;
+                closes_chunked_mint |= chunked.authority == *authority.key;
+                (chunked.mint, Some(chunked.authority))
+            }
+            AccountType::DistributionList => {
+                let header = read_distribution_list_header(program_id, account)?;
+                (header.mint, Some(header.authority))
+            }
+            AccountType::LaunchStats => {
+                closes_stats = true;
+                (read_launch_stats(program_id, account)?.mint, None)
+            }
+            _ => {
+                msg!("Account {} is a permanent record and can't be closed", account.key);
+                return Err(ProgramError::InvalidAccountData);
+            }
+        };
+        require!(mint == *mint_account.key, ProgramError::InvalidAccountData);
+        if let Some(creator) = creator {
+            require!(creator == *authority.key, CustomError::InvalidAuthorityAccount);
+        }
+    }
+    // Launch stats carry no authority of their own, they belong to the chunked mint's authority
+    if closes_stats && !closes_chunked_mint {
+        msg!("Launch stats can only be closed together with the chunked mint PDA");
+        return Err(CustomError::InvalidAuthorityAccount.into());
+    }
+
+    let mut reclaimed: u64 = 0;
+    for account in transient {
+        reclaimed = reclaimed.saturating_add(account.lamports());
+        close_program_account(account, receiver)?;
+    }
+    msg!(
+        "Closed {} transient PDAs of {}, reclaimed {} lamports",
+        transient.len(),
+        mint_account.key,
+        reclaimed
+    );
+
+    Ok(())
+}
+
+/// Processes the `GetValuation` instruction
+/// Reports the mint's fully diluted value at the given price through the log and return data
+pub fn process_get_valuation(accounts: &[AccountInfo], args: ValuationArgs) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+
+    let mint_data = unpack_mint(mint_account)?;
+    let value = fully_diluted_value(mint_data.supply, mint_data.decimals, args.price_per_token)
+        .ok_or(CustomError::ValuationOverflow)?;
+    msg!(
+        "Fully diluted value of {} tokens at {} per token: {}",
+        format_ui_amount(mint_data.supply, mint_data.decimals),
+        args.price_per_token,
+        value
+    );
+    set_return_data(&value.to_le_bytes());
+
+    Ok(())
+}
+
+/// Processes the `ValidateOnly` instruction
+/// Runs every launch check and reports all failures through return data without modifying state
+pub fn process_validate_only(accounts: &[AccountInfo], args: ValidateOnlyArgs) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let token_account = next_account_info(accounts_iter)?;     // The destination token account
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+
+    let report = run_launch_checks(
+        mint_account,
+        token_account,
+        mint_authority,
+        payer,
+        token_program,
+        args.decimals,
+    );
+    return_validation_report(&report)
+}
+
+/// Processes the `VerifyAndFinalize` instruction
+/// Runs the `ValidateOnly` checks and, only when all of them pass, mints the supply and revokes
+pub fn process_verify_and_finalize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: ValidateOnlyArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let gate = LaunchGate::parse(program_id, accounts_iter, 1)?;
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let token_account = next_account_info(accounts_iter)?;     // The destination token account
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+
+    // A failed check returns the full report before anything is written
+    let report = run_launch_checks(
+        mint_account,
+        token_account,
+        mint_authority,
+        payer,
+        token_program,
+        args.decimals,
+    );
+    return_validation_report(&report)?;
+
+    // The report doesn't cover everything a launch checks, e.g. destination extensions
+    ensure_distinct(&[mint_account, token_account, mint_authority])?;
+    validate_authority(mint_authority, payer)?;
+    gate.authorize(mint_authority.key, &[mint_account.key])?;
+    gate.charge(payer, 1, &[])?;
+    validate_token_program(token_program, mint_account)?;
+    require!(mint_account.is_writable, ProgramError::InvalidAccountData);
+    let mint_data = validate_mint(mint_account, mint_authority, args.decimals, None)?;
+    validate_destination(token_account, mint_account, Some(payer.key))?;
+
+    let total_supply_with_decimals = total_supply_with_decimals(mint_data.decimals)?;
+    mint_supply(
+        token_program,
+        mint_account,
+        token_account,
+        mint_authority,
+        total_supply_with_decimals,
+        mint_data.decimals,
+    )?;
+    revoke_mint_authority(token_program, mint_account, mint_authority, total_supply_with_decimals)?;
+
+    // Replace the passing report with the finalized state
+    let final_mint_data = unpack_mint(mint_account)?;
+    let final_report = FinalizeReport {
+        validation: report,
+        supply: final_mint_data.supply,
+        decimals: final_mint_data.decimals,
+        mint_authority_revoked: final_mint_data.mint_authority.is_none(),
+    };
+    set_return_data(&borsh::to_vec(&final_report)?);
+
+    Ok(())
+}
+
+// Runs every check of a plain launch without stopping at the first failure
+fn run_launch_checks(
+    mint_account: &AccountInfo,
+    token_account: &AccountInfo,
+    mint_authority: &AccountInfo,
+    payer: &AccountInfo,
+    token_program: &AccountInfo,
+    decimals: Option<u8>,
+) -> ValidationReport {
+    use ReportValue::{Flag, Key, Number};
+    let mut report = ValidationReport::default();
+    report.require(
+        ValidationCheck::AuthoritySigned,
+        mint_authority.is_signer,
+        Flag(mint_authority.is_signer),
+        Flag(true),
+    );
+    report.require(
+        ValidationCheck::PayerSigned,
+        payer.is_signer,
+        Flag(payer.is_signer),
+        Flag(true),
+    );
+    report.require(
+        ValidationCheck::AuthorityIsPayer,
+        mint_authority.key == payer.key,
+        Key(*mint_authority.key),
+        Key(*payer.key),
+    );
+    report.require(
+        ValidationCheck::TokenProgram,
+        is_token_program(token_program.key) && mint_account.owner == token_program.key,
+        Key(*mint_account.owner),
+        Key(*token_program.key),
+    );
+
+    // Checks on an account that can't be read are reported once, as the account's first check
+    match unpack_mint(mint_account) {
+        Ok(mint_data) => {
+            report.require(
+                ValidationCheck::MintInitialized,
+                mint_data.is_initialized,
+                Flag(mint_data.is_initialized),
+                Flag(true),
+            );
+            let decimals_ok = match decimals {
+                Some(decimals) => mint_data.decimals == decimals,
+                None => ALLOWED_DECIMALS.contains(&mint_data.decimals),
+            };
+            report.require(
+                ValidationCheck::Decimals,
+                decimals_ok,
+                Number(mint_data.decimals.into()),
+                decimals.map_or(ReportValue::None, |decimals| Number(decimals.into())),
+            );
+            report.require(
+                ValidationCheck::MintSupply,
+                mint_data.supply == 0,
+                Number(mint_data.supply),
+                Number(0),
+            );
+            report.require(
+                ValidationCheck::MintAuthority,
+                mint_data.mint_authority == COption::Some(*mint_authority.key),
+                mint_data.mint_authority.into(),
+                Key(*mint_authority.key),
+            );
+            report.require(
+                ValidationCheck::FreezeAuthority,
+                mint_data.freeze_authority.is_none(),
+                mint_data.freeze_authority.into(),
+                ReportValue::None,
+            );
+        }
+        Err(_) => {
+            report.require(ValidationCheck::MintInitialized, false, ReportValue::None, Flag(true))
+        }
+    }
+
+    report.require(
+        ValidationCheck::DestinationProgram,
+        token_account.owner == mint_account.owner,
+        Key(*token_account.owner),
+        Key(*mint_account.owner),
+    );
+    match unpack_token_account(token_account) {
+        Ok(token_data) => {
+            report.require(
+                ValidationCheck::DestinationMint,
+                token_data.mint == *mint_account.key,
+                Key(token_data.mint),
+                Key(*mint_account.key),
+            );
+            report.require(
+                ValidationCheck::DestinationOwner,
+                token_data.owner == *payer.key,
+                Key(token_data.owner),
+                Key(*payer.key),
+            );
+            report.require(
+                ValidationCheck::DestinationBalance,
+                token_data.amount == 0,
+                Number(token_data.amount),
+                Number(0),
+            );
+            let delegated = token_data.delegate.is_some() || token_data.close_authority.is_some();
+            report.require(
+                ValidationCheck::DestinationDelegate,
+                !delegated,
+                Flag(delegated),
+                Flag(false),
+            );
+        }
+        Err(_) => report.require(
+            ValidationCheck::DestinationMint,
+            false,
+            ReportValue::None,
+            Key(*mint_account.key),
+        ),
+    }
+
+    report
+}
+
+// Returns `report` through return data, failing with `ValidationFailed` when any check failed
+fn return_validation_report(report: &ValidationReport) -> ProgramResult {
+    set_return_data(&borsh::to_vec(report)?);
+    if report.failures.is_empty() {
+        msg!("All launch checks passed");
+        return Ok(());
+    }
+    for failure in &report.failures {
+        msg!(
+            "Check {:?} failed: got {:?} expected {:?}",
+            failure.check,
+            failure.observed,
+            failure.expected
+        );
+    }
+    Err(CustomError::ValidationFailed.into())
+}
+
+/// Processes the `CheckPolicy` instruction
+/// Reports which immutable token criteria the mint meets without modifying any state
+pub fn process_check_policy(accounts: &[AccountInfo], policy: MintPolicy) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+
+    let mint_data = unpack_mint(mint_account)?;
+
+    let mut result = 0;
+    if mint_data.mint_authority.is_none() {
+        result |= POLICY_MINT_AUTHORITY_REVOKED;
+    }
+    if mint_data.freeze_authority == COption::from(policy.freeze_authority)
+        || mint_data.freeze_authority.is_none()
+    {
+        result |= POLICY_FREEZE_AUTHORITY_ALLOWED;
+    }
+    if policy.allowed_decimals.is_empty() || policy.allowed_decimals.contains(&mint_data.decimals)
+    {
+        result |= POLICY_DECIMALS_ALLOWED;
+    }
+    if mint_data.supply > 0 {
+        result |= POLICY_SUPPLY_NONZERO;
+    }
+    verbose!("Policy check for {}: {:#06b}", mint_account.key, result);
+    set_return_data(&[result]);
+
+    Ok(())
+}
+
+// Resolves the decimals for a launch, enforcing the platform whitelist on configured values
+fn configured_decimals(decimals: Option<u8>) -> Result<u8, ProgramError> {
+    match decimals {
+        None => Ok(DECIMALS),
+        Some(decimals) if ALLOWED_DECIMALS.contains(&decimals) => Ok(decimals),
+        Some(_) => Err(CustomError::DecimalsNotAllowed.into()),
+    }
+}
+
+/// Processes the `RevokeAuthority` instruction
+/// Locks down the mint or freeze authority on its own and verifies it is gone afterwards
+pub fn process_revoke_authority(
+    accounts: &[AccountInfo],
+    args: RevokeAuthorityArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let authority = next_account_info(accounts_iter)?;         // Current authority (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validate_token_program(token_program, mint_account)?;
+    if !mint_account.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    ensure_distinct(&[mint_account, authority])?;
+
+    let mint_data = unpack_mint(mint_account)?;
+    match decode_authority_type(args.authority_type)? {
+        AuthorityType::MintTokens => {
+            if mint_data.mint_authority != COption::Some(*authority.key) {
+                return Err(CustomError::MintAuthorityMismatch.into());
+            }
+            // A standalone revocation fixes whatever the holder has minted so far
+            revoke_mint_authority(token_program, mint_account, authority, mint_data.supply)
+        }
+        AuthorityType::FreezeAccount => {
+            if mint_data.freeze_authority != COption::Some(*authority.key) {
+                return Err(CustomError::FreezeAuthorityMismatch.into());
+            }
+            revoke_freeze_authority(token_program, mint_account, authority)
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+/// Processes the `RevokeAuthorities` instruction
+/// Revokes every listed authority of the mint in order, then verifies that none of them is set
+pub fn process_revoke_authorities(
+    accounts: &[AccountInfo],
+    args: RevokeAuthoritiesArgs,
+) -> ProgramResult {
+    // Extract the mint and token program, followed by one signer per authority type
+    let accounts_iter = &mut accounts.iter();
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+    let authorities = accounts_iter.as_slice();
+    if authorities.len() != args.authority_types.len() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    validate_token_program(token_program, mint_account)?;
+    if !mint_account.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    for (authority_type, authority) in args.authority_types.iter().zip(authorities) {
+        let authority_type = decode_authority_type(*authority_type)?;
+        if let Err(e) =
+            revoke_authority_checked(token_program, mint_account, authority, &authority_type)
+        {
+            msg!("Revoking the {:?} authority failed", authority_type);
+            return Err(e);
+        }
+    }
+
+    // Re-read the mint once all CPIs are done, so no later revocation can have undone one
+    for authority_type in &args.authority_types {
+        let authority_type = decode_authority_type(*authority_type)?;
+        if mint_authority_of_type(mint_account, &authority_type)?.is_some() {
+            msg!("The {:?} authority is still set after the sweep", authority_type);
+            return Err(match authority_type {
+                AuthorityType::MintTokens => CustomError::MintAuthorityNotRevoked,
+                AuthorityType::FreezeAccount => CustomError::FreezeAuthorityNotRevoked,
+                _ => CustomError::ExtensionAuthorityNotRevoked,
+            }
+            .into());
+        }
+    }
+    msg!("Revoked {} authorities of {}", args.authority_types.len(), mint_account.key);
+
+    Ok(())
+}
+
+// Checks that `authority` signs for and holds `authority_type` on the mint, then revokes it
+fn revoke_authority_checked<'a>(
+    token_program: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    authority_type: &AuthorityType,
+) -> ProgramResult {
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    ensure_distinct(&[mint_account, authority])?;
+
+    if mint_authority_of_type(mint_account, authority_type)? != Some(*authority.key) {
+        return Err(match authority_type {
+            AuthorityType::MintTokens => CustomError::MintAuthorityMismatch,
+            AuthorityType::FreezeAccount => CustomError::FreezeAuthorityMismatch,
+            _ => CustomError::InvalidAuthorityAccount,
+        }
+        .into());
+    }
+    solana_program::program::invoke(
+        &set_authority(
+            token_program.key,
+            mint_account.key,
+            None,
+            authority_type.clone(),
+            authority.key,
+            &[],
+        )?,
+        &[mint_account.clone(), authority.clone(), token_program.clone()],
+    )
+}
+
+// Current holder of one of the mint's authorities, `None` once revoked
+// Extension authorities fail with the token program's error when the mint lacks the extension.
+fn mint_authority_of_type(
+    mint_account: &AccountInfo,
+    authority_type: &AuthorityType,
+) -> Result<Option<Pubkey>, ProgramError> {
+    let data = mint_account.data.borrow();
+    let state = StateWithExtensions::<Mint>::unpack(&data)?;
+    let authority = match authority_type {
+        AuthorityType::MintTokens => state.base.mint_authority.into(),
+        AuthorityType::FreezeAccount => state.base.freeze_authority.into(),
+        AuthorityType::TransferFeeConfig => {
+            state.get_extension::<TransferFeeConfig>()?.transfer_fee_config_authority.into()
+        }
+        AuthorityType::WithheldWithdraw => {
+            state.get_extension::<TransferFeeConfig>()?.withdraw_withheld_authority.into()
+        }
+        AuthorityType::CloseMint => {
+            state.get_extension::<MintCloseAuthority>()?.close_authority.into()
+        }
+        AuthorityType::InterestRate => {
+            state.get_extension::<InterestBearingConfig>()?.rate_authority.into()
+        }
+        AuthorityType::PermanentDelegate => {
+            state.get_extension::<PermanentDelegate>()?.delegate.into()
+        }
+        AuthorityType::ConfidentialTransferMint => {
+            state.get_extension::<ConfidentialTransferMint>()?.authority.into()
+        }
+        AuthorityType::TransferHookProgramId => {
+            state.get_extension::<TransferHook>()?.authority.into()
+        }
+        AuthorityType::ConfidentialTransferFeeConfig => {
+            state.get_extension::<ConfidentialTransferFeeConfig>()?.authority.into()
+        }
+        AuthorityType::MetadataPointer => {
+            state.get_extension::<MetadataPointer>()?.authority.into()
+        }
+        AuthorityType::GroupPointer => state.get_extension::<GroupPointer>()?.authority.into(),
+        AuthorityType::GroupMemberPointer => {
+            state.get_extension::<GroupMemberPointer>()?.authority.into()
+        }
+        AuthorityType::AccountOwner | AuthorityType::CloseAccount => {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+    };
+    Ok(authority)
+}
+
+/// Processes the `BatchRevoke` instruction
+/// Revokes the mint authority of every (mint, authority) pair in order, stopping at the first
+/// failure
+pub fn process_batch_revoke(accounts: &[AccountInfo], args: BatchRevokeArgs) -> ProgramResult {
+    // Extract the token program, followed by exactly one (mint, authority) pair per mint
+    let accounts_iter = &mut accounts.iter();
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+    let pairs = accounts_iter.as_slice();
+    if pairs.len() != args.mints as usize * 2 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    for (index, pair) in pairs.chunks_exact(2).enumerate() {
+        if let Err(e) = revoke_mint_authority_checked(token_program, &pair[0], &pair[1]) {
+            msg!("Batch revoke failed at mint index {}", index);
+            return Err(e);
+        }
+    }
+
+    Ok(())
+}
+
+// Validates the mint and its signing authority, then revokes the mint authority
+fn revoke_mint_authority_checked<'a>(
+    token_program: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+) -> ProgramResult {
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validate_token_program(token_program, mint_account)?;
+    if !mint_account.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    ensure_distinct(&[mint_account, authority])?;
+
+    let mint_data = unpack_mint(mint_account)?;
+    if mint_data.mint_authority != COption::Some(*authority.key) {
+        return Err(CustomError::MintAuthorityMismatch.into());
+    }
+    revoke_mint_authority(token_program, mint_account, authority, mint_data.supply)
+}
+
+// Revokes the mint's freeze authority and verifies it is gone afterwards
+fn revoke_freeze_authority<'a>(
+    token_program: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    freeze_authority: &AccountInfo<'a>,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &set_authority(
+            token_program.key,
+            mint_account.key,
+            None,
+            AuthorityType::FreezeAccount,
+            freeze_authority.key,
+            &[],
+        )?,
+        &[mint_account.clone(), freeze_authority.clone(), token_program.clone()],
+    )?;
+    require!(
+        unpack_mint(mint_account)?.freeze_authority.is_none(),
+        CustomError::FreezeAuthorityNotRevoked
+    );
+    Ok(())
+}
+
+/// Processes the `SetFreezeAuthority` instruction
+/// Hands the freeze authority of a fixed-supply mint to a compliance multisig
+pub fn process_set_freeze_authority(accounts: &[AccountInfo]) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let freeze_authority = next_account_info(accounts_iter)?;  // Current freeze authority (signs)
+    let multisig_account = next_account_info(accounts_iter)?;  // The new freeze authority
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+
+    if !freeze_authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validate_token_program(token_program, mint_account)?;
+    if !mint_account.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    validate_multisig(multisig_account, token_program)?;
+
+    // Only finalized mints qualify, and the signer must be the current freeze authority
+    let mint_data = unpack_mint(mint_account)?;
+    if !(mint_data.is_initialized
+        && mint_data.mint_authority.is_none()
+        && mint_data.freeze_authority == COption::Some(*freeze_authority.key))
+    {
+        return Err(CustomError::InvalidMintState.into());
+    }
+
+    solana_program::program::invoke(
+        &set_authority(
+            token_program.key,
+            mint_account.key,
+            Some(multisig_account.key),
+            AuthorityType::FreezeAccount,
+            freeze_authority.key,
+            &[],
+        )?,
+        &[
+            mint_account.clone(),
+            freeze_authority.clone(),
+            token_program.clone(),
+        ],
+    )?;
+
+    // The multisig must now hold the freeze authority while minting stays impossible. Comparing
+    // against the exact key catches a transfer that left some other authority (or none) in place.
+    let final_mint_data = unpack_mint(mint_account)?;
+    require!(final_mint_data.mint_authority.is_none(), CustomError::MintAuthorityNotRevoked);
+    verbose!(
+        "checking new freeze authority: got {:?} expected {}",
+        final_mint_data.freeze_authority,
+        multisig_account.key
+    );
+    require!(
+        final_mint_data.freeze_authority == COption::Some(*multisig_account.key),
+        CustomError::AuthorityTransferFailed
+    );
+
+    Ok(())
+}
+
+/// Processes the `CreateDistributionList` instruction
+/// Allocates an empty, program-owned list of recipients for a mint that is yet to be launched
+pub fn process_create_distribution_list(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: CreateDistributionListArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let list_account = next_account_info(accounts_iter)?;      // The distribution list PDA
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let system_program = next_account_info(accounts_iter)?;    // System program
+
+    validate_authority(mint_authority, payer)?;
+    validate_mint(mint_account, mint_authority, None, None)?;
+
+    verify_pda(
+        list_account.key,
+        &[DISTRIBUTION_SEED, mint_account.key.as_ref()],
+        args.bump,
+        program_id,
+    )?;
+    if list_account.owner == program_id || !list_account.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    create_pda_account(
+        payer,
+        list_account,
+        system_program,
+        program_id,
+        DistributionListHeader::account_len(args.capacity),
+        &[DISTRIBUTION_SEED, mint_account.key.as_ref(), &[args.bump]],
+    )?;
+
+    let header = DistributionListHeader {
+        account_type: AccountType::DistributionList,
+        mint: *mint_account.key,
+        authority: *mint_authority.key,
+        capacity: args.capacity,
+        written: 0,
+    };
+    header.serialize(&mut &mut list_account.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+/// Processes the `WriteDistributionList` instruction
+/// Appends entries in order, so a list can be filled across several transactions
+pub fn process_write_distribution_list(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: WriteDistributionListArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let list_account = next_account_info(accounts_iter)?;      // The distribution list PDA
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+
+    if !mint_authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let mut header = read_distribution_list_header(program_id, list_account)?;
+    if header.authority != *mint_authority.key {
+        return Err(CustomError::MintAuthorityMismatch.into());
+    }
+
+    // Entries are append-only, which keeps the written prefix contiguous and easy to verify
+    let written = u16::try_from(args.entries.len())
+        .ok()
+        .and_then(|count| header.written.checked_add(count))
+        .filter(|written| *written <= header.capacity)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    if args.offset != header.written {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let mut data = list_account.data.borrow_mut();
+    for (index, entry) in (args.offset..).zip(&args.entries) {
+        let start = DistributionListHeader::entry_offset(index);
+        entry.serialize(&mut &mut data[start..start + DistributionEntry::LEN])?;
+    }
+    header.written = written;
+    header.serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+/// Processes the `Distribute` instruction
+/// Mints the total supply to every recipient of a complete distribution list, then revokes
+pub fn process_distribute(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: DistributeArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let gate = LaunchGate::parse(program_id, accounts_iter, 1)?;
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let list_account = next_account_info(accounts_iter)?;      // The distribution list PDA
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+    let destinations = accounts_iter.as_slice();
+
+    // Catch a client whose account list drifted from its own count before anything else
+    verbose!(
+        "checking recipient count: got {} expected {}",
+        destinations.len(),
+        args.num_recipients
+    );
+    require!(
+        destinations.len() == args.num_recipients as usize,
+        CustomError::RecipientCountMismatch
+    );
+
+    ensure_distinct(&[mint_account, list_account, mint_authority])?;
+    for destination in destinations {
+        ensure_distinct(&[mint_account, list_account, mint_authority, destination])?;
+    }
+    ensure_distinct_destinations(destinations.iter())?;
+    validate_authority(mint_authority, payer)?;
+    gate.authorize(mint_authority.key, &[mint_account.key])?;
+    gate.charge(payer, 1, &[])?;
+    validate_token_program(token_program, mint_account)?;
+    if !mint_account.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let mint_data = validate_mint(mint_account, mint_authority, None, None)?;
+
+    // The list must belong to this mint and authority and be completely written
+    let header = read_distribution_list_header(program_id, list_account)?;
+    if header.mint != *mint_account.key || header.written != header.capacity {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if header.authority != *mint_authority.key {
+        return Err(CustomError::MintAuthorityMismatch.into());
+    }
+    if destinations.len() != header.capacity as usize {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let entries = read_distribution_entries(list_account, &header)?;
+
+    // The amounts must add up to exactly the total supply for the mint's decimals
+    let total_supply_with_decimals = total_supply_with_decimals(mint_data.decimals)?;
+    let mut distributed: u64 = 0;
+    for entry in &entries {
+        // A zero amount usually means a weight rounded away, so it has to be opted into
+        if entry.amount == 0 && !args.allow_zero_amounts {
+            return Err(CustomError::ZeroAmountRecipient.into());
+        }
+        distributed = distributed
+            .checked_add(entry.amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+    if distributed != total_supply_with_decimals {
+        return Err(CustomError::DistributionTotalMismatch.into());
+    }
+
+    // Validate every destination before minting anything
+    for (destination, entry) in destinations.iter().zip(&entries) {
+        if *destination.key != entry.destination {
+            return Err(ProgramError::InvalidArgument);
+        }
+        validate_destination(destination, mint_account, None)?;
+    }
+
+    for (destination, entry) in destinations.iter().zip(&entries) {
+        mint_supply(
+            token_program,
+            mint_account,
+            destination,
+            mint_authority,
+            entry.amount,
+            mint_data.decimals,
+        )?;
+    }
+
+    revoke_mint_authority(token_program, mint_account, mint_authority, total_supply_with_decimals)
+}
+
+/// Processes the `DistributeChunk` instruction
+/// Mints to the list entries from the stored cursor on, advances it, and revokes once the whole
+/// list is minted to
+pub fn process_distribute_chunk(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: DistributeChunkArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let gate = LaunchGate::parse(program_id, accounts_iter, 1)?;
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let list_account = next_account_info(accounts_iter)?;      // The distribution list PDA
+    let cursor_account = next_account_info(accounts_iter)?;    // The distribution cursor PDA
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+    let system_program = next_account_info(accounts_iter)?;    // System program
+    let destinations = accounts_iter.as_slice();
+
+    require!(
+        !destinations.is_empty() && destinations.len() <= MAX_DISTRIBUTION_CHUNK,
+        ProgramError::NotEnoughAccountKeys
+    );
+    ensure_distinct_destinations(destinations.iter())?;
+    let mut distinct: Vec<&AccountInfo> =
+        vec![mint_account, list_account, cursor_account, mint_authority];
+    distinct.extend(destinations);
+    ensure_distinct(&distinct)?;
+    validate_authority(mint_authority, payer)?;
+    gate.authorize(mint_authority.key, &[mint_account.key])?;
+    validate_token_program(token_program, mint_account)?;
+    require!(mint_account.is_writable, ProgramError::InvalidAccountData);
+
+    // The list must belong to this mint and authority and be completely written
+    let header = read_distribution_list_header(program_id, list_account)?;
+    require!(
+        header.mint == *mint_account.key && header.written == header.capacity,
+        ProgramError::InvalidAccountData
+    );
+    require!(header.authority == *mint_authority.key, CustomError::MintAuthorityMismatch);
+    let entries = read_distribution_entries(list_account, &header)?;
+    verify_pda(
+        cursor_account.key,
+        &[DISTRIBUTION_CURSOR_SEED, mint_account.key.as_ref()],
+        args.cursor_bump,
+        program_id,
+    )?;
+
+    // The first chunk checks the whole list up front, later chunks trust the cursor it created
+    let mut cursor = if cursor_account.data_is_empty() {
+        let mint_data = validate_mint(mint_account, mint_authority, None, None)?;
+        let total_supply_with_decimals = total_supply_with_decimals(mint_data.decimals)?;
+        let mut distributed: u64 = 0;
+        for entry in &entries {
+            // A zero amount usually means a weight rounded away, so it has to be opted into
+            if entry.amount == 0 && !args.allow_zero_amounts {
+                return Err(CustomError::ZeroAmountRecipient.into());
+            }
+            distributed = distributed
+                .checked_add(entry.amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+        require!(
+            distributed == total_supply_with_decimals,
+            CustomError::DistributionTotalMismatch
+        );
+        gate.charge(payer, 1, &[DistributionCursor::LEN])?;
+
+        create_pda_account(
+            payer,
+            cursor_account,
+            system_program,
+            program_id,
+            DistributionCursor::LEN,
+            &[DISTRIBUTION_CURSOR_SEED, mint_account.key.as_ref(), &[args.cursor_bump]],
+        )?;
+        DistributionCursor {
+            account_type: AccountType::DistributionCursor,
+            mint: *mint_account.key,
+            list: *list_account.key,
+            next_index: 0,
+            distributed: 0,
+        }
+    } else {
+        read_distribution_cursor(program_id, cursor_account, mint_account, list_account)?
+    };
+    require!(cursor.next_index < header.capacity, CustomError::DistributionAlreadyComplete);
+
+    // A chunk replayed or sent out of order starts somewhere other than the cursor and fails
+    // instead of skipping recipients or minting to them twice
+    verbose!("checking chunk start: got {} expected {}", args.start_index, cursor.next_index);
+    require!(args.start_index == cursor.next_index, ProgramError::InvalidArgument);
+    let start = cursor.next_index as usize;
+    let end = start + destinations.len();
+    require!(end <= entries.len(), ProgramError::InvalidArgument);
+
+    // Nothing may have been minted outside the distribution since the previous chunk
+    let mint_data = unpack_mint(mint_account)?;
+    require!(
+        mint_data.mint_authority == COption::Some(*mint_authority.key),
+        CustomError::MintAuthorityMismatch
+    );
+    verbose!("checking supply: got {} expected {}", mint_data.supply, cursor.distributed);
+    require!(mint_data.supply == cursor.distributed, CustomError::InvalidMintState);
+
+    // Validate every destination of the chunk before minting anything
+    let chunk = &entries[start..end];
+    for (destination, entry) in destinations.iter().zip(chunk) {
+        require!(*destination.key == entry.destination, ProgramError::InvalidArgument);
+        validate_destination(destination, mint_account, None)?;
+    }
+
+    for (destination, entry) in destinations.iter().zip(chunk) {
+        mint_supply(
+            token_program,
+            mint_account,
+            destination,
+            mint_authority,
+            entry.amount,
+            mint_data.decimals,
+        )?;
+        cursor.distributed = cursor
+            .distributed
+            .checked_add(entry.amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+    cursor.next_index = end as u16;
+    cursor.serialize(&mut &mut cursor_account.data.borrow_mut()[..])?;
+    msg!("Distributed {} of {} recipients", cursor.next_index, header.capacity);
+
+    if cursor.next_index == header.capacity {
+        revoke_mint_authority(token_program, mint_account, mint_authority, cursor.distributed)?;
+    }
+
+    Ok(())
+}
+
+/// Processes the `DistributeToOwners` instruction
+/// Mints the total supply to the associated token accounts of the given wallets, creating them
+/// where needed, then revokes
+pub fn process_distribute_to_owners(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: DistributeToOwnersArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let gate = LaunchGate::parse(program_id, accounts_iter, 1)?;
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+    let system_program = next_account_info(accounts_iter)?;    // System program
+    let ata_program = next_account_info(accounts_iter)?;       // Associated Token Account program
+    let recipients = accounts_iter.as_slice();
+
+    // Catch a client whose account pairs or allocations drifted from its own count first
+    verbose!(
+        "checking recipient count: got {} accounts and {} allocations expected {}",
+        recipients.len() / 2,
+        args.recipients.len(),
+        args.num_recipients
+    );
+    let num_recipients = args.num_recipients as usize;
+    require!(
+        recipients.len() == 2 * num_recipients && args.recipients.len() == num_recipients,
+        CustomError::RecipientCountMismatch
+    );
+
+    validate_authority(mint_authority, payer)?;
+    gate.authorize(mint_authority.key, &[mint_account.key])?;
+    validate_token_program(token_program, mint_account)?;
+    if !mint_account.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let mint_data = validate_mint(mint_account, mint_authority, None, None)?;
+    if recipients.len() != 2 * args.recipients.len() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    // The amounts must add up to exactly the total supply for the mint's decimals
+    let total_supply_with_decimals = total_supply_with_decimals(mint_data.decimals)?;
+    let mut distributed: u64 = 0;
+    for allocation in &args.recipients {
+        if allocation.amount == 0 {
+            return Err(CustomError::ZeroAmountRecipient.into());
+        }
+        distributed = distributed
+            .checked_add(allocation.amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+    if distributed != total_supply_with_decimals {
+        return Err(CustomError::DistributionTotalMismatch.into());
+    }
+
+    // Every token account must be the wallet's derived associated account, and appear only once
+    for (pair, allocation) in recipients.chunks_exact(2).zip(&args.recipients) {
+        let (wallet, token_account) = (&pair[0], &pair[1]);
+        if *wallet.key != allocation.owner {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let expected =
+            find_associated_token_address(wallet.key, mint_account.key, token_program.key);
+        verbose!(
+            "checking associated token account: got {} expected {}",
+            token_account.key,
+            expected
+        );
+        if *token_account.key != expected {
+            return Err(ProgramError::InvalidArgument);
+        }
+        ensure_distinct(&[mint_account, mint_authority, token_account])?;
+    }
+    ensure_distinct_destinations(recipients.iter().skip(1).step_by(2))?;
+    let missing = recipients
+        .chunks_exact(2)
+        .filter(|pair| pair[1].data_is_empty())
+        .count();
+    let new_account_lens = vec![associated_account_len(token_program.key)?; missing];
+    require_rent_funds(payer, &new_account_lens)?;
+    gate.charge(payer, 1, &new_account_lens)?;
+
+    // Create any missing accounts and validate every destination before minting anything
+    for pair in recipients.chunks_exact(2) {
+        let (wallet, token_account) = (&pair[0], &pair[1]);
+        create_associated_token_account(
+            payer,
+            wallet,
+            token_account,
+            mint_account,
+            system_program,
+            token_program,
+            ata_program,
+            true,
+        )?;
+        validate_destination(token_account, mint_account, Some(wallet.key))?;
+    }
+
+    for (pair, allocation) in recipients.chunks_exact(2).zip(&args.recipients) {
+        mint_supply(
+            token_program,
+            mint_account,
+            &pair[1],
+            mint_authority,
+            allocation.amount,
+            mint_data.decimals,
+        )?;
+    }
+
+    revoke_mint_authority(token_program, mint_account, mint_authority, total_supply_with_decimals)
+}
+
+/// Processes the `InitializeWithAllocationPlan` instruction
+/// Mints each bucket's share of the total supply to its destination, then revokes
+pub fn process_initialize_with_allocation_plan(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    plan: AllocationPlan,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let gate = LaunchGate::parse(program_id, accounts_iter, 1)?;
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // SPL Token program
+    let destinations = accounts_iter.as_slice();
+
+    if destinations.len() != plan.buckets.len() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let total_bps = plan.buckets.iter().map(|bucket| bucket.bps as u32).sum::<u32>();
+    if total_bps != MAX_BASIS_POINTS as u32 {
+        msg!("Allocation plan sums to {} basis points, expected {}", total_bps, MAX_BASIS_POINTS);
+        return Err(CustomError::AllocationPlanSumMismatch.into());
+    }
+    for destination in destinations {
+        ensure_distinct(&[mint_account, mint_authority, destination])?;
+    }
+    ensure_distinct_destinations(destinations.iter())?;
+
+    validate_authority(mint_authority, payer)?;
+    gate.authorize(mint_authority.key, &[mint_account.key])?;
+    gate.charge(payer, 1, &[])?;
+    validate_token_program(token_program, mint_account)?;
+    if !mint_account.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let mint_data = validate_mint(mint_account, mint_authority, None, None)?;
+    for destination in destinations {
+        validate_destination(destination, mint_account, None)?;
+    }
+
+    // Shares are rounded down, and the last bucket takes the remainder so the plan always adds
+    // up to the exact total supply
+    let total_supply_with_decimals = total_supply_with_decimals(mint_data.decimals)?;
+    let mut remaining = total_supply_with_decimals;
+    for (index, (destination, bucket)) in destinations.iter().zip(&plan.buckets).enumerate() {
+        let amount = if index + 1 == plan.buckets.len() {
+            remaining
+        } else {
+            (total_supply_with_decimals as u128)
+                .checked_mul(bucket.bps as u128)
+                .map(|v| v / MAX_BASIS_POINTS as u128)
+                .and_then(|v| u64::try_from(v).ok())
+                .ok_or(ProgramError::ArithmeticOverflow)?
+        };
+        remaining = remaining.checked_sub(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+
+        msg!(
+            "Allocation bucket {}: {} bps",
+            String::from_utf8_lossy(&bucket.label).trim_end_matches('\0'),
+            bucket.bps
+        );
+        mint_supply(
+            token_program,
+            mint_account,
+            destination,
+            mint_authority,
+            amount,
+            mint_data.decimals,
+        )?;
+    }
+
+    revoke_mint_authority(token_program, mint_account, mint_authority, total_supply_with_decimals)
+}
+
+/// Processes the `InitializeGroup` instruction
+/// Initializes the token-group extension on a Token-2022 mint that points at itself
+pub fn process_initialize_group(
+    accounts: &[AccountInfo],
+    args: InitializeGroupArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let group_mint = next_account_info(accounts_iter)?;        // The group mint account
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // Token-2022 program
+    let system_program = next_account_info(accounts_iter)?;    // System program
+
+    validate_authority(mint_authority, payer)?;
+    validate_token_2022_program(token_program, group_mint)?;
+    if !group_mint.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    validate_mint(group_mint, mint_authority, None, None)?;
+
+    // Token-2022 stores the group in the mint itself, so the pointer has to reference it
+    let new_len = {
+        let data = group_mint.data.borrow();
+        let mint_state = StateWithExtensions::<Mint>::unpack(&data)?;
+        let pointer = mint_state.get_extension::<GroupPointer>()?;
+        if Option::<Pubkey>::from(pointer.group_address) != Some(*group_mint.key) {
+            msg!("Group pointer of {} does not point at the mint itself", group_mint.key);
+            return Err(ProgramError::InvalidAccountData);
+        }
+        mint_state.try_get_new_account_len::<TokenGroup>()?
+    };
+    fund_account_growth(payer, group_mint, system_program, new_len)?;
+
+    solana_program::program::invoke(
+        &initialize_group(
+            token_program.key,
+            group_mint.key,
+            group_mint.key,
+            mint_authority.key,
+            Some(*mint_authority.key),
+            args.max_size,
+        ),
+        &[group_mint.clone(), mint_authority.clone(), token_program.clone()],
+    )
+}
+
+/// Processes the `WriteAdditionalMetadata` instruction
+/// Sets custom fields in the on-mint metadata of a Token-2022 mint that isn't finalized yet
+pub fn process_write_additional_metadata(
+    accounts: &[AccountInfo],
+    args: AdditionalMetadataArgs,
+) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let mint_account = next_account_info(accounts_iter)?;      // The token mint account
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // Token-2022 program
+    let system_program = next_account_info(accounts_iter)?;    // System program
+
+    validate_authority(mint_authority, payer)?;
+    validate_token_2022_program(token_program, mint_account)?;
+    require!(mint_account.is_writable, ProgramError::InvalidAccountData);
+    validate_mint(mint_account, mint_authority, None, None)?;
+
+    // Apply the fields to a copy first, so the final size is known before any CPI
+    let new_len = {
+        let data = mint_account.data.borrow();
+        let mint_state = StateWithExtensions::<Mint>::unpack(&data)?;
+        let pointer = mint_state.get_extension::<MetadataPointer>()?;
+        if Option::<Pubkey>::from(pointer.metadata_address) != Some(*mint_account.key) {
+            msg!("Metadata pointer of {} does not point at the mint itself", mint_account.key);
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut metadata = mint_state.get_variable_len_extension::<TokenMetadata>()?;
+        require!(
+            Option::<Pubkey>::from(metadata.update_authority) == Some(*mint_authority.key),
+            CustomError::InvalidAuthorityAccount
+        );
+        for (key, value) in &args.fields {
+            metadata.update(Field::Key(key.clone()), value.clone());
+        }
+        mint_state.try_get_new_account_len_for_variable_len_extension(&metadata)?
+    };
+    let growth = new_len.saturating_sub(mint_account.data_len());
+    verbose!(
+        "checking metadata growth: got {} bytes expected at most {}",
+        growth,
+        MAX_PERMITTED_DATA_INCREASE
+    );
+    if growth > MAX_PERMITTED_DATA_INCREASE {
+        msg!("Metadata would grow the mint by {} bytes", growth);
+        return Err(CustomError::MetadataTooLarge.into());
+    }
+    fund_account_growth(payer, mint_account, system_program, new_len)?;
+
+    for (key, value) in args.fields {
+        solana_program::program::invoke(
+            &update_field(
+                token_program.key,
+                mint_account.key,
+                mint_authority.key,
+                Field::Key(key),
+                value,
+            ),
+            &[mint_account.clone(), mint_authority.clone(), token_program.clone()],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Processes the `AddGroupMember` instruction
+/// Registers a not yet launched Token-2022 mint as the next member of a group
+pub fn process_add_group_member(accounts: &[AccountInfo]) -> ProgramResult {
+    // Extract required accounts from the instruction
+    let accounts_iter = &mut accounts.iter();
+    let member_mint = next_account_info(accounts_iter)?;       // The member mint account
+    let group_mint = next_account_info(accounts_iter)?;        // The group mint account
+    let mint_authority = next_account_info(accounts_iter)?;    // The mint authority (must sign)
+    let payer = next_account_info(accounts_iter)?;             // The transaction payer (must sign)
+    let token_program = next_account_info(accounts_iter)?;     // Token-2022 program
+    let system_program = next_account_info(accounts_iter)?;    // System program
+
+    validate_authority(mint_authority, payer)?;
+    validate_token_2022_program(token_program, member_mint)?;
+    validate_token_2022_program(token_program, group_mint)?;
+    if !member_mint.is_writable || !group_mint.is_writable || member_mint.key == group_mint.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    validate_mint(member_mint, mint_authority, None, None)?;
+
+    // The group must be controlled by the same authority and still have room for a member
+    {
+        let data = group_mint.data.borrow();
+        let group_state = StateWithExtensions::<Mint>::unpack(&data)?;
+        let group = group_state.get_extension::<TokenGroup>()?;
+        if Option::<Pubkey>::from(group.update_authority) != Some(*mint_authority.key) {
+            return Err(CustomError::MintAuthorityMismatch.into());
+        }
+        if u32::from(group.size) >= u32::from(group.max_size) {
+            msg!("Group {} is full at {} members", group_mint.key, u32::from(group.max_size));
+            return Err(CustomError::GroupFull.into());
+        }
+    }
+
+    let new_len = {
+        let data = member_mint.data.borrow();
+        let mint_state = StateWithExtensions::<Mint>::unpack(&data)?;
+        let pointer = mint_state.get_extension::<GroupMemberPointer>()?;
+        if Option::<Pubkey>::from(pointer.member_address) != Some(*member_mint.key) {
+            msg!("Group member pointer of {} does not point at the mint itself", member_mint.key);
+            return Err(ProgramError::InvalidAccountData);
+        }
+        mint_state.try_get_new_account_len::<TokenGroupMember>()?
+    };
+    fund_account_growth(payer, member_mint, system_program, new_len)?;
+
+    solana_program::program::invoke(
+        &initialize_member(
+            token_program.key,
+            member_mint.key,
+            member_mint.key,
+            mint_authority.key,
+            group_mint.key,
+            mint_authority.key,
+        ),
+        &[
+            member_mint.clone(),
+            mint_authority.clone(),
+            group_mint.clone(),
+            token_program.clone(),
+        ],
+    )
+}
+
+/// Processes the `VerifyGroupMembership` instruction
+/// Lets wallets and marketplaces confirm collection membership without parsing extensions
+pub fn process_verify_group_membership(accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let member_mint = next_account_info(accounts_iter)?;       // The member mint account
+    let group_mint = next_account_info(accounts_iter)?;        // The expected group mint account
+
+    if *member_mint.owner != spl_token_2022::id() {
+        return Err(CustomError::NotGroupMember.into());
+    }
+    check_account_len(member_mint, Mint::LEN, CustomError::MintWrongSize)?;
+    let data = member_mint.data.borrow();
+    let mint_state = StateWithExtensions::<Mint>::unpack(&data)?;
+
+    // Membership is only recognized when recorded in the mint itself, as `AddGroupMember` does
+    let points_at_self = mint_state
+        .get_extension::<GroupMemberPointer>()
+        .is_ok_and(|pointer| {
+            Option::<Pubkey>::from(pointer.member_address) == Some(*member_mint.key)
+        });
+    let in_group = mint_state
+        .get_extension::<TokenGroupMember>()
+        .is_ok_and(|member| member.mint == *member_mint.key && member.group == *group_mint.key);
+    if !(points_at_self && in_group) {
+        msg!("Mint {} is not a member of group {}", member_mint.key, group_mint.key);
+        return Err(CustomError::NotGroupMember.into());
+    }
+
+    Ok(())
+}
+
+// Calculates the total supply with decimals (500M * 10^decimals)
+fn total_supply_with_decimals(decimals: u8) -> Result<u64, ProgramError> {
+    require!(TOTAL_SUPPLY >= MIN_SUPPLY, CustomError::SupplyBelowMinimum);
+    supply_with_decimals(TOTAL_SUPPLY, decimals).ok_or(ProgramError::InvalidArgument)
+}
+
+// Converts a supply in whole tokens to base units, `None` if it doesn't fit in a u64
+fn supply_with_decimals(ui_supply: u64, decimals: u8) -> Option<u64> {
+    10u64
+        .checked_pow(decimals as u32)
+        .and_then(|factor| ui_supply.checked_mul(factor))
+}
+
+// Rejects aliased accounts up front, since unpacking or CPIs on the same account through two
+// different roles could otherwise hit conflicting borrows
+fn ensure_distinct(accounts: &[&AccountInfo]) -> ProgramResult {
+    for (index, account) in accounts.iter().enumerate() {
+        if accounts[index + 1..].iter().any(|other| other.key == account.key) {
+            msg!("Account {} is passed in more than one role", account.key);
+            return Err(CustomError::AliasedAccounts.into());
+        }
+    }
+    Ok(())
+}
+
+// Rejects a mint or token account passed under the key of one of the instruction's program or
+// sysvar accounts, which points at a malformed account list
+fn ensure_not_program_accounts(
+    accounts: &[(&str, &AccountInfo)],
+    programs: &[(&str, &AccountInfo)],
+) -> ProgramResult {
+    for (role, account) in accounts {
+        let alias = programs.iter().find(|(_, program)| program.key == account.key);
+        if let Some((program_role, _)) = alias {
+            msg!("The {} {} is also passed as the {}", role, account.key, program_role);
+            return Err(CustomError::AliasedAccounts.into());
+        }
+    }
+    Ok(())
+}
+
+// Rejects a destination listed twice, which would receive several shares of the distribution
+// Sorting a copy of the keys is cheap at the capped recipient counts.
+fn ensure_distinct_destinations<'a, 'b: 'a>(
+    destinations: impl Iterator<Item = &'a AccountInfo<'b>>,
+) -> ProgramResult {
+    let mut keys: Vec<&Pubkey> = destinations.map(|destination| destination.key).collect();
+    keys.sort_unstable();
+    if let Some(pair) = keys.windows(2).find(|pair| pair[0] == pair[1]) {
+        msg!("Destination {} appears more than once", pair[0]);
+        return Err(CustomError::DuplicateDestination.into());
+    }
+    Ok(())
+}
+
+// Reads and validates the program's launcher allowlist
+fn read_launcher_allowlist(
+    program_id: &Pubkey,
+    allowlist_account: &AccountInfo,
+) -> Result<LauncherAllowlist, ProgramError> {
+    require!(allowlist_account.owner == program_id, ProgramError::IllegalOwner);
+    let allowlist = LauncherAllowlist::deserialize(&mut &allowlist_account.data.borrow()[..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    require!(
+        allowlist.account_type == AccountType::LauncherAllowlist,
+        ProgramError::InvalidAccountData
+    );
+    Ok(allowlist)
+}
+
+// Reads the program's config, checking it is a config account of this program
+// Only `SetConfigAdmin` creates accounts of this type, at the single config PDA, so the owner and
+// tag checks pin the account without re-deriving its address.
+fn read_program_config(
+    program_id: &Pubkey,
+    config_account: &AccountInfo,
+) -> Result<ProgramConfig, ProgramError> {
+    require!(config_account.owner == program_id, ProgramError::IllegalOwner);
+    let config = ProgramConfig::try_from_slice(&config_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    require!(config.account_type == AccountType::ProgramConfig, ProgramError::InvalidAccountData);
+    Ok(config)
+}
+
+// Checks that `admin` signed and is the admin stored in the program's config
+fn verify_config_admin(
+    program_id: &Pubkey,
+    config_account: &AccountInfo,
+    admin: &AccountInfo,
+) -> ProgramResult {
+    require!(admin.is_signer, ProgramError::MissingRequiredSignature);
+    let config = read_program_config(program_id, config_account)?;
+    verbose!("checking admin: got {} expected {}", admin.key, config.admin);
+    require!(*admin.key == config.admin, CustomError::AdminMismatch);
+    Ok(())
+}
+
+// Program-wide launch policy of the config PDA, with the accounts it calls for, parsed off the
+// front of every launching instruction
+struct LaunchGate<'a, 'b> {
+    program_id: &'b Pubkey,
+    settings: ConfigSettings,
+    allowlist: Option<&'b AccountInfo<'a>>,
+    fee_accounts: Option<(&'b AccountInfo<'a>, &'b AccountInfo<'a>)>,
+    oracle_approvals: Vec<&'b AccountInfo<'a>>,
+}
+
+impl<'a, 'b> LaunchGate<'a, 'b> {
+    // Reads the config PDA, then each account its settings call for, for `launches` mints
+    // Without a config there is nothing to enforce, but the empty account still has to be the
+    // config PDA, or any empty account would let a launch skip the policy.
+    fn parse(
+        program_id: &'b Pubkey,
+        accounts_iter: &mut slice::Iter<'b, AccountInfo<'a>>,
+        launches: usize,
+    ) -> Result<Self, ProgramError> {
+        let config_account = next_account_info(accounts_iter)?;    // The config PDA
+        let settings = if config_account.owner == program_id {
+            read_program_config(program_id, config_account)?.settings
+        } else {
+            let (expected, _) = find_config_address(program_id);
+            require!(*config_account.key == expected, ProgramError::InvalidSeeds);
+            ConfigSettings::default()
+        };
+        let allowlist = match settings.launcher_allowlist_enabled {
+            true => Some(next_account_info(accounts_iter)?),       // The launcher allowlist PDA
+            false => None,
+        };
+        let fee_accounts = match settings.charges_launch_fee() {
+            true => Some((
+                next_account_info(accounts_iter)?,                 // The fee treasury
+                next_account_info(accounts_iter)?,                 // System program
+            )),
+            false => None,
+        };
+        let mut oracle_approvals = Vec::new();
+        if settings.requires_oracle_approval() {
+            for _ in 0..launches {
+                oracle_approvals.push(next_account_info(accounts_iter)?); // A mint's approval
+            }
+        }
+
+        Ok(Self { program_id, settings, allowlist, fee_accounts, oracle_approvals })
+    }
+
+    // Checks that the policy lets `launcher` launch `mints`, in the order the gate was parsed for
+    // A failed launch reverts entirely, so an unapproved launch can simply be retried later.
+    fn authorize(&self, launcher: &Pubkey, mints: &[&Pubkey]) -> ProgramResult {
+        if let Some(allowlist) = self.allowlist {
+            verify_launcher_allowlisted(self.program_id, allowlist, launcher)?;
+        }
+        for (approval, mint) in self.oracle_approvals.iter().zip(mints) {
+            verify_oracle_approval(&self.settings.oracle_program, approval, mint)?;
+        }
+        Ok(())
+    }
+
+    // Charges the payer the configured fee for each of `launches` mints, keeping the rent of the
+    // accounts of the given sizes the launch still creates
+    fn charge(
+        &self,
+        payer: &AccountInfo<'a>,
+        launches: u64,
+        account_lens: &[usize],
+    ) -> ProgramResult {
+        if let Some((treasury, system_program)) = self.fee_accounts {
+            require!(*treasury.key == self.settings.fee_treasury, ProgramError::InvalidArgument);
+            let fee = self
+                .settings
+                .launch_fee_lamports
+                .checked_mul(launches)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            pay_launch_fee(payer, treasury, system_program, fee, account_lens)?;
+        }
+        Ok(())
+    }
+}
+
+// Checks that `launcher` is on the launcher allowlist
+// Only `AddLauncher` creates accounts of this type, at the single allowlist PDA, so the owner and
+// tag checks pin the account without re-deriving its address.
+fn verify_launcher_allowlisted(
+    program_id: &Pubkey,
+    allowlist_account: &AccountInfo,
+    launcher: &Pubkey,
+) -> ProgramResult {
+    let allowlist = read_launcher_allowlist(program_id, allowlist_account)?;
+    if !allowlist.launchers.contains(launcher) {
+        msg!("Launcher {} is not allowlisted", launcher);
+        return Err(CustomError::LauncherNotAllowlisted.into());
+    }
+    Ok(())
+}
+
+// Checks that the verification oracle approved launching `mint`
+fn verify_oracle_approval(
+    oracle_program: &Pubkey,
+    oracle_approval: &AccountInfo,
+    mint: &Pubkey,
+) -> ProgramResult {
+    require!(oracle_approval.owner == oracle_program, ProgramError::IllegalOwner);
+    let data = oracle_approval.data.borrow();
+    let approval = data
+        .get(..OracleApproval::LEN)
+        .and_then(|bytes| OracleApproval::try_from_slice(bytes).ok())
+        .ok_or(ProgramError::InvalidAccountData)?;
+    require!(approval.mint == *mint, ProgramError::InvalidAccountData);
+    verbose!("checking oracle approval: got {} expected true", approval.approved);
+    if !approval.approved {
+        msg!("Launch of {} is not approved by the oracle yet", mint);
+        return Err(CustomError::OracleNotApproved.into());
+    }
+    Ok(())
+}
+
+// Checks that this program can no longer be upgraded, so the launch logic is final
+fn verify_program_immutable(program_id: &Pubkey, program_data: &AccountInfo) -> ProgramResult {
+    let upgrade_authority = read_upgrade_authority(program_id, program_data)?;
+    verbose!("checking upgrade authority: got {:?} expected None", upgrade_authority);
+    if let Some(upgrade_authority) = upgrade_authority {
+        msg!("Program {} can still be upgraded by {}", program_id, upgrade_authority);
+        return Err(CustomError::ProgramStillUpgradeable.into());
+    }
+    Ok(())
+}
+
+// Reads this program's upgrade authority from its ProgramData account, `None` once immutable
+// Reads straight from the ProgramData layout: a u32 state tag, the deployment slot and a
+// Borsh-like optional authority key.
+fn read_upgrade_authority(
+    program_id: &Pubkey,
+    program_data: &AccountInfo,
+) -> Result<Option<Pubkey>, ProgramError> {
+    let (expected, _) = find_program_data_address(program_id);
+    require!(*program_data.key == expected, ProgramError::InvalidSeeds);
+    require!(*program_data.owner == bpf_loader_upgradeable::id(), ProgramError::IllegalOwner);
+
+    let data = program_data.data.borrow();
+    let metadata = data
+        .get(..UpgradeableLoaderState::size_of_programdata_metadata())
+        .ok_or(ProgramError::InvalidAccountData)?;
+    // `UpgradeableLoaderState::ProgramData` is the fourth variant of the loader state
+    require!(metadata[..4] == 3u32.to_le_bytes(), ProgramError::InvalidAccountData);
+    match metadata[12] {
+        0 => Ok(None),
+        1 => Ok(Some(
+            Pubkey::try_from(&metadata[13..45]).map_err(|_| ProgramError::InvalidAccountData)?,
+        )),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+// Checks that a protocol destination's owner is an allowlisted program, or an account such as a
+// pool or escrow state that one of them owns
+fn validate_protocol_owner(destination_owner: &AccountInfo) -> ProgramResult {
+    verbose!(
+        "checking destination owner program: got {} owned by {}",
+        destination_owner.key,
+        destination_owner.owner
+    );
+    if !ALLOWED_DESTINATION_PROGRAMS.contains(destination_owner.key)
+        && !ALLOWED_DESTINATION_PROGRAMS.contains(destination_owner.owner)
+    {
+        msg!("Destination owner {} is not an allowed protocol account", destination_owner.key);
+        return Err(CustomError::DestinationOwnerNotAllowed.into());
+    }
+    Ok(())
+}
+
+// Validates the signing authority and payer
+fn validate_authority(mint_authority: &AccountInfo, payer: &AccountInfo) -> ProgramResult {
+    verbose!("checking authority: got {} expected payer {}", mint_authority.key, payer.key);
+    verbose!(
+        "checking signers: got authority {} payer {} expected true true",
+        mint_authority.is_signer,
+        payer.is_signer
+    );
+
+    // Reject well-known program and sysvar ids, which can never legitimately act as the authority
+    require!(
+        !is_reserved_authority_key(mint_authority.key) && !is_reserved_authority_key(payer.key),
+        CustomError::InvalidAuthorityAccount
+    );
+
+    // Verify that required signers have signed the transaction, each with its own error so the
+    // missing signature is obvious even if the two accounts stop being required to match
+    require!(mint_authority.is_signer, CustomError::AuthorityNotSigner);
+    require!(payer.is_signer, CustomError::PayerNotSigner);
+
+    // Ensure mint authority and payer are the same account for security
+    require!(mint_authority.key == payer.key, CustomError::MintAuthorityMismatch);
+
+    Ok(())
+}
+
+// Validates the mint account before minting and returns its unpacked state
+// `decimals` is the exact value the mint must use, or `None` to accept any allowed value, and
+// `freeze_authority` is the freeze authority the mint is expected to keep, if any
+fn validate_mint(
+    mint_account: &AccountInfo,
+    mint_authority: &AccountInfo,
+    decimals: Option<u8>,
+    freeze_authority: Option<&Pubkey>,
+) -> Result<Mint, ProgramError> {
+    // The mint must be owned by a supported token program, initialized with correct decimals,
+    // zero supply, the expected freeze authority, and the mint authority must match the
+    // provided authority
+    // Wrapped SOL has no mint authority to revoke, reject it before anything confusing happens
+    require!(
+        *mint_account.key != spl_token::native_mint::id()
+            && *mint_account.key != spl_token_2022::native_mint::id(),
+        CustomError::CannotUseNativeMint
+    );
+    let mint_data = unpack_mint(mint_account)?;
+    verbose!("checking mint initialized: got {} expected true", mint_data.is_initialized);
+    match decimals {
+        Some(decimals) => {
+            verbose!("checking decimals: got {} expected {}", mint_data.decimals, decimals)
+        }
+        None => verbose!(
+            "checking decimals: got {} expected one of {:?}",
+            mint_data.decimals,
+            ALLOWED_DECIMALS
+        ),
+    }
+    verbose!("checking supply: got {} expected 0", mint_data.supply);
+    verbose!(
+        "checking freeze authority: got {:?} expected {:?}",
+        mint_data.freeze_authority,
+        freeze_authority
+    );
+    verbose!(
+        "checking mint authority: got {:?} expected {}",
+        mint_data.mint_authority,
+        mint_authority.key
+    );
+    // A revoked authority with tokens in circulation is a token that already launched
+    require!(
+        mint_data.mint_authority.is_some() || mint_data.supply == 0,
+        CustomError::MintAlreadyFinalized
+    );
+    // A mint created with a freeze authority can only go through a launch that keeps it
+    if let (COption::Some(current), None) = (mint_data.freeze_authority, freeze_authority) {
+        msg!("Mint has freeze authority {} but this launch doesn't keep one", current);
+        return Err(CustomError::FreezeAuthorityConflict.into());
+    }
+    // Externally created mints included: the supply is never minted on top of existing tokens
+    if mint_data.supply != 0 {
+        msg!("Mint already has a supply of {} base units", mint_data.supply);
+        return Err(CustomError::MintSupplyNotZero.into());
+    }
+    let decimals_ok = match decimals {
+        Some(decimals) => mint_data.decimals == decimals,
+        None => ALLOWED_DECIMALS.contains(&mint_data.decimals),
+    };
+    require!(
+        mint_data.is_initialized
+            && decimals_ok
+            && mint_data.supply == 0
+            && mint_data.freeze_authority == COption::from(freeze_authority.copied())
+            && mint_data.mint_authority == COption::Some(*mint_authority.key),
+        CustomError::InvalidMintState
+    );
+
+    Ok(mint_data)
+}
+
+// Validates a destination token account before minting
+// `expected_owner` is the wallet the account must belong to, or `None` for third-party recipients
+fn validate_destination(
+    token_account: &AccountInfo,
+    mint_account: &AccountInfo,
+    expected_owner: Option<&Pubkey>,
+) -> Result<TokenAccount, ProgramError> {
+    // Verify that the token account belongs to the same token program as the mint and is writable
+    verbose!(
+        "checking destination program: got {} expected {}",
+        token_account.owner,
+        mint_account.owner
+    );
+    require!(token_account.owner == mint_account.owner, ProgramError::IllegalOwner);
+    require!(token_account.is_writable, ProgramError::InvalidAccountData);
+    check_account_len(token_account, TokenAccount::LEN, CustomError::TokenAccountWrongSize)?;
+
+    // Token-2022 accounts may carry extensions, only mint into ones we know how to handle
+    let data = token_account.data.borrow();
+    let token_state = StateWithExtensions::<TokenAccount>::unpack(&data)?;
+    for extension in token_state.get_extension_types()? {
+        if !SUPPORTED_ACCOUNT_EXTENSIONS.contains(&extension) {
+            msg!("Unsupported token account extension: {:?}", extension);
+            return Err(CustomError::UnsupportedExtension.into());
+        }
+    }
+
+    // The token account must be owned by the expected wallet (if any), match the mint,
+    // have zero balance, and no delegates or close authority
+    let token_data = token_state.base;
+    verbose!(
+        "checking destination owner: got {} expected {:?}",
+        token_data.owner,
+        expected_owner
+    );
+    verbose!("checking destination mint: got {} expected {}", token_data.mint, mint_account.key);
+    verbose!("checking destination balance: got {} expected 0", token_data.amount);
+    if let Some(expected_owner) = expected_owner {
+        require!(token_data.owner == *expected_owner, CustomError::TokenAccountOwnerMismatch);
+    }
+    // Wrapped SOL accounts report lamports above their rent-exempt reserve as `amount`, so their
+    // balance never means "holds no tokens" the way it does for a fixed-supply mint. They are
+    // always rejected, since a launched mint can never be the native mint.
+    if token_data.is_native() {
+        msg!(
+            "Token account {} is a native account with a {} lamport reserve",
+            token_account.key,
+            token_data.is_native.unwrap_or(0)
+        );
+        return Err(CustomError::TokenAccountWrongMint.into());
+    }
+    if token_data.mint != *mint_account.key {
+        msg!(
+            "Token account {} belongs to mint {}, expected mint {}",
+            token_account.key,
+            token_data.mint,
+            mint_account.key
+        );
+        return Err(CustomError::TokenAccountWrongMint.into());
+    }
+    require!(
+        token_data.amount == 0
+            && token_data.delegate.is_none()
+            && token_data.close_authority.is_none(),
+        CustomError::TokenAccountNotEmpty
+    );
+
+    Ok(token_data)
+}
+
+// Mints `amount` base units into the destination token account
+fn mint_supply<'a>(
+    token_program: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    mint_authority: &AccountInfo<'a>,
+    amount: u64,
+    decimals: u8,
+) -> ProgramResult {
+    mint_supply_signed(
+        token_program,
+        mint_account,
+        destination,
+        mint_authority,
+        amount,
+        decimals,
+        &[],
+    )
+}
+
+// Same as `mint_supply`, signing for a program-derived mint authority with `signer_seeds`
+fn mint_supply_signed<'a>(
+    token_program: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    mint_authority: &AccountInfo<'a>,
+    amount: u64,
+    decimals: u8,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    // Launches start from a zero supply, but don't rely on the token program to catch an
+    // overflow should a mode ever mint on top of an existing supply
+    let mint_data = unpack_mint(mint_account)?;
+    require!(mint_data.supply.checked_add(amount).is_some(), CustomError::SupplyOverflow);
+
+    // Cross-account guard at the last moment, whatever the caller validated before: the
+    // destination must point at this mint, and the passed authority must control that mint
+    let destination_data = unpack_token_account(destination)?;
+    verbose!(
+        "checking mint before minting: got {} for destination mint {} with authority {:?}",
+        mint_account.key,
+        destination_data.mint,
+        mint_data.mint_authority
+    );
+    if destination_data.mint != *mint_account.key {
+        msg!(
+            "Token account {} belongs to mint {}, not {}",
+            destination.key,
+            destination_data.mint,
+            mint_account.key
+        );
+        return Err(CustomError::TokenAccountWrongMint.into());
+    }
+    if mint_data.mint_authority != COption::Some(*mint_authority.key) {
+        msg!("Mint {} isn't controlled by {}", mint_account.key, mint_authority.key);
+        return Err(CustomError::MintAuthorityMismatch.into());
+    }
+
+    // Capture the balance before the CPI so the delta can be checked afterwards
+    let pre_amount = destination_data.amount;
+
+    solana_program::program::invoke_signed(
+        &mint_to(
+            token_program.key,
+            mint_account.key,
+            destination.key,
+            mint_authority.key,
+            &[],
+            amount,
+        )?,
+        &[
+            mint_account.clone(),
+            destination.clone(),
+            mint_authority.clone(),
+            token_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+
+    // Belt-and-suspenders: the destination must have received exactly the minted amount
+    let post_amount = unpack_token_account(destination)?.amount;
+    if post_amount.checked_sub(pre_amount) != Some(amount) {
+        return Err(CustomError::UnexpectedBalanceDelta.into());
+    }
+
+    msg!(
+        "Minted {} base units ({} tokens) to {}",
+        amount,
+        format_ui_amount(amount, decimals),
+        destination.key
+    );
+
+    Ok(())
+}
+
+// Permanently revokes the mint authority once the supply is exactly `expected_supply`, and
+// verifies the revocation took effect
+fn revoke_mint_authority<'a>(
+    token_program: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    mint_authority: &AccountInfo<'a>,
+    expected_supply: u64,
+) -> ProgramResult {
+    revoke_mint_authority_signed(token_program, mint_account, mint_authority, expected_supply, &[])
+}
+
+// Same as `revoke_mint_authority`, signing for a program-derived mint authority
+fn revoke_mint_authority_signed<'a>(
+    token_program: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    mint_authority: &AccountInfo<'a>,
+    expected_supply: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    invoke_revocation(token_program, mint_account, mint_authority, expected_supply, signer_seeds)?;
+
+    // Final verification - ensure mint authority was successfully revoked
+    // This is a critical security check to confirm the token supply is now permanently fixed
+    let final_mint_data = unpack_mint(mint_account)?;
+    require!(final_mint_data.mint_authority.is_none(), CustomError::MintAuthorityNotRevoked);
+
+    msg!(
+        "{}",
+        format_result_line(mint_account.key, final_mint_data.supply, final_mint_data.decimals)
+    );
+
+    Ok(())
+}
+
+// Same as `revoke_mint_authority` without re-reading the mint afterwards, for launches that set
+// `CAPABILITY_SKIP_FINAL_VERIFICATION`. `decimals` is only used for the result line.
+fn revoke_mint_authority_unverified<'a>(
+    token_program: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    mint_authority: &AccountInfo<'a>,
+    supply: u64,
+    decimals: u8,
+) -> ProgramResult {
+    invoke_revocation(token_program, mint_account, mint_authority, supply, &[])?;
+    msg!("Warning: final mint authority verification skipped at the caller's request");
+    msg!("{}", format_result_line(mint_account.key, supply, decimals));
+
+    Ok(())
+}
+
+// The invariant every launch relies on: the whole supply exists before the mint authority goes
+// Every revocation funnels through `invoke_revocation`, so no launch mode can fix a short supply.
+fn assert_fully_minted(mint_data: &Mint, expected: u64) -> ProgramResult {
+    verbose!("checking supply before revoke: got {} expected {}", mint_data.supply, expected);
+    if mint_data.supply != expected {
+        msg!("Refusing to revoke with {} of {} base units minted", mint_data.supply, expected);
+        return Err(CustomError::IncompleteSupplyBeforeRevoke.into());
+    }
+    Ok(())
+}
+
+// Invokes the token program to set the mint authority to none, once the supply is complete
+fn invoke_revocation<'a>(
+    token_program: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    mint_authority: &AccountInfo<'a>,
+    expected_supply: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    assert_fully_minted(&unpack_mint(mint_account)?, expected_supply)?;
+
+    // This ensures no additional tokens can ever be minted, making the supply truly fixed
+    // A failure surfacing here is kept apart from the verification that follows it. An error
+    // inside the token program aborts the transaction before we get control back, so this
+    // covers failures of building and dispatching the CPI.
+    set_authority(
+        token_program.key,
+        mint_account.key,
+        None,  // Set authority to None (revoked)
+        AuthorityType::MintTokens,
+        mint_authority.key,
+        &[mint_authority.key],
+    )
+    .and_then(|instruction| {
+        solana_program::program::invoke_signed(
+            &instruction,
+            &[
+                mint_account.clone(),
+                mint_authority.clone(),
+                token_program.clone(),
+            ],
+            signer_seeds,
+        )
+    })
+    .map_err(|e| {
+        msg!("Revocation CPI failed: {}", e);
+        CustomError::RevocationCpiFailed.into()
+    })
+}
+
+// Verifies the token program is SPL Token or Token-2022 and is the program that owns the mint
+fn validate_token_program(
+    token_program: &AccountInfo,
+    mint_account: &AccountInfo,
+) -> ProgramResult {
+    require!(
+        is_token_program(token_program.key) && token_program.key == mint_account.owner,
+        ProgramError::IncorrectProgramId
+    );
+    require_executable(&[token_program])
+}
+
+// Rejects a data account passed under a program's key, which only a custom validator could
+// set up, before it is invoked
+fn require_executable(programs: &[&AccountInfo]) -> ProgramResult {
+    for program in programs {
+        if !program.executable {
+            msg!("Program account {} is not executable", program.key);
+            return Err(ProgramError::InvalidAccountData);
+        }
+    }
+    Ok(())
+}
+
+// Tops up an account so it stays rent-exempt once a CPI grows its data to `new_len`
+fn fund_account_growth<'a>(
+    payer: &AccountInfo<'a>,
+    account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    new_len: usize,
+) -> ProgramResult {
+    if *system_program.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    require_executable(&[system_program])?;
+    let shortfall = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if shortfall == 0 {
+        return Ok(());
+    }
+    solana_program::program::invoke(
+        &system_instruction::transfer(payer.key, account.key, shortfall),
+        &[payer.clone(), account.clone(), system_program.clone()],
+    )
+}
+
+// Creates a rent-exempt account owned by this program at a PDA, funded by the payer
+fn create_pda_account<'a>(
+    payer: &AccountInfo<'a>,
+    new_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    program_id: &Pubkey,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    if *system_program.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    require_executable(&[system_program])?;
+    let lamports = Rent::get()?.minimum_balance(space);
+    solana_program::program::invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            new_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        ),
+        &[payer.clone(), new_account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )
+}
+
+// Closes a program-owned account, moving all of its lamports to `receiver`
+fn close_program_account(account: &AccountInfo, receiver: &AccountInfo) -> ProgramResult {
+    let refunded = receiver
+        .lamports()
+        .checked_add(account.lamports())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **receiver.try_borrow_mut_lamports()? = refunded;
+    **account.try_borrow_mut_lamports()? = 0;
+    account.realloc(0, false)?;
+    account.assign(&system_program::id());
+    Ok(())
+}
+
+// Reads a mint's launch stats, creating the zeroed account on its first write
+fn load_or_create_launch_stats<'a>(
+    program_id: &Pubkey,
+    stats_account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    mint: &Pubkey,
+    bump: u8,
+) -> Result<LaunchStats, ProgramError> {
+    verify_pda(stats_account.key, &[LAUNCH_STATS_SEED, mint.as_ref()], bump, program_id)?;
+    require!(stats_account.is_writable, ProgramError::InvalidAccountData);
+    if stats_account.owner != program_id {
+        create_pda_account(
+            payer,
+            stats_account,
+            system_program,
+            program_id,
+            LaunchStats::LEN,
+            &[LAUNCH_STATS_SEED, mint.as_ref(), &[bump]],
+        )?;
+        return Ok(LaunchStats {
+            account_type: AccountType::LaunchStats,
+            mint: *mint,
+            mint_operations: 0,
+            total_minted: 0,
+            recipients: 0,
+            finalized: false,
+        });
+    }
+    read_launch_stats(program_id, stats_account)
+}
+
+// Reads and validates a program-owned launch stats account
+fn read_launch_stats(
+    program_id: &Pubkey,
+    stats_account: &AccountInfo,
+) -> Result<LaunchStats, ProgramError> {
+    require!(stats_account.owner == program_id, ProgramError::IllegalOwner);
+    let stats = LaunchStats::try_from_slice(&stats_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    require!(stats.account_type == AccountType::LaunchStats, ProgramError::InvalidAccountData);
+    Ok(stats)
+}
+
+// Reads and validates the header of a program-owned distribution list, including its framing
+fn read_distribution_list_header(
+    program_id: &Pubkey,
+    list_account: &AccountInfo,
+) -> Result<DistributionListHeader, ProgramError> {
+    if list_account.owner != program_id {
+        return Err(ProgramError::IllegalOwner);
+    }
+    let data = list_account.data.borrow();
+    let header = DistributionListHeader::deserialize(&mut &data[..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if header.account_type != AccountType::DistributionList
+        || header.written > header.capacity
+        || data.len() != DistributionListHeader::account_len(header.capacity)
+    {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(header)
+}
+
+// Reads a distribution cursor, checking it belongs to the given mint and list
+fn read_distribution_cursor(
+    program_id: &Pubkey,
+    cursor_account: &AccountInfo,
+    mint_account: &AccountInfo,
+    list_account: &AccountInfo,
+) -> Result<DistributionCursor, ProgramError> {
+    require!(cursor_account.owner == program_id, ProgramError::IllegalOwner);
+    let cursor = DistributionCursor::try_from_slice(&cursor_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    require!(
+        cursor.account_type == AccountType::DistributionCursor
+            && cursor.mint == *mint_account.key
+            && cursor.list == *list_account.key,
+        ProgramError::InvalidAccountData
+    );
+    Ok(cursor)
+}
+
+// Reads the written entries of a distribution list whose header was already validated
+fn read_distribution_entries(
+    list_account: &AccountInfo,
+    header: &DistributionListHeader,
+) -> Result<Vec<DistributionEntry>, ProgramError> {
+    let data = list_account.data.borrow();
+    (0..header.written)
+        .map(|index| {
+            let start = DistributionListHeader::entry_offset(index);
+            DistributionEntry::try_from_slice(&data[start..start + DistributionEntry::LEN])
+                .map_err(|_| ProgramError::InvalidAccountData)
+        })
+        .collect()
+}
+
+// Validates the token program like `validate_token_program`, additionally requiring Token-2022
+// for features built on Token-2022 extensions
+fn validate_token_2022_program(
+    token_program: &AccountInfo,
+    mint_account: &AccountInfo,
+) -> ProgramResult {
+    validate_token_program(token_program, mint_account)?;
+    if *token_program.key != spl_token_2022::id() {
+        msg!("Token groups require a Token-2022 mint");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
+}
+
+// Validates that an account is an initialized SPL multisig of the given token program
+fn validate_multisig(
+    multisig_account: &AccountInfo,
+    token_program: &AccountInfo,
+) -> Result<Multisig, ProgramError> {
+    require!(multisig_account.owner == token_program.key, ProgramError::IllegalOwner);
+    let multisig = Multisig::unpack(&multisig_account.data.borrow())
+        .map_err(|_| ProgramError::from(CustomError::InvalidMultisig))?;
+    require!(
+        multisig.m != 0 && multisig.m <= multisig.n && multisig.n as usize <= MAX_SIGNERS,
+        CustomError::InvalidMultisig
+    );
+    Ok(multisig)
+}
+
+// Checks that a multisig actually spreads control of a treasury over several distinct keys
+fn validate_multisig_treasury(multisig: &Multisig) -> ProgramResult {
+    let signers = &multisig.signers[..multisig.n as usize];
+    let distinct = signers.iter().enumerate().all(|(index, signer)| {
+        *signer != Pubkey::default() && !signers[index + 1..].contains(signer)
+    });
+    verbose!(
+        "checking treasury multisig: got {} of {} expected at least {} of distinct signers",
+        multisig.m,
+        multisig.n,
+        MIN_TREASURY_MULTISIG_SIGNERS
+    );
+    if multisig.m < MIN_TREASURY_MULTISIG_SIGNERS || !distinct {
+        msg!("A {} of {} multisig can't hold the treasury", multisig.m, multisig.n);
+        return Err(CustomError::InvalidMultisigTreasury.into());
+    }
+    Ok(())
+}
+
+// Unpacks a mint owned by either token program, ignoring any Token-2022 extensions
+fn unpack_mint(mint_account: &AccountInfo) -> Result<Mint, ProgramError> {
+    require!(is_token_program(mint_account.owner), ProgramError::IllegalOwner);
+    check_account_len(mint_account, Mint::LEN, CustomError::MintWrongSize)?;
+    let data = mint_account.data.borrow();
+    Ok(StateWithExtensions::<Mint>::unpack(&data)?.base)
+}
+
+// Unpacks a token account owned by either token program, ignoring any Token-2022 extensions
+fn unpack_token_account(token_account: &AccountInfo) -> Result<TokenAccount, ProgramError> {
+    require!(is_token_program(token_account.owner), ProgramError::IllegalOwner);
+    check_account_len(token_account, TokenAccount::LEN, CustomError::TokenAccountWrongSize)?;
+    let data = token_account.data.borrow();
+    Ok(StateWithExtensions::<TokenAccount>::unpack(&data)?.base)
+}
+
+// Checks the data length of a token program account before it is unpacked, so an account with
+// the right owner but the wrong size fails with a clear error. SPL Token accounts are exactly
+// `base_len` bytes, Token-2022 ones may also carry extensions past the padded base state.
+fn check_account_len(account: &AccountInfo, base_len: usize, error: CustomError) -> ProgramResult {
+    let len = account.data_len();
+    let has_extensions = *account.owner == spl_token_2022::id() && len > TokenAccount::LEN;
+    verbose!("checking data length of {}: got {} expected {}", account.key, len, base_len);
+    require!(len == base_len || has_extensions, error);
+    Ok(())
+}
+
+// Returns true for the token programs this program can launch tokens with
+fn is_token_program(key: &Pubkey) -> bool {
+    *key == spl_token::id() || *key == spl_token_2022::id()
+}
+
+// Checks that every instruction in the transaction targets a whitelisted program, so a launch
+// can't be bundled with e.g. a transfer draining the authority's SOL
+fn verify_transaction_programs(
+    program_id: &Pubkey,
+    instructions_sysvar: &AccountInfo,
+) -> ProgramResult {
+    if !sysvar::instructions::check_id(instructions_sysvar.key) {
+        return Err(ProgramError::UnsupportedSysvar);
+    }
+
+    // Loading past the last instruction fails, which ends the scan
+    let mut index = 0;
+    while let Ok(instruction) =
+        sysvar::instructions::load_instruction_at_checked(index, instructions_sysvar)
+    {
+        if !is_whitelisted_program(program_id, &instruction.program_id) {
+            msg!(
+                "Instruction {} invokes disallowed program {}",
+                index,
+                instruction.program_id
+            );
+            return Err(CustomError::DisallowedInstructionInTx.into());
+        }
+        index += 1;
+    }
+
+    Ok(())
+}
+
+// Checks that the instruction right before this one is an Ed25519 signature check by
+// `authorizer` over the launch authorization message, and that the signed mint is this mint so
+// the authorization can't be replayed for another launch
+fn verify_launch_authorization(
+    instructions_sysvar: &AccountInfo,
+    authorizer: &Pubkey,
+    mint: &Pubkey,
+) -> ProgramResult {
+    if !sysvar::instructions::check_id(instructions_sysvar.key) {
+        return Err(ProgramError::UnsupportedSysvar);
+    }
+
+    let current = sysvar::instructions::load_current_index_checked(instructions_sysvar)?;
+    let index = current.checked_sub(1).ok_or(ProgramError::MissingRequiredSignature)?;
+    let instruction =
+        sysvar::instructions::load_instruction_at_checked(index as usize, instructions_sysvar)?;
+    require!(
+        instruction.program_id == ed25519_program::id(),
+        ProgramError::MissingRequiredSignature
+    );
+
+    let (signer, message) = parse_ed25519_instruction(&instruction.data)?;
+    require!(signer == authorizer.as_ref(), ProgramError::MissingRequiredSignature);
+    let signed_mint = message
+        .strip_prefix(LAUNCH_AUTHORIZATION_PREFIX)
+        .filter(|signed_mint| signed_mint.len() == 32)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    verbose!("checking authorized mint: got {:?} expected {}", signed_mint, mint);
+    require!(signed_mint == mint.as_ref(), CustomError::AuthorizationMintMismatch);
+
+    Ok(())
+}
+
+// Extracts the public key and message of a single-signature Ed25519 program instruction
+// The Ed25519 program has already verified the signature, but only offsets pointing into the
+// instruction's own data (instruction index u16::MAX) are accepted, so the checked key and
+// message are the ones read here
+fn parse_ed25519_instruction(data: &[u8]) -> Result<(&[u8], &[u8]), ProgramError> {
+    // One signature count byte and a padding byte, then seven u16 offsets
+    let read_u16 = |field: usize| {
+        data.get(2 + field * 2..4 + field * 2)
+            .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+            .ok_or(ProgramError::InvalidInstructionData)
+    };
+    require!(data.first() == Some(&1), ProgramError::InvalidInstructionData);
+    let own_data = [read_u16(1)?, read_u16(3)?, read_u16(6)?];
+    require!(own_data.iter().all(|index| *index == u16::MAX), ProgramError::InvalidInstructionData);
+
+    let public_key_offset = read_u16(2)? as usize;
+    let message_offset = read_u16(4)? as usize;
+    let message_size = read_u16(5)? as usize;
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    Ok((public_key, message))
+}
+
+// Programs a verified launch transaction may invoke
+fn is_whitelisted_program(program_id: &Pubkey, key: &Pubkey) -> bool {
+    *key == *program_id
+        || *key == system_program::id()
+        || *key == ASSOCIATED_TOKEN_PROGRAM_ID
+        || *key == COMPUTE_BUDGET_PROGRAM_ID
+        || *key == ed25519_program::id()
+        || is_token_program(key)
+}
+
+// Returns true for program and sysvar ids that can't be a signing authority
+fn is_reserved_authority_key(key: &Pubkey) -> bool {
+    *key == system_program::id() || is_token_program(key) || sysvar::is_sysvar_id(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_authority_tells_which_signature_is_missing() {
+        let key = Pubkey::new_unique();
+        let owner = system_program::id();
+        let (mut authority_lamports, mut payer_lamports) = (0, 0);
+        let (mut authority_data, mut payer_data) = (Vec::new(), Vec::new());
+        // One key with two signer bits, which only a refactor could produce: a transaction
+        // marks every occurrence of a key alike
+        let mut authority = AccountInfo::new(
+            &key,
+            true,
+            false,
+            &mut authority_lamports,
+            &mut authority_data,
+            &owner,
+            false,
+            0,
+        );
+        let mut payer = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut payer_lamports,
+            &mut payer_data,
+            &owner,
+            false,
+            0,
+        );
+
+        assert_eq!(
+            validate_authority(&authority, &payer),
+            Err(CustomError::PayerNotSigner.into())
+        );
+        (authority.is_signer, payer.is_signer) = (false, true);
+        assert_eq!(
+            validate_authority(&authority, &payer),
+            Err(CustomError::AuthorityNotSigner.into())
+        );
+        authority.is_signer = true;
+        assert_eq!(validate_authority(&authority, &payer), Ok(()));
+    }
+}
@@ -0,0 +1,619 @@
+// On-chain account state owned by the fixed supply token program
+// Every account starts with an `AccountType` tag so the different records can't be confused.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{hash::hashv, pubkey::Pubkey};
+
+/// Seed prefix for launch receipt PDAs: [RECEIPT_SEED, mint, nonce as little-endian bytes]
+pub const RECEIPT_SEED: &[u8] = b"receipt";
+
+/// Seed prefix for distribution list PDAs: [DISTRIBUTION_SEED, mint]
+pub const DISTRIBUTION_SEED: &[u8] = b"distribution";
+
+/// Seed prefix for program-derived mint authorities: [MINT_AUTHORITY_SEED, mint]
+pub const MINT_AUTHORITY_SEED: &[u8] = b"mint_authority";
+
+/// Seed prefix for chunked mint tracking PDAs: [CHUNKED_MINT_SEED, mint]
+pub const CHUNKED_MINT_SEED: &[u8] = b"chunked_mint";
+
+/// Seed prefix for distribution cursor PDAs: [DISTRIBUTION_CURSOR_SEED, mint]
+pub const DISTRIBUTION_CURSOR_SEED: &[u8] = b"distribution_cursor";
+
+/// Seed prefix for supply lock PDAs: [SUPPLY_LOCK_SEED, mint]
+pub const SUPPLY_LOCK_SEED: &[u8] = b"supply_lock";
+
+/// Seed prefix for launch statistics PDAs: [LAUNCH_STATS_SEED, mint]
+pub const LAUNCH_STATS_SEED: &[u8] = b"launch_stats";
+
+/// Seed prefix for governance handoff record PDAs: [GOVERNANCE_RECORD_SEED, mint]
+pub const GOVERNANCE_RECORD_SEED: &[u8] = b"governance_record";
+
+/// Seed prefix of governance authorities, PDAs of a governance program: [GOVERNANCE_SEED, mint]
+pub const GOVERNANCE_SEED: &[u8] = b"governance";
+
+/// Seed prefix of timelock freeze authorities, PDAs of the config's timelock program:
+/// [TIMELOCK_SEED, mint]
+pub const TIMELOCK_SEED: &[u8] = b"timelock";
+
+/// Seed prefix for supply cap PDAs: [SUPPLY_CAP_SEED, mint]
+pub const SUPPLY_CAP_SEED: &[u8] = b"supply_cap";
+
+/// Seed of the program's config PDA: [CONFIG_SEED]
+pub const CONFIG_SEED: &[u8] = b"config";
+
+/// Seed of the program's launcher allowlist PDA: [LAUNCHER_ALLOWLIST_SEED]
+pub const LAUNCHER_ALLOWLIST_SEED: &[u8] = b"launcher_allowlist";
+
+/// Seed prefix for test mint record PDAs, `test-mode` builds only: [TEST_MINT_SEED, mint]
+pub const TEST_MINT_SEED: &[u8] = b"test_mint";
+
+/// Seed of the PDA that signs `EmitEvent` self-invocations, the same seed Anchor uses
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// Maximum number of recipients a single `Distribute` transaction can reference, lists up to
+/// `MAX_LIST_RECIPIENTS` long are minted with `DistributeChunk` instead
+pub const MAX_DISTRIBUTION_RECIPIENTS: u16 = 20;
+
+/// Maximum number of recipients a distribution list can hold, bounded by the 10 KiB a program can
+/// allocate for an account it creates
+pub const MAX_LIST_RECIPIENTS: u16 = 250;
+
+/// Maximum number of keys the launcher allowlist can hold
+pub const MAX_ALLOWLISTED_LAUNCHERS: usize = 32;
+
+/// Tag stored in the first byte of every program-owned account
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountType {
+    Uninitialized,
+    LaunchReceipt,
+    DistributionList,
+    ChunkedMint,
+    SupplyLock,
+    LaunchStats,
+    GovernanceRecord,
+    SupplyCap,
+    LauncherAllowlist,
+    TestMint,
+    DistributionCursor,
+    ProgramConfig,
+}
+
+/// Permanent record of a completed launch
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LaunchReceipt {
+    /// Always `AccountType::LaunchReceipt`
+    pub account_type: AccountType,
+    /// The launched mint
+    pub mint: Pubkey,
+    /// The authority that performed the launch
+    pub creator: Pubkey,
+    /// Total supply minted, in base units
+    pub supply: u64,
+    /// Decimals of the mint
+    pub decimals: u8,
+    /// Client-supplied nonce that is part of the receipt address
+    pub nonce: u64,
+    /// Unix timestamp of the launch
+    pub launched_at: i64,
+    /// `PROGRAM_VERSION` of the program that performed the launch
+    pub program_version: u32,
+    /// `compute_checksum` of the fields above, written together with them
+    pub checksum: [u8; 32],
+}
+
+impl LaunchReceipt {
+    /// Serialized size of a receipt account
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 1 + 8 + 8 + 4 + 32;
+
+    /// sha256 of every other field in serialization order, integers as little-endian bytes
+    pub fn compute_checksum(&self) -> [u8; 32] {
+        hashv(&[
+            &[self.account_type as u8],
+            self.mint.as_ref(),
+            self.creator.as_ref(),
+            &self.supply.to_le_bytes(),
+            &[self.decimals],
+            &self.nonce.to_le_bytes(),
+            &self.launched_at.to_le_bytes(),
+            &self.program_version.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+}
+
+/// Header of a distribution list account, followed by `capacity` fixed-size entries
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DistributionListHeader {
+    /// Always `AccountType::DistributionList`
+    pub account_type: AccountType,
+    /// The mint the list distributes
+    pub mint: Pubkey,
+    /// The mint authority allowed to write and distribute the list
+    pub authority: Pubkey,
+    /// Number of entry slots allocated in the account
+    pub capacity: u16,
+    /// Number of entries written so far, entries are appended in order
+    pub written: u16,
+}
+
+impl DistributionListHeader {
+    /// Serialized size of the header
+    pub const LEN: usize = 1 + 32 + 32 + 2 + 2;
+
+    /// Total account size for a list with `capacity` entries
+    pub fn account_len(capacity: u16) -> usize {
+        Self::LEN + capacity as usize * DistributionEntry::LEN
+    }
+
+    /// Byte offset of the entry at `index`
+    pub fn entry_offset(index: u16) -> usize {
+        Self::LEN + index as usize * DistributionEntry::LEN
+    }
+}
+
+/// A single recipient of a distribution
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct DistributionEntry {
+    /// Destination token account
+    pub destination: Pubkey,
+    /// Amount to mint, in base units
+    pub amount: u64,
+}
+
+impl DistributionEntry {
+    /// Serialized size of an entry
+    pub const LEN: usize = 32 + 8;
+}
+
+/// Progress of a distribution list minted across several `DistributeChunk` calls
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DistributionCursor {
+    /// Always `AccountType::DistributionCursor`
+    pub account_type: AccountType,
+    /// The mint being distributed
+    pub mint: Pubkey,
+    /// The distribution list the cursor walks
+    pub list: Pubkey,
+    /// Index of the next list entry to mint to, equal to the list capacity once complete
+    pub next_index: u16,
+    /// Base units minted by the chunks so far
+    pub distributed: u64,
+}
+
+impl DistributionCursor {
+    /// Serialized size of a distribution cursor account
+    pub const LEN: usize = 1 + 32 + 32 + 2 + 8;
+}
+
+/// Progress of a launch that mints the total supply in several chunks before revoking
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ChunkedMint {
+    /// Always `AccountType::ChunkedMint`
+    pub account_type: AccountType,
+    /// The mint being launched
+    pub mint: Pubkey,
+    /// The mint authority allowed to mint chunks
+    pub authority: Pubkey,
+    /// Total supply the chunks must add up to, in base units
+    pub target_total: u64,
+    /// Base units minted by the chunks so far
+    pub cumulative_minted: u64,
+}
+
+impl ChunkedMint {
+    /// Serialized size of a chunked mint account
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8;
+}
+
+/// Running totals of a launch minted across several instructions, for dashboards
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LaunchStats {
+    /// Always `AccountType::LaunchStats`
+    pub account_type: AccountType,
+    /// The mint the totals are for
+    pub mint: Pubkey,
+    /// Number of mint operations recorded
+    pub mint_operations: u32,
+    /// Base units minted by the recorded operations
+    pub total_minted: u64,
+    /// Number of destinations credited while they held no tokens yet
+    pub recipients: u32,
+    /// Whether the mint authority has been revoked, ending the launch
+    pub finalized: bool,
+}
+
+impl LaunchStats {
+    /// Serialized size of a launch stats account
+    pub const LEN: usize = 1 + 32 + 4 + 8 + 4 + 1;
+}
+
+/// Record of a launch that handed the mint authority to a governance program instead of
+/// revoking it
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GovernanceRecord {
+    /// Always `AccountType::GovernanceRecord`
+    pub account_type: AccountType,
+    /// The launched mint
+    pub mint: Pubkey,
+    /// The governance program that derives `governance_authority`
+    pub governance_program: Pubkey,
+    /// The new mint authority, a PDA of `governance_program` [GOVERNANCE_SEED, mint]
+    pub governance_authority: Pubkey,
+    /// Supply minted at launch, in base units
+    pub supply: u64,
+    /// Unix timestamp of the handoff
+    pub recorded_at: i64,
+}
+
+impl GovernanceRecord {
+    /// Serialized size of a governance record account
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 8 + 8;
+}
+
+/// Permanent total supply of a finalized mint, for programs composing with the token
+/// Only written once the mint authority is revoked, and never modified afterwards.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SupplyCap {
+    /// Always `AccountType::SupplyCap`
+    pub account_type: AccountType,
+    /// The finalized mint
+    pub mint: Pubkey,
+    /// Total supply of the mint, in base units
+    pub supply: u64,
+    /// Decimals of the mint
+    pub decimals: u8,
+    /// Unix timestamp the cap was recorded at
+    pub recorded_at: i64,
+}
+
+impl SupplyCap {
+    /// Serialized size of a supply cap account
+    pub const LEN: usize = 1 + 32 + 8 + 1 + 8;
+}
+
+/// Shape of the release curve of a supply lock
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VestingCurve {
+    /// Everything unlocks at the unlock timestamp
+    Cliff,
+    /// Unlocks continuously from `start` until the unlock timestamp
+    Linear,
+    /// Nothing until `cliff`, then whatever vested linearly since `start`
+    CliffThenLinear,
+    /// Unlocks in `steps` equal tranches spread between `start` and the unlock timestamp
+    Stepped,
+}
+
+/// Release schedule of a supply lock, fields a curve doesn't use must be zero
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VestingSchedule {
+    /// The curve the locked tokens follow
+    pub curve: VestingCurve,
+    /// Unix timestamp vesting starts from, unused by `Cliff`
+    pub start: i64,
+    /// Unix timestamp before which nothing unlocks, only used by `CliffThenLinear`
+    pub cliff: i64,
+    /// Number of tranches, only used by `Stepped`
+    pub steps: u16,
+}
+
+impl VestingSchedule {
+    /// Serialized size of a vesting schedule
+    pub const LEN: usize = 1 + 8 + 8 + 2;
+
+    /// The schedule releasing everything at the unlock timestamp
+    pub const CLIFF: Self = Self { curve: VestingCurve::Cliff, start: 0, cliff: 0, steps: 0 };
+
+    /// Whether the parameters are consistent for the curve and end at `unlock_timestamp`
+    pub fn is_valid(&self, unlock_timestamp: i64) -> bool {
+        match self.curve {
+            VestingCurve::Cliff => self.start == 0 && self.cliff == 0 && self.steps == 0,
+            VestingCurve::Linear => {
+                self.start < unlock_timestamp && self.cliff == 0 && self.steps == 0
+            }
+            VestingCurve::CliffThenLinear => {
+                self.start < self.cliff && self.cliff < unlock_timestamp && self.steps == 0
+            }
+            VestingCurve::Stepped => {
+                self.start < unlock_timestamp && self.cliff == 0 && self.steps > 0
+            }
+        }
+    }
+
+    /// Base units of `total` vested at `now` for a schedule ending at `unlock_timestamp`
+    pub fn vested_amount(&self, total: u64, unlock_timestamp: i64, now: i64) -> u64 {
+        if now >= unlock_timestamp {
+            return total;
+        }
+        let duration = (unlock_timestamp as i128 - self.start as i128) as u128;
+        let elapsed = (now as i128 - self.start as i128).max(0) as u128;
+        let vested = match self.curve {
+            VestingCurve::Cliff => 0,
+            VestingCurve::CliffThenLinear if now < self.cliff => 0,
+            VestingCurve::Linear | VestingCurve::CliffThenLinear => {
+                total as u128 * elapsed / duration
+            }
+            VestingCurve::Stepped => {
+                let steps = self.steps as u128;
+                total as u128 * (elapsed * steps / duration) / steps
+            }
+        };
+        // Below `total` since `now` is before the unlock timestamp
+        vested as u64
+    }
+}
+
+/// Record of the part of a launch's supply held in a time-locked vault
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SupplyLock {
+    /// Always `AccountType::SupplyLock`
+    pub account_type: AccountType,
+    /// The launched mint
+    pub mint: Pubkey,
+    /// Token account owned by this PDA that holds the locked tokens
+    pub vault: Pubkey,
+    /// Wallet allowed to release the locked tokens once they unlock
+    pub beneficiary: Pubkey,
+    /// Base units minted to the treasury at launch
+    pub circulating_amount: u64,
+    /// Base units minted to the vault at launch
+    pub locked_amount: u64,
+    /// Unix timestamp from which all locked tokens can be released
+    pub unlock_timestamp: i64,
+    /// How the locked tokens unlock up to `unlock_timestamp`
+    pub vesting: VestingSchedule,
+    /// Base units already released to the beneficiary
+    pub released_amount: u64,
+    /// Bump of this PDA, used to sign for the vault
+    pub bump: u8,
+}
+
+impl SupplyLock {
+    /// Serialized size of a supply lock account
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 8 + 8 + 8 + VestingSchedule::LEN + 8 + 1;
+
+    /// Base units vested at `now` that haven't been released yet
+    pub fn releasable_amount(&self, now: i64) -> u64 {
+        self.vesting
+            .vested_amount(self.locked_amount, self.unlock_timestamp, now)
+            .saturating_sub(self.released_amount)
+    }
+
+    /// Base units still owed to the beneficiary
+    pub fn unreleased_amount(&self) -> u64 {
+        self.locked_amount.saturating_sub(self.released_amount)
+    }
+}
+
+/// Keys allowed to launch while the config enables the launcher allowlist, managed by the admin
+/// The account is allocated for `MAX_ALLOWLISTED_LAUNCHERS` keys, any data past the serialized
+/// list is ignored.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LauncherAllowlist {
+    /// Always `AccountType::LauncherAllowlist`
+    pub account_type: AccountType,
+    /// The allowlisted launcher keys, in the order they were added
+    pub launchers: Vec<Pubkey>,
+}
+
+impl LauncherAllowlist {
+    /// Allocated size of the allowlist account
+    pub const LEN: usize = 1 + 4 + MAX_ALLOWLISTED_LAUNCHERS * 32;
+}
+
+/// Program-wide settings stored in the config PDA, created by `SetConfigAdmin`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProgramConfig {
+    /// Always `AccountType::ProgramConfig`
+    pub account_type: AccountType,
+    /// The key every admin instruction must be signed by, and `IsAdmin` reports
+    pub admin: Pubkey,
+    /// The launch policy every launching instruction enforces, set with `UpdateConfig`
+    pub settings: ConfigSettings,
+}
+
+impl ProgramConfig {
+    /// Serialized size of the config account
+    pub const LEN: usize = 1 + 32 + ConfigSettings::LEN;
+}
+
+/// Program-wide launch policy stored in the config, all of it off by default
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigSettings {
+    /// Only keys on the launcher allowlist may launch, through any launching instruction
+    pub launcher_allowlist_enabled: bool,
+    /// Lamports every launch pays to `fee_treasury`, 0 charges nothing
+    pub launch_fee_lamports: u64,
+    /// Platform wallet credited with the launch fee
+    pub fee_treasury: Pubkey,
+    /// Verification oracle whose approval every launched mint needs, the default key needs none
+    pub oracle_program: Pubkey,
+    /// Timelock program whose PDA a timelocked-freeze launch hands the freeze authority to, the
+    /// default key disables those launches
+    pub timelock_program: Pubkey,
+}
+
+impl ConfigSettings {
+    /// Serialized size of the settings
+    pub const LEN: usize = 1 + 8 + 32 + 32 + 32;
+
+    /// Whether launches have to pay a fee
+    pub fn charges_launch_fee(&self) -> bool {
+        self.launch_fee_lamports > 0
+    }
+
+    /// Whether launches need the verification oracle's approval
+    pub fn requires_oracle_approval(&self) -> bool {
+        self.oracle_program != Pubkey::default()
+    }
+
+    /// Whether timelocked-freeze launches are enabled
+    pub fn has_timelock_program(&self) -> bool {
+        self.timelock_program != Pubkey::default()
+    }
+}
+
+/// Flags a mint whose supply was minted in test mode, with the mint authority kept
+/// Only `test-mode` builds create it. While it exists the authority can burn the test supply and
+/// reset the mint with `ResetTestMint`, which closes the record.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TestMint {
+    /// Always `AccountType::TestMint`
+    pub account_type: AccountType,
+    /// The mint minted in test mode
+    pub mint: Pubkey,
+    /// The mint authority that minted the test supply and may reset it
+    pub authority: Pubkey,
+    /// Test supply minted, in base units
+    pub supply: u64,
+    /// Unix timestamp of the test mint
+    pub minted_at: i64,
+}
+
+impl TestMint {
+    /// Serialized size of a test mint account
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8;
+}
+
+/// Approval record written by the config's verification oracle
+/// Owned by the oracle rather than this program, so it carries no `AccountType` tag. Any data
+/// past `LEN` is ignored.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OracleApproval {
+    /// Whether the oracle approved the launch
+    pub approved: bool,
+    /// The mint the approval is for
+    pub mint: Pubkey,
+}
+
+impl OracleApproval {
+    /// Serialized size of the approval record
+    pub const LEN: usize = 1 + 32;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: i64 = 1_000;
+    const UNLOCK: i64 = 2_000;
+    const TOTAL: u64 = 1_000;
+
+    fn schedule(curve: VestingCurve, start: i64, cliff: i64, steps: u16) -> VestingSchedule {
+        VestingSchedule { curve, start, cliff, steps }
+    }
+
+    // Asserts the amount vested at each (timestamp, amount) pair
+    fn assert_vested(schedule: VestingSchedule, expected: &[(i64, u64)]) {
+        assert!(schedule.is_valid(UNLOCK));
+        for (now, amount) in expected {
+            assert_eq!(schedule.vested_amount(TOTAL, UNLOCK, *now), *amount, "at {}", now);
+        }
+    }
+
+    #[test]
+    fn cliff_releases_everything_at_the_unlock() {
+        let expected = [(0, 0), (START, 0), (1_999, 0), (UNLOCK, TOTAL), (3_000, TOTAL)];
+        assert_vested(VestingSchedule::CLIFF, &expected);
+    }
+
+    #[test]
+    fn linear_releases_in_proportion_to_the_elapsed_time() {
+        let expected =
+            [(500, 0), (START, 0), (1_250, 250), (1_500, 500), (1_999, 999), (UNLOCK, TOTAL)];
+        assert_vested(schedule(VestingCurve::Linear, START, 0, 0), &expected);
+    }
+
+    #[test]
+    fn cliff_then_linear_releases_nothing_before_the_cliff() {
+        let expected = [(START, 0), (1_499, 0), (1_500, 500), (1_750, 750), (UNLOCK, TOTAL)];
+        assert_vested(schedule(VestingCurve::CliffThenLinear, START, 1_500, 0), &expected);
+    }
+
+    #[test]
+    fn stepped_releases_whole_tranches() {
+        let expected =
+            [(START, 0), (1_249, 0), (1_250, 250), (1_600, 500), (1_999, 750), (UNLOCK, TOTAL)];
+        assert_vested(schedule(VestingCurve::Stepped, START, 0, 4), &expected);
+    }
+
+    #[test]
+    fn invalid_curve_parameters_are_rejected() {
+        let invalid = [
+            schedule(VestingCurve::Cliff, START, 0, 0),
+            schedule(VestingCurve::Linear, UNLOCK, 0, 0),
+            schedule(VestingCurve::Linear, START, 0, 4),
+            schedule(VestingCurve::CliffThenLinear, START, START, 0),
+            schedule(VestingCurve::CliffThenLinear, START, UNLOCK, 0),
+            schedule(VestingCurve::Stepped, START, 0, 0),
+            schedule(VestingCurve::Stepped, START, 1_500, 4),
+        ];
+        for schedule in invalid {
+            assert!(!schedule.is_valid(UNLOCK), "{:?}", schedule);
+        }
+    }
+
+    fn receipt() -> LaunchReceipt {
+        let mut receipt = LaunchReceipt {
+            account_type: AccountType::LaunchReceipt,
+            mint: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            supply: 500_000_000_000_000_000,
+            decimals: 9,
+            nonce: 7,
+            launched_at: 1_700_000_000,
+            program_version: 1,
+            checksum: [0; 32],
+        };
+        receipt.checksum = receipt.compute_checksum();
+        receipt
+    }
+
+    #[test]
+    fn receipt_checksum_is_stable_for_an_intact_receipt() {
+        let receipt = receipt();
+        let stored = borsh::to_vec(&receipt).unwrap();
+        assert_eq!(stored.len(), LaunchReceipt::LEN);
+        let reread = LaunchReceipt::try_from_slice(&stored).unwrap();
+        assert_eq!(reread.compute_checksum(), receipt.checksum);
+    }
+
+    #[test]
+    fn receipt_checksum_detects_tampering_with_any_field() {
+        let intact = receipt();
+        let tampered: [fn(&mut LaunchReceipt); 8] = [
+            |r| r.account_type = AccountType::SupplyCap,
+            |r| r.mint = Pubkey::new_unique(),
+            |r| r.creator = Pubkey::new_unique(),
+            |r| r.supply += 1,
+            |r| r.decimals = 6,
+            |r| r.nonce += 1,
+            |r| r.launched_at -= 1,
+            |r| r.program_version += 1,
+        ];
+        for tamper in tampered {
+            let mut receipt = intact.clone();
+            tamper(&mut receipt);
+            assert_ne!(receipt.compute_checksum(), receipt.checksum, "{:?}", receipt);
+        }
+    }
+
+    #[test]
+    fn releasable_amount_excludes_what_was_released() {
+        let lock = SupplyLock {
+            account_type: AccountType::SupplyLock,
+            mint: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            beneficiary: Pubkey::new_unique(),
+            circulating_amount: 0,
+            locked_amount: TOTAL,
+            unlock_timestamp: UNLOCK,
+            vesting: schedule(VestingCurve::Linear, START, 0, 0),
+            released_amount: 250,
+            bump: 255,
+        };
+        assert_eq!(lock.releasable_amount(1_500), 250);
+        assert_eq!(lock.releasable_amount(UNLOCK), TOTAL - 250);
+        assert_eq!(lock.unreleased_amount(), TOTAL - 250);
+    }
+}
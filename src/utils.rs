@@ -0,0 +1,232 @@
+// Small formatting and math helpers shared by the processor and off-chain tooling
+
+use solana_program::{hash::hashv, program_error::ProgramError, pubkey::Pubkey, rent::Rent};
+use spl_token_2022::{extension::ExtensionType, state::Mint};
+
+use crate::{ALLOWED_DECIMALS, MAX_DECIMALS_OVER_SUPPLY_DIGITS, MIN_SUPPLY, TOTAL_SUPPLY};
+
+/// Formats a base-unit amount as a UI amount string using only integer math
+/// For example 1_500_000_000 with 9 decimals becomes "1.500000000", and any amount with
+/// 0 decimals is printed without a fractional part.
+pub fn format_ui_amount(amount: u64, decimals: u8) -> String {
+    let digits = amount.to_string();
+    if decimals == 0 {
+        return digits;
+    }
+
+    // Left-pad with zeros so there is always at least one integer digit
+    let decimals = decimals as usize;
+    let padded = format!("{:0>width$}", digits, width = decimals + 1);
+    let (integer, fraction) = padded.split_at(padded.len() - decimals);
+    format!("{}.{}", integer, fraction)
+}
+
+/// Converts a UI amount string to base units, rejecting values the mint can't represent exactly
+/// For example "100.5" with 1 decimal becomes 1005, while "100.5" with 0 decimals is rejected
+/// instead of being truncated to 100. Zeros past the mint's decimals are accepted.
+pub fn parse_ui_amount(ui_amount: &str, decimals: u8) -> Result<u64, ProgramError> {
+    let (integer, fraction) = ui_amount.split_once('.').unwrap_or((ui_amount, ""));
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if (integer.is_empty() && fraction.is_empty()) || !is_digits(integer) || !is_digits(fraction) {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Digits below the smallest base unit would be silently dropped, so they must all be zero
+    let decimals = decimals as usize;
+    let (kept, dropped) = fraction.split_at(fraction.len().min(decimals));
+    if dropped.bytes().any(|b| b != b'0') {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let digits = format!("{}{:0<width$}", integer, kept, width = decimals);
+    digits.parse().map_err(|_| ProgramError::ArithmeticOverflow)
+}
+
+/// Fully diluted value of `supply` base units at `price_per_token` per whole token
+/// The product is taken in 128 bits before dividing by 10^decimals, so only a value that doesn't
+/// fit in a u64 returns `None`. Fractions of the price's unit are rounded down.
+pub fn fully_diluted_value(supply: u64, decimals: u8, price_per_token: u64) -> Option<u64> {
+    let one_token = 10u128.checked_pow(decimals as u32)?;
+    let value = supply as u128 * price_per_token as u128 / one_token;
+    u64::try_from(value).ok()
+}
+
+/// Commitment to a finalized launch checked by `VerifySupplyHash`: sha256 of the mint address,
+/// the supply in base units as little-endian bytes and the decimals byte
+pub fn supply_hash(mint: &Pubkey, supply: u64, decimals: u8) -> [u8; 32] {
+    hashv(&[mint.as_ref(), &supply.to_le_bytes(), &[decimals]]).to_bytes()
+}
+
+/// Domain separator hashed in front of the seed by `derive_launch_params`
+pub const SEED_LAUNCH_DOMAIN: &[u8] = b"fixed-supply:seed-launch";
+
+/// Derives the (whole-token supply, decimals) of a seeded launch
+/// With `h = sha256(SEED_LAUNCH_DOMAIN || seed)`:
+/// - decimals = `ALLOWED_DECIMALS[h[0] % ALLOWED_DECIMALS.len()]`
+/// - supply = `MIN_SUPPLY + u64::from_le_bytes(h[1..9]) % (TOTAL_SUPPLY - MIN_SUPPLY + 1)`
+///
+/// Returns `None` for degenerate outputs: a supply whose base units overflow a u64, or more
+/// decimals than `MAX_DECIMALS_OVER_SUPPLY_DIGITS` over the supply's digit count.
+pub fn derive_launch_params(seed: &[u8; 32]) -> Option<(u64, u8)> {
+    let hash = hashv(&[SEED_LAUNCH_DOMAIN, seed]).to_bytes();
+    let decimals = *ALLOWED_DECIMALS.get(hash[0] as usize % ALLOWED_DECIMALS.len().max(1))?;
+    let range = TOTAL_SUPPLY.checked_sub(MIN_SUPPLY)?.checked_add(1)?;
+    let sample = u64::from_le_bytes(hash[1..9].try_into().ok()?);
+    let ui_supply = MIN_SUPPLY + sample % range;
+
+    // The supply in base units has to fit in a u64
+    10u64.checked_pow(decimals as u32)?.checked_mul(ui_supply)?;
+    let supply_digits = ui_supply.checked_ilog10().map_or(1, |log| log as u8 + 1);
+    if decimals > supply_digits.saturating_add(MAX_DECIMALS_OVER_SUPPLY_DIGITS) {
+        return None;
+    }
+    Some((ui_supply, decimals))
+}
+
+/// Prefix of the single result line logged after every successful launch
+pub const RESULT_LINE_PREFIX: &str = "[fixed-supply] RESULT";
+
+/// Upper bound on the result line length: a 44 character mint, a 20 digit supply and
+/// 3 digit decimals
+pub const RESULT_LINE_MAX_LEN: usize = 132;
+
+/// Formats the canonical launch result line that monitoring tools can grep for
+/// For example `[fixed-supply] RESULT mint=<pubkey> supply=500000000000000000 decimals=9
+/// mint_authority=none`, always on a single line of at most `RESULT_LINE_MAX_LEN` bytes.
+pub fn format_result_line(mint: &Pubkey, supply: u64, decimals: u8) -> String {
+    format!(
+        "{} mint={} supply={} decimals={} mint_authority=none",
+        RESULT_LINE_PREFIX, mint, supply, decimals
+    )
+}
+
+/// Space of a Token-2022 mint account carrying `extensions`, the base mint without any
+/// Also sizes the mints `CreateMint` creates on-chain. Fails for account-only extensions, which
+/// can't be added to a mint, and for variable-length ones such as `TokenMetadata`.
+pub fn token2022_mint_len(extensions: &[ExtensionType]) -> Result<usize, ProgramError> {
+    ExtensionType::try_calculate_account_len::<Mint>(extensions)
+}
+
+/// Rent-exempt minimum of a Token-2022 mint account carrying `extensions`
+pub fn token2022_mint_rent(rent: &Rent, extensions: &[ExtensionType]) -> Result<u64, ProgramError> {
+    Ok(rent.minimum_balance(token2022_mint_len(extensions)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_ui_amount_splits_integer_and_fraction() {
+        assert_eq!(format_ui_amount(500_000_000_000_000_000, 9), "500000000.000000000");
+        assert_eq!(format_ui_amount(1_500_000_000, 9), "1.500000000");
+        assert_eq!(format_ui_amount(42, 6), "0.000042");
+        assert_eq!(format_ui_amount(0, 2), "0.00");
+        assert_eq!(format_ui_amount(u64::MAX, 19), "1.8446744073709551615");
+    }
+
+    #[test]
+    fn format_ui_amount_without_decimals_has_no_fraction() {
+        assert_eq!(format_ui_amount(500_000_000, 0), "500000000");
+        assert_eq!(format_ui_amount(0, 0), "0");
+    }
+
+    #[test]
+    fn parse_ui_amount_converts_exact_amounts() {
+        assert_eq!(parse_ui_amount("100.5", 1), Ok(1005));
+        assert_eq!(parse_ui_amount("100", 0), Ok(100));
+        assert_eq!(parse_ui_amount("0.000000001", 9), Ok(1));
+        assert_eq!(parse_ui_amount(".5", 2), Ok(50));
+        // Zeros past the mint's decimals don't lose anything
+        assert_eq!(parse_ui_amount("100.500", 1), Ok(1005));
+    }
+
+    #[test]
+    fn parse_ui_amount_rejects_lossy_conversions() {
+        assert_eq!(parse_ui_amount("100.5", 0), Err(ProgramError::InvalidArgument));
+        assert_eq!(parse_ui_amount("1.0000000001", 9), Err(ProgramError::InvalidArgument));
+    }
+
+    #[test]
+    fn parse_ui_amount_rejects_malformed_and_overflowing_amounts() {
+        for ui_amount in ["", ".", "-1", "1.2.3", "1e9", " 1"] {
+            assert_eq!(parse_ui_amount(ui_amount, 9), Err(ProgramError::InvalidArgument));
+        }
+        assert_eq!(parse_ui_amount("18446744074", 9), Err(ProgramError::ArithmeticOverflow));
+    }
+
+    #[test]
+    fn fully_diluted_value_prices_whole_tokens() {
+        // 500 million tokens at 2 units each
+        assert_eq!(fully_diluted_value(500_000_000_000_000_000, 9, 2), Some(1_000_000_000));
+        // 1.5 tokens at 3 units each, the half unit is rounded down
+        assert_eq!(fully_diluted_value(1_500_000_000, 9, 3), Some(4));
+        assert_eq!(fully_diluted_value(1_000, 0, 0), Some(0));
+    }
+
+    #[test]
+    fn fully_diluted_value_overflows_to_none() {
+        // The product exceeds a u64 but is taken in 128 bits, so only the final value matters
+        assert_eq!(fully_diluted_value(u64::MAX, 9, 1_000_000_000), Some(u64::MAX));
+        assert_eq!(fully_diluted_value(u64::MAX, 0, 2), None);
+        // 10^39 doesn't fit in 128 bits either
+        assert_eq!(fully_diluted_value(1, 39, 1), None);
+    }
+
+    #[test]
+    fn format_result_line_is_pinned_for_log_scrapers() {
+        let mint = Pubkey::new_from_array([1; 32]);
+        assert_eq!(
+            format_result_line(&mint, 500_000_000_000_000_000, 9),
+            format!(
+                "[fixed-supply] RESULT mint={} supply=500000000000000000 decimals=9 \
+                 mint_authority=none",
+                mint
+            )
+        );
+    }
+
+    #[test]
+    fn format_result_line_stays_within_the_max_len() {
+        // The largest mint address in base58 with the widest supply and decimals
+        let mint = Pubkey::new_from_array([u8::MAX; 32]);
+        assert_eq!(mint.to_string().len(), 44);
+        let line = format_result_line(&mint, u64::MAX, u8::MAX);
+        assert_eq!(line.len(), RESULT_LINE_MAX_LEN);
+        assert!(!line.contains('\n'));
+    }
+
+    #[test]
+    fn supply_hash_commits_to_the_mint_supply_and_decimals() {
+        let mint = Pubkey::new_from_array([1; 32]);
+        let mut preimage = mint.to_bytes().to_vec();
+        preimage.extend(1_000u64.to_le_bytes());
+        preimage.push(9);
+        assert_eq!(supply_hash(&mint, 1_000, 9), hashv(&[&preimage]).to_bytes());
+
+        assert_ne!(supply_hash(&mint, 1_000, 9), supply_hash(&mint, 1_001, 9));
+        assert_ne!(supply_hash(&mint, 1_000, 9), supply_hash(&mint, 1_000, 6));
+        let other_mint = Pubkey::new_from_array([2; 32]);
+        assert_ne!(supply_hash(&mint, 1_000, 9), supply_hash(&other_mint, 1_000, 9));
+    }
+
+    #[test]
+    fn derive_launch_params_is_deterministic() {
+        for seed in [[0u8; 32], [1; 32], [0xAB; 32]] {
+            assert_eq!(derive_launch_params(&seed), derive_launch_params(&seed));
+        }
+        assert_ne!(derive_launch_params(&[1; 32]), derive_launch_params(&[2; 32]));
+    }
+
+    #[test]
+    fn derive_launch_params_follows_the_documented_hash_to_range() {
+        let seed = [42u8; 32];
+        let hash = hashv(&[SEED_LAUNCH_DOMAIN, &seed]).to_bytes();
+        let decimals = ALLOWED_DECIMALS[hash[0] as usize % ALLOWED_DECIMALS.len()];
+        let sample = u64::from_le_bytes(hash[1..9].try_into().unwrap());
+        let supply = MIN_SUPPLY + sample % (TOTAL_SUPPLY - MIN_SUPPLY + 1);
+
+        assert_eq!(derive_launch_params(&seed), Some((supply, decimals)));
+        assert!((MIN_SUPPLY..=TOTAL_SUPPLY).contains(&supply));
+    }
+}
@@ -0,0 +1,64 @@
+// Program tests of the config admin instructions
+
+mod common;
+
+use common::{assert_custom_error, TestContext};
+use fixed_supply_token::{
+    client::{add_launcher, is_admin, set_config_admin},
+    error::CustomError,
+};
+use solana_sdk::{pubkey::Pubkey, signature::Signer, transaction::TransactionError};
+
+// Simulates `IsAdmin` for `candidate`, returning whether the program reports it as the admin
+async fn query_is_admin(test: &mut TestContext, candidate: &Pubkey) -> bool {
+    let instruction = is_admin(&test.program_id, candidate);
+    let (result, return_data) = test.simulate(&[instruction], &[]).await;
+    assert_eq!(result, Ok::<(), TransactionError>(()));
+    match return_data.as_slice() {
+        [0] => false,
+        [1] => true,
+        other => panic!("unexpected return data {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn is_admin_reports_the_stored_admin() {
+    let mut test = TestContext::new().await;
+    let admin = test.payer().pubkey();
+    test.initialize_config(&admin).await;
+
+    assert!(query_is_admin(&mut test, &admin).await);
+    assert!(!query_is_admin(&mut test, &Pubkey::new_unique()).await);
+}
+
+#[tokio::test]
+async fn config_can_only_be_bootstrapped_by_the_upgrade_authority() {
+    let mut test = TestContext::new().await;
+    test.set_upgrade_authority(&Pubkey::new_unique());
+    let payer = test.payer().pubkey();
+
+    let instruction = set_config_admin(&test.program_id, &payer, &payer);
+    let result = test.process(&[instruction], &[]).await;
+
+    assert_custom_error(result, CustomError::AdminMismatch);
+}
+
+#[tokio::test]
+async fn handed_over_admin_role_moves_every_admin_instruction() {
+    let mut test = TestContext::new().await;
+    let old_admin = test.payer();
+    test.initialize_config(&old_admin.pubkey()).await;
+    let new_admin = test.funded_keypair().await;
+
+    let instruction = set_config_admin(&test.program_id, &old_admin.pubkey(), &new_admin.pubkey());
+    test.process(&[instruction], &[]).await.unwrap();
+
+    assert!(query_is_admin(&mut test, &new_admin.pubkey()).await);
+    assert!(!query_is_admin(&mut test, &old_admin.pubkey()).await);
+    let launcher = Pubkey::new_unique();
+    let instruction = add_launcher(&test.program_id, &old_admin.pubkey(), &launcher);
+    let result = test.process(&[instruction], &[]).await;
+    assert_custom_error(result, CustomError::AdminMismatch);
+    let instruction = add_launcher(&test.program_id, &new_admin.pubkey(), &launcher);
+    test.process(&[instruction], &[&new_admin]).await.unwrap();
+}
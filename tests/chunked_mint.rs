@@ -0,0 +1,143 @@
+// Program tests of the chunked launch and the cleanup of its transient PDAs
+
+mod common;
+
+use borsh::BorshDeserialize;
+use common::{assert_custom_error, TestContext};
+use fixed_supply_token::{
+    client::{
+        cleanup, create_distribution_list, mint_chunk, remaining_mintable, start_chunked_mint,
+    },
+    error::CustomError,
+    pda::{find_chunked_mint_address, find_distribution_list_address, find_launch_stats_address},
+    state::ChunkedMint,
+    DECIMALS, TOTAL_SUPPLY,
+};
+use solana_program_test::BanksClientError;
+use solana_sdk::{pubkey::Pubkey, signature::Signer, system_program};
+
+// The default supply in base units, the target of every chunked launch below
+fn total_supply() -> u64 {
+    TOTAL_SUPPLY * 10u64.pow(DECIMALS as u32)
+}
+
+// Creates an SPL Token mint controlled by the payer, the payer's token account for it and the
+// chunked mint PDA. Returns the mint and token account.
+async fn start_chunked_launch(test: &mut TestContext) -> (Pubkey, Pubkey) {
+    let authority = test.payer().pubkey();
+    let token_program = spl_token::id();
+    let mint = test.create_mint(&token_program, &authority, None).await.pubkey();
+    let token_account = test.create_ata(&authority, &mint, &token_program).await;
+    let instruction = start_chunked_mint(&test.program_id, &mint, &authority);
+    test.process(&[instruction], &[]).await.unwrap();
+    (mint, token_account)
+}
+
+// Mints a chunk of `amount` base units into `token_account`, recording launch stats
+async fn mint_next_chunk(
+    test: &mut TestContext,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    amount: u64,
+) -> Result<(), BanksClientError> {
+    let gate = test.launch_gate().await;
+    let instruction = mint_chunk(
+        &test.program_id,
+        &gate,
+        mint,
+        token_account,
+        &test.payer().pubkey(),
+        &spl_token::id(),
+        amount,
+        true,
+    );
+    test.process(&[instruction], &[]).await
+}
+
+// The progress recorded in the mint's chunked mint PDA
+async fn chunked_mint_state(test: &mut TestContext, mint: &Pubkey) -> ChunkedMint {
+    let (address, _) = find_chunked_mint_address(&test.program_id, mint);
+    let account = test.account(&address).await.expect("chunked mint doesn't exist");
+    ChunkedMint::try_from_slice(&account.data).unwrap()
+}
+
+#[tokio::test]
+async fn chunks_never_mint_past_the_target() {
+    let mut test = TestContext::new().await;
+    let (mint, token_account) = start_chunked_launch(&mut test).await;
+    let first_chunk = total_supply() / 4;
+    mint_next_chunk(&mut test, &mint, &token_account, first_chunk).await.unwrap();
+
+    // One base unit too many on what would be the final chunk
+    let remaining = total_supply() - first_chunk;
+    let result = mint_next_chunk(&mut test, &mint, &token_account, remaining + 1).await;
+    assert_custom_error(result, CustomError::ChunkExceedsTarget);
+    assert_eq!(test.mint_state(&mint).await.supply, first_chunk);
+    assert_eq!(chunked_mint_state(&mut test, &mint).await.cumulative_minted, first_chunk);
+
+    // The exact remainder completes the launch and gives up the authority
+    mint_next_chunk(&mut test, &mint, &token_account, remaining).await.unwrap();
+    let chunked = chunked_mint_state(&mut test, &mint).await;
+    assert_eq!(chunked.cumulative_minted, chunked.target_total);
+    let mint_state = test.mint_state(&mint).await;
+    assert_eq!(mint_state.supply, total_supply());
+    assert!(mint_state.mint_authority.is_none());
+}
+
+// The `RemainingMintable` answer for `mint`
+async fn remaining_mintable_amount(test: &mut TestContext, mint: &Pubkey) -> u64 {
+    let instruction = remaining_mintable(&test.program_id, mint);
+    let (result, return_data) = test.simulate(&[instruction], &[]).await;
+    result.unwrap();
+    u64::from_le_bytes(return_data.try_into().unwrap())
+}
+
+#[tokio::test]
+async fn remaining_mintable_drops_to_zero_once_finalized() {
+    let mut test = TestContext::new().await;
+    let (mint, token_account) = start_chunked_launch(&mut test).await;
+    let first_chunk = total_supply() / 4;
+    mint_next_chunk(&mut test, &mint, &token_account, first_chunk).await.unwrap();
+
+    // The authority can still mint anything that fits in the supply
+    assert_eq!(remaining_mintable_amount(&mut test, &mint).await, u64::MAX - first_chunk);
+
+    mint_next_chunk(&mut test, &mint, &token_account, total_supply() - first_chunk)
+        .await
+        .unwrap();
+    assert_eq!(remaining_mintable_amount(&mut test, &mint).await, 0);
+}
+
+#[tokio::test]
+async fn cleanup_closes_the_transient_pdas_of_a_finalized_launch() {
+    let mut test = TestContext::new().await;
+    let authority = test.payer().pubkey();
+    let (mint, token_account) = start_chunked_launch(&mut test).await;
+    let instruction = create_distribution_list(&test.program_id, &mint, &authority, 1);
+    test.process(&[instruction], &[]).await.unwrap();
+    let (chunked, _) = find_chunked_mint_address(&test.program_id, &mint);
+    let (stats, _) = find_launch_stats_address(&test.program_id, &mint);
+    let (list, _) = find_distribution_list_address(&test.program_id, &mint);
+    let transient = [chunked, stats, list];
+
+    // Nothing can be closed while the launch still needs the PDAs
+    // Uneven chunks, so the last one isn't deduplicated as a replay of the first
+    let first_chunk = total_supply() / 4;
+    mint_next_chunk(&mut test, &mint, &token_account, first_chunk).await.unwrap();
+    let instruction = cleanup(&test.program_id, &mint, &authority, &authority, &transient);
+    let result = test.process(&[instruction], &[]).await;
+    assert_custom_error(result, CustomError::CannotCleanupBeforeFinalize);
+
+    mint_next_chunk(&mut test, &mint, &token_account, total_supply() - first_chunk).await.unwrap();
+    assert!(test.mint_state(&mint).await.mint_authority.is_none());
+    let receiver = Pubkey::new_unique();
+    let instruction = cleanup(&test.program_id, &mint, &authority, &receiver, &transient);
+    test.process(&[instruction], &[]).await.unwrap();
+
+    assert!(test.account(&receiver).await.unwrap().lamports > 0);
+    for pda in transient {
+        let account = test.account(&pda).await.unwrap_or_default();
+        assert_eq!(account.lamports, 0);
+        assert_eq!(account.owner, system_program::id());
+    }
+}
@@ -0,0 +1,351 @@
+// Shared fixtures for the program tests
+// `TestContext` starts a `ProgramTest` bank with this program loaded, a funded payer and helpers
+// to set up mints and token accounts, send instructions and read the resulting account states.
+
+#![allow(dead_code)]
+
+use borsh::BorshDeserialize;
+use fixed_supply_token::{
+    client::{launch_gate_accounts, set_config_admin},
+    error::CustomError,
+    pda::{find_associated_token_address, find_config_address, find_program_data_address},
+    processor::ASSOCIATED_TOKEN_PROGRAM_ID,
+    state::ProgramConfig,
+    DECIMALS,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    bpf_loader_upgradeable,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+    transaction::{Transaction, TransactionError},
+};
+use spl_token_2022::{
+    extension::StateWithExtensions,
+    instruction::{initialize_account3, initialize_mint2, mint_to, set_authority, AuthorityType},
+    state::{Account as TokenAccount, Mint},
+};
+
+/// Lamports every keypair made by `TestContext::funded_keypair` starts with
+pub const FUNDED_LAMPORTS: u64 = 10_000_000_000;
+
+/// A running test bank with this program loaded under `program_id`
+pub struct TestContext {
+    pub context: ProgramTestContext,
+    pub program_id: Pubkey,
+}
+
+impl TestContext {
+    /// Starts a bank with this program and the SPL programs `ProgramTest` ships with
+    pub async fn new() -> Self {
+        Self::start(Self::program_test()).await
+    }
+
+    /// `ProgramTest` with this program added, for tests that load extra programs before starting
+    pub fn program_test() -> ProgramTest {
+        ProgramTest::new(
+            "fixed_supply_token",
+            program_id(),
+            processor!(fixed_supply_token::process_instruction),
+        )
+    }
+
+    /// Starts the bank of a `ProgramTest` built with `TestContext::program_test`
+    pub async fn start(program_test: ProgramTest) -> Self {
+        Self { context: program_test.start_with_context().await, program_id: program_id() }
+    }
+
+    /// The funded payer of every transaction the context sends
+    pub fn payer(&self) -> Keypair {
+        self.context.payer.insecure_clone()
+    }
+
+    /// Sends `instructions` in one transaction paid by the payer, signed by `signers` as well
+    pub async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let transaction = self.transaction(instructions, signers).await?;
+        self.context.banks_client.process_transaction(transaction).await
+    }
+
+    /// Simulates `instructions` like `process`, returning the result and the return data
+    pub async fn simulate(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> (Result<(), TransactionError>, Vec<u8>) {
+        let transaction = self.transaction(instructions, signers).await.unwrap();
+        let simulation = self.context.banks_client.simulate_transaction(transaction).await.unwrap();
+        let return_data = simulation
+            .simulation_details
+            .and_then(|details| details.return_data)
+            .map_or_else(Vec::new, |return_data| return_data.data);
+        (simulation.result.expect("transaction wasn't simulated"), return_data)
+    }
+
+    /// Simulates `instructions` like `process`, returning the result and the program logs
+    pub async fn simulate_logs(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> (Result<(), TransactionError>, Vec<String>) {
+        let transaction = self.transaction(instructions, signers).await.unwrap();
+        let simulation = self.context.banks_client.simulate_transaction(transaction).await.unwrap();
+        let logs = simulation.simulation_details.map_or_else(Vec::new, |details| details.logs);
+        (simulation.result.expect("transaction wasn't simulated"), logs)
+    }
+
+    // `instructions` in one transaction paid by the payer and signed by `signers` as well
+    async fn transaction(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Transaction, BanksClientError> {
+        let payer = self.payer();
+        let mut all_signers = vec![&payer];
+        all_signers.extend(signers.iter().filter(|signer| signer.pubkey() != payer.pubkey()));
+        let blockhash = self.context.banks_client.get_latest_blockhash().await?;
+        Ok(Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            blockhash,
+        ))
+    }
+
+    /// Creates a keypair holding `FUNDED_LAMPORTS`, e.g. for an authority other than the payer
+    pub async fn funded_keypair(&mut self) -> Keypair {
+        let keypair = Keypair::new();
+        let payer = self.payer().pubkey();
+        let instruction = system_instruction::transfer(&payer, &keypair.pubkey(), FUNDED_LAMPORTS);
+        self.process(&[instruction], &[]).await.unwrap();
+        keypair
+    }
+
+    /// Writes this program's ProgramData account naming `upgrade_authority`, which `ProgramTest`
+    /// doesn't create for builtin programs
+    pub fn set_upgrade_authority(&mut self, upgrade_authority: &Pubkey) {
+        let (program_data, _) = find_program_data_address(&self.program_id);
+        // `UpgradeableLoaderState::ProgramData` tag, deployment slot and the optional authority
+        let mut data = 3u32.to_le_bytes().to_vec();
+        data.extend(0u64.to_le_bytes());
+        data.push(1);
+        data.extend(upgrade_authority.to_bytes());
+        let account = Account {
+            lamports: FUNDED_LAMPORTS,
+            data,
+            owner: bpf_loader_upgradeable::id(),
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.context.set_account(&program_data, &account.into());
+    }
+
+    /// Deploys an executable stand-in for an external program the config points to, e.g. the
+    /// oracle, that this program never invokes
+    pub fn deploy_program_stub(&mut self) -> Pubkey {
+        let program = Pubkey::new_unique();
+        let account = Account {
+            lamports: FUNDED_LAMPORTS,
+            data: vec![0; 36],
+            owner: bpf_loader_upgradeable::id(),
+            executable: true,
+            rent_epoch: 0,
+        };
+        self.context.set_account(&program, &account.into());
+        program
+    }
+
+    /// Creates the program's config with `admin` as its admin, bootstrapped by the payer as the
+    /// upgrade authority
+    pub async fn initialize_config(&mut self, admin: &Pubkey) {
+        let payer = self.payer().pubkey();
+        self.set_upgrade_authority(&payer);
+        let instruction = set_config_admin(&self.program_id, &payer, admin);
+        self.process(&[instruction], &[]).await.unwrap();
+    }
+
+    /// Launch gate accounts for the program's current config, for the builders of launching
+    /// instructions
+    pub async fn launch_gate(&mut self) -> Vec<AccountMeta> {
+        self.launch_gate_with_approvals(&[]).await
+    }
+
+    /// Launch gate accounts like `launch_gate`, with the oracle approvals of the launched mints
+    pub async fn launch_gate_with_approvals(&mut self, approvals: &[Pubkey]) -> Vec<AccountMeta> {
+        let (address, _) = find_config_address(&self.program_id);
+        let config = self
+            .account(&address)
+            .await
+            .map(|account| ProgramConfig::try_from_slice(&account.data).unwrap());
+        launch_gate_accounts(&self.program_id, config.as_ref(), approvals)
+    }
+
+    /// Creates and initializes a mint under `token_program` with `DECIMALS` decimals
+    pub async fn create_mint(
+        &mut self,
+        token_program: &Pubkey,
+        mint_authority: &Pubkey,
+        freeze_authority: Option<&Pubkey>,
+    ) -> Keypair {
+        self.create_mint_with_decimals(token_program, mint_authority, freeze_authority, DECIMALS)
+            .await
+    }
+
+    /// Same as `create_mint` with the given decimals
+    pub async fn create_mint_with_decimals(
+        &mut self,
+        token_program: &Pubkey,
+        mint_authority: &Pubkey,
+        freeze_authority: Option<&Pubkey>,
+        decimals: u8,
+    ) -> Keypair {
+        let mint = Keypair::new();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        let instructions = [
+            system_instruction::create_account(
+                &self.payer().pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(Mint::LEN),
+                Mint::LEN as u64,
+                token_program,
+            ),
+            initialize_mint2(
+                token_program,
+                &mint.pubkey(),
+                mint_authority,
+                freeze_authority,
+                decimals,
+            )
+            .unwrap(),
+        ];
+        self.process(&instructions, &[&mint]).await.unwrap();
+        mint
+    }
+
+    /// Creates `owner`'s associated token account for `mint` and returns its address
+    pub async fn create_ata(
+        &mut self,
+        owner: &Pubkey,
+        mint: &Pubkey,
+        token_program: &Pubkey,
+    ) -> Pubkey {
+        let address = find_associated_token_address(owner, mint, token_program);
+        let instruction = Instruction {
+            program_id: ASSOCIATED_TOKEN_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(self.payer().pubkey(), true),
+                AccountMeta::new(address, false),
+                AccountMeta::new_readonly(*owner, false),
+                AccountMeta::new_readonly(*mint, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(*token_program, false),
+            ],
+            // `Create`
+            data: vec![0],
+        };
+        self.process(&[instruction], &[]).await.unwrap();
+        address
+    }
+
+    /// Creates a token account for `mint` owned by `owner` at a fresh, non-associated address
+    pub async fn create_token_account(
+        &mut self,
+        owner: &Pubkey,
+        mint: &Pubkey,
+        token_program: &Pubkey,
+    ) -> Pubkey {
+        let account = Keypair::new();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        let instructions = [
+            system_instruction::create_account(
+                &self.payer().pubkey(),
+                &account.pubkey(),
+                rent.minimum_balance(TokenAccount::LEN),
+                TokenAccount::LEN as u64,
+                token_program,
+            ),
+            initialize_account3(token_program, &account.pubkey(), mint, owner).unwrap(),
+        ];
+        self.process(&instructions, &[&account]).await.unwrap();
+        account.pubkey()
+    }
+
+    /// Mints `amount` base units outside this program, signed by the mint's `authority`
+    pub async fn mint_to(
+        &mut self,
+        token_program: &Pubkey,
+        mint: &Pubkey,
+        destination: &Pubkey,
+        authority: &Keypair,
+        amount: u64,
+    ) {
+        let instruction =
+            mint_to(token_program, mint, destination, &authority.pubkey(), &[], amount).unwrap();
+        self.process(&[instruction], &[authority]).await.unwrap();
+    }
+
+    /// Revokes a mint's authority outside this program, signed by the current `authority`
+    pub async fn revoke_mint_authority(
+        &mut self,
+        token_program: &Pubkey,
+        mint: &Pubkey,
+        authority: &Keypair,
+    ) {
+        let instruction = set_authority(
+            token_program,
+            mint,
+            None,
+            AuthorityType::MintTokens,
+            &authority.pubkey(),
+            &[],
+        )
+        .unwrap();
+        self.process(&[instruction], &[authority]).await.unwrap();
+    }
+
+    /// The account at `address`, if it exists
+    pub async fn account(&mut self, address: &Pubkey) -> Option<Account> {
+        self.context.banks_client.get_account(*address).await.unwrap()
+    }
+
+    /// The state of the mint at `address`, ignoring any Token-2022 extensions
+    pub async fn mint_state(&mut self, address: &Pubkey) -> Mint {
+        let account = self.account(address).await.expect("mint doesn't exist");
+        StateWithExtensions::<Mint>::unpack(&account.data).unwrap().base
+    }
+
+    /// The state of the token account at `address`, ignoring any Token-2022 extensions
+    pub async fn token_account_state(&mut self, address: &Pubkey) -> TokenAccount {
+        let account = self.account(address).await.expect("token account doesn't exist");
+        StateWithExtensions::<TokenAccount>::unpack(&account.data).unwrap().base
+    }
+}
+
+/// Fixed id the program is loaded under
+pub fn program_id() -> Pubkey {
+    Pubkey::new_from_array([7; 32])
+}
+
+/// The `CustomError` a failed transaction returned, if it failed with one of ours
+pub fn custom_error(error: BanksClientError) -> Option<CustomError> {
+    match error.unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            CustomError::from_code(code)
+        }
+        _ => None,
+    }
+}
+
+/// Asserts a transaction failed with `expected`
+pub fn assert_custom_error(result: Result<(), BanksClientError>, expected: CustomError) {
+    let error = result.expect_err("transaction should have failed");
+    let actual = custom_error(error);
+    assert_eq!(actual.map(CustomError::code), Some(expected.code()), "got {:?}", actual);
+}
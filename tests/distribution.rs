@@ -0,0 +1,315 @@
+// Program tests of the distribution list instructions
+
+mod common;
+
+use common::{assert_custom_error, TestContext};
+use fixed_supply_token::{
+    client::{
+        create_distribution_list, distribute, distribute_chunk, distribute_to_owners,
+        write_distribution_list,
+    },
+    error::CustomError,
+    instruction::{
+        DistributeArgs, OwnerAllocation, WriteDistributionListArgs, MAX_DISTRIBUTION_CHUNK,
+    },
+    state::DistributionEntry,
+    DECIMALS, TOTAL_SUPPLY,
+};
+use solana_sdk::{
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Signer,
+    transaction::TransactionError,
+};
+
+// Entries written per `WriteDistributionList` transaction
+const WRITE_BATCH: usize = 10;
+
+// The default supply in base units
+fn total_supply() -> u64 {
+    TOTAL_SUPPLY * 10u64.pow(DECIMALS as u32)
+}
+
+// Creates an SPL Token mint controlled by the payer and a distribution list for it holding
+// `amounts`, one fresh recipient token account per amount. Returns the mint and destinations.
+async fn setup_distribution(test: &mut TestContext, amounts: &[u64]) -> (Pubkey, Vec<Pubkey>) {
+    let authority = test.payer().pubkey();
+    let token_program = spl_token::id();
+    let mint = test.create_mint(&token_program, &authority, None).await.pubkey();
+
+    let mut destinations = Vec::new();
+    for _ in amounts {
+        let owner = Pubkey::new_unique();
+        destinations.push(test.create_ata(&owner, &mint, &token_program).await);
+    }
+    let entries: Vec<_> = destinations
+        .iter()
+        .zip(amounts)
+        .map(|(destination, amount)| DistributionEntry {
+            destination: *destination,
+            amount: *amount,
+        })
+        .collect();
+    write_list(test, &mint, &entries).await;
+    (mint, destinations)
+}
+
+// Creates the mint's distribution list and writes `entries` into it in batches
+async fn write_list(test: &mut TestContext, mint: &Pubkey, entries: &[DistributionEntry]) {
+    let program_id = test.program_id;
+    let authority = test.payer().pubkey();
+    let instruction = create_distribution_list(&program_id, mint, &authority, entries.len() as u16);
+    test.process(&[instruction], &[]).await.unwrap();
+    for (batch, chunk) in entries.chunks(WRITE_BATCH).enumerate() {
+        let args = WriteDistributionListArgs {
+            offset: (batch * WRITE_BATCH) as u16,
+            entries: chunk.to_vec(),
+        };
+        let instruction = write_distribution_list(&program_id, mint, &authority, args);
+        test.process(&[instruction], &[]).await.unwrap();
+    }
+}
+
+// `DistributeChunk` of the payer's mint to `destinations`, starting at `start_index` of the list
+async fn chunk_instruction(
+    test: &mut TestContext,
+    mint: &Pubkey,
+    start_index: usize,
+    destinations: &[Pubkey],
+) -> Instruction {
+    let gate = test.launch_gate().await;
+    distribute_chunk(
+        &test.program_id,
+        &gate,
+        mint,
+        &test.payer().pubkey(),
+        &spl_token::id(),
+        start_index as u16,
+        destinations,
+        false,
+    )
+}
+
+// Splits the supply by weight, rounding each share down and giving the remainder to the first
+fn weighted_amounts(weights: &[u128]) -> Vec<u64> {
+    let total_weight: u128 = weights.iter().sum();
+    let mut amounts: Vec<u64> = weights
+        .iter()
+        .map(|weight| (total_supply() as u128 * weight / total_weight) as u64)
+        .collect();
+    amounts[0] += total_supply() - amounts.iter().sum::<u64>();
+    amounts
+}
+
+#[tokio::test]
+async fn distribute_mints_the_full_supply_from_a_written_list() {
+    let mut test = TestContext::new().await;
+    // More entries than one write batch, so the list is assembled over several writes
+    let amounts = weighted_amounts(&[5, 3, 2, 1, 1, 1, 1, 1, 1, 1, 1, 1]);
+    let (mint, destinations) = setup_distribution(&mut test, &amounts).await;
+
+    let args = DistributeArgs { allow_zero_amounts: false, num_recipients: amounts.len() as u16 };
+    let gate = test.launch_gate().await;
+    let instruction = distribute(
+        &test.program_id,
+        &gate,
+        &mint,
+        &test.payer().pubkey(),
+        &spl_token::id(),
+        &destinations,
+        args,
+    );
+    test.process(&[instruction], &[]).await.unwrap();
+
+    for (destination, amount) in destinations.iter().zip(&amounts) {
+        assert_eq!(test.token_account_state(destination).await.amount, *amount);
+    }
+    let mint_state = test.mint_state(&mint).await;
+    assert_eq!(mint_state.supply, total_supply());
+    assert!(mint_state.mint_authority.is_none());
+}
+
+#[tokio::test]
+async fn distribute_rejects_a_weight_that_rounds_to_zero() {
+    let mut test = TestContext::new().await;
+    // The second weight is far too small for a share of even one base unit
+    let amounts = weighted_amounts(&[999_999_999_999_999_999, 1]);
+    assert_eq!(amounts[1], 0);
+    let (mint, destinations) = setup_distribution(&mut test, &amounts).await;
+
+    let args = DistributeArgs { allow_zero_amounts: false, num_recipients: 2 };
+    let gate = test.launch_gate().await;
+    let instruction = distribute(
+        &test.program_id,
+        &gate,
+        &mint,
+        &test.payer().pubkey(),
+        &spl_token::id(),
+        &destinations,
+        args,
+    );
+    let result = test.process(&[instruction], &[]).await;
+
+    assert_custom_error(result, CustomError::ZeroAmountRecipient);
+    assert_eq!(test.mint_state(&mint).await.supply, 0);
+}
+
+#[tokio::test]
+async fn distribute_accepts_zero_amounts_when_allowed() {
+    let mut test = TestContext::new().await;
+    let amounts = weighted_amounts(&[999_999_999_999_999_999, 1]);
+    let (mint, destinations) = setup_distribution(&mut test, &amounts).await;
+
+    let args = DistributeArgs { allow_zero_amounts: true, num_recipients: 2 };
+    let gate = test.launch_gate().await;
+    let instruction = distribute(
+        &test.program_id,
+        &gate,
+        &mint,
+        &test.payer().pubkey(),
+        &spl_token::id(),
+        &destinations,
+        args,
+    );
+    test.process(&[instruction], &[]).await.unwrap();
+
+    assert_eq!(test.token_account_state(&destinations[0]).await.amount, total_supply());
+    assert_eq!(test.token_account_state(&destinations[1]).await.amount, 0);
+    assert!(test.mint_state(&mint).await.mint_authority.is_none());
+}
+
+#[tokio::test]
+async fn distribute_rejects_a_duplicated_destination() {
+    let mut test = TestContext::new().await;
+    let authority = test.payer().pubkey();
+    let token_program = spl_token::id();
+    let mint = test.create_mint(&token_program, &authority, None).await.pubkey();
+    let destination = test.create_ata(&Pubkey::new_unique(), &mint, &token_program).await;
+    let half = total_supply() / 2;
+    let entries = [
+        DistributionEntry { destination, amount: half },
+        DistributionEntry { destination, amount: total_supply() - half },
+    ];
+    write_list(&mut test, &mint, &entries).await;
+
+    let args = DistributeArgs { allow_zero_amounts: false, num_recipients: 2 };
+    let gate = test.launch_gate().await;
+    let instruction = distribute(
+        &test.program_id,
+        &gate,
+        &mint,
+        &authority,
+        &token_program,
+        &[destination, destination],
+        args,
+    );
+    let result = test.process(&[instruction], &[]).await;
+
+    assert_custom_error(result, CustomError::DuplicateDestination);
+    assert_eq!(test.mint_state(&mint).await.supply, 0);
+}
+
+#[tokio::test]
+async fn distribute_rejects_fewer_destinations_than_declared() {
+    let mut test = TestContext::new().await;
+    let amounts = weighted_amounts(&[1; 3]);
+    let (mint, destinations) = setup_distribution(&mut test, &amounts).await;
+
+    // The client declares the whole list but drops a destination account
+    let args = DistributeArgs { allow_zero_amounts: false, num_recipients: 3 };
+    let gate = test.launch_gate().await;
+    let instruction = distribute(
+        &test.program_id,
+        &gate,
+        &mint,
+        &test.payer().pubkey(),
+        &spl_token::id(),
+        &destinations[..2],
+        args,
+    );
+    let result = test.process(&[instruction], &[]).await;
+
+    assert_custom_error(result, CustomError::RecipientCountMismatch);
+    assert_eq!(test.mint_state(&mint).await.supply, 0);
+}
+
+#[tokio::test]
+async fn distribute_to_owners_rejects_fewer_accounts_than_declared() {
+    let mut test = TestContext::new().await;
+    let authority = test.payer().pubkey();
+    let token_program = spl_token::id();
+    let mint = test.create_mint(&token_program, &authority, None).await.pubkey();
+    let amounts = weighted_amounts(&[1; 2]);
+    let recipients: Vec<_> = amounts
+        .iter()
+        .map(|amount| OwnerAllocation { owner: Pubkey::new_unique(), amount: *amount })
+        .collect();
+
+    let gate = test.launch_gate().await;
+    let mut instruction = distribute_to_owners(
+        &test.program_id,
+        &gate,
+        &mint,
+        &authority,
+        &token_program,
+        &recipients,
+    );
+    // Drop the last recipient's owner and token account, keeping both allocations
+    instruction.accounts.truncate(instruction.accounts.len() - 2);
+    let result = test.process(&[instruction], &[]).await;
+
+    assert_custom_error(result, CustomError::RecipientCountMismatch);
+    assert_eq!(test.mint_state(&mint).await.supply, 0);
+}
+
+#[tokio::test]
+async fn distribute_chunk_completes_a_list_over_several_chunks() {
+    let mut test = TestContext::new().await;
+    let amounts = weighted_amounts(&[1; 25]);
+    let (mint, destinations) = setup_distribution(&mut test, &amounts).await;
+
+    let chunks: Vec<_> = destinations.chunks(MAX_DISTRIBUTION_CHUNK).collect();
+    assert_eq!(chunks.len(), 3);
+    for (index, chunk) in chunks.iter().enumerate() {
+        let start_index = index * MAX_DISTRIBUTION_CHUNK;
+        let instruction = chunk_instruction(&mut test, &mint, start_index, chunk).await;
+        test.process(&[instruction], &[]).await.unwrap();
+
+        // The authority is only given up once the last recipient has been minted to
+        let mint_state = test.mint_state(&mint).await;
+        let distributed: u64 = amounts[..start_index + chunk.len()].iter().sum();
+        assert_eq!(mint_state.supply, distributed);
+        assert_eq!(mint_state.mint_authority.is_none(), index == chunks.len() - 1);
+    }
+
+    assert_eq!(test.mint_state(&mint).await.supply, total_supply());
+    for (destination, amount) in destinations.iter().zip(&amounts) {
+        assert_eq!(test.token_account_state(destination).await.amount, *amount);
+    }
+
+    let instruction = chunk_instruction(&mut test, &mint, 20, &destinations[20..]).await;
+    let result = test.process(&[instruction], &[]).await;
+    assert_custom_error(result, CustomError::DistributionAlreadyComplete);
+}
+
+#[tokio::test]
+async fn distribute_chunk_rejects_a_replayed_chunk() {
+    let mut test = TestContext::new().await;
+    let amounts = weighted_amounts(&[1; 25]);
+    let (mint, destinations) = setup_distribution(&mut test, &amounts).await;
+    let first_chunk = &destinations[..MAX_DISTRIBUTION_CHUNK];
+    let instruction = chunk_instruction(&mut test, &mint, 0, first_chunk).await;
+    test.process(&[instruction], &[]).await.unwrap();
+
+    // Same chunk again, in a new transaction so it isn't deduplicated as the same signature
+    let instruction = chunk_instruction(&mut test, &mint, 0, first_chunk).await;
+    test.context.get_new_latest_blockhash().await.unwrap();
+    let result = test.process(&[instruction], &[]).await;
+
+    assert_eq!(
+        result.map_err(|error| error.unwrap()),
+        Err(TransactionError::InstructionError(0, InstructionError::InvalidArgument))
+    );
+    assert_eq!(test.token_account_state(&destinations[0]).await.amount, amounts[0]);
+    assert!(test.mint_state(&mint).await.mint_authority.is_some());
+}
@@ -0,0 +1,688 @@
+// Program tests of the launch instructions
+
+mod common;
+
+use common::{assert_custom_error, TestContext};
+use fixed_supply_token::{
+    client::{
+        batch_initialize, initialize_fixed_supply, initialize_with_pda_authority,
+        initialize_with_timelock_freeze, initialize_with_ui_supply, revoke_authorities,
+        revoke_authority, token2022_mint_len, treasury_and_liquidity, update_config,
+        verify_supply_hash,
+    },
+    error::CustomError,
+    instruction::{
+        BatchInitializeArgs, InitializeArgs, PdaAuthorityArgs, RevokeAuthorityArgs,
+        TokenInstruction, TreasuryAndLiquidityArgs, UiSupplyArgs, CAPABILITY_CREATE_ATA,
+        CAPABILITY_REVOKE_FREEZE,
+    },
+    pda::{
+        find_associated_token_address, find_mint_authority_address,
+        find_timelock_authority_address,
+    },
+    state::ConfigSettings,
+    DECIMALS, ERROR_CODE_OFFSET, TOTAL_SUPPLY,
+};
+use solana_program_test::BanksClientError;
+use solana_sdk::{
+    account::Account,
+    instruction::{Instruction, InstructionError},
+    program_option::COption,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+    transaction::TransactionError,
+};
+use spl_token_2022::{
+    extension::{
+        transfer_fee::{instruction::initialize_transfer_fee_config, TransferFeeConfig},
+        BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+    },
+    instruction::{initialize_mint2, set_authority, AuthorityType},
+    state::Mint,
+};
+use std::slice;
+
+// Creates an SPL Token mint with the given decimals controlled by the payer, and the payer's
+// associated token account for it
+async fn setup_mint(test: &mut TestContext, decimals: u8) -> (Pubkey, Pubkey) {
+    let authority = test.payer().pubkey();
+    let token_program = spl_token::id();
+    let mint = test.create_mint_with_decimals(&token_program, &authority, None, decimals).await;
+    let token_account = test.create_ata(&authority, &mint.pubkey(), &token_program).await;
+    (mint.pubkey(), token_account)
+}
+
+// Launches `mint` into `token_account` with the payer as the authority
+async fn launch(
+    test: &mut TestContext,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    args: InitializeArgs,
+) -> Result<(), BanksClientError> {
+    let gate = test.launch_gate().await;
+    let instruction = initialize_fixed_supply(
+        &test.program_id,
+        &gate,
+        mint,
+        token_account,
+        &test.payer().pubkey(),
+        &spl_token::id(),
+        None,
+        args,
+        &[],
+    );
+    test.process(&[instruction], &[]).await
+}
+
+#[tokio::test]
+async fn initialize_fixed_supply_mints_the_supply_and_revokes_the_authority() {
+    let mut test = TestContext::new().await;
+    let (mint, token_account) = setup_mint(&mut test, DECIMALS).await;
+
+    launch(&mut test, &mint, &token_account, InitializeArgs::default()).await.unwrap();
+
+    let supply = TOTAL_SUPPLY * 10u64.pow(DECIMALS as u32);
+    let mint_state = test.mint_state(&mint).await;
+    assert_eq!(mint_state.supply, supply);
+    assert!(mint_state.mint_authority.is_none());
+    assert_eq!(test.token_account_state(&token_account).await.amount, supply);
+}
+
+// Launches `mint` into `token_account` bundled with a `VerifySupplyHash` of `committed_supply`
+async fn launch_committed_to(
+    test: &mut TestContext,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    committed_supply: u64,
+) -> Result<(), BanksClientError> {
+    let gate = test.launch_gate().await;
+    let instructions = [
+        initialize_fixed_supply(
+            &test.program_id,
+            &gate,
+            mint,
+            token_account,
+            &test.payer().pubkey(),
+            &spl_token::id(),
+            None,
+            InitializeArgs::default(),
+            &[],
+        ),
+        verify_supply_hash(&test.program_id, mint, committed_supply, DECIMALS),
+    ];
+    test.process(&instructions, &[]).await
+}
+
+#[tokio::test]
+async fn supply_hash_accepts_the_committed_supply() {
+    let mut test = TestContext::new().await;
+    let (mint, token_account) = setup_mint(&mut test, DECIMALS).await;
+    let supply = TOTAL_SUPPLY * 10u64.pow(DECIMALS as u32);
+
+    launch_committed_to(&mut test, &mint, &token_account, supply).await.unwrap();
+
+    assert_eq!(test.mint_state(&mint).await.supply, supply);
+}
+
+#[tokio::test]
+async fn supply_hash_mismatch_fails_the_whole_launch() {
+    let mut test = TestContext::new().await;
+    let (mint, token_account) = setup_mint(&mut test, DECIMALS).await;
+    let supply = TOTAL_SUPPLY * 10u64.pow(DECIMALS as u32);
+
+    let result = launch_committed_to(&mut test, &mint, &token_account, supply - 1).await;
+
+    assert_custom_error(result, CustomError::SupplyHashMismatch);
+    let mint_state = test.mint_state(&mint).await;
+    assert_eq!(mint_state.supply, 0);
+    assert!(mint_state.mint_authority.is_some());
+}
+
+// `BatchInitialize` of the (mint, token account) `pairs` with the payer as the authority
+async fn batch_launch_instruction(
+    test: &mut TestContext,
+    pairs: &[(Pubkey, Pubkey)],
+    mints: Vec<InitializeArgs>,
+) -> Instruction {
+    let gate = test.launch_gate().await;
+    batch_initialize(
+        &test.program_id,
+        &gate,
+        &test.payer().pubkey(),
+        &spl_token::id(),
+        pairs,
+        BatchInitializeArgs { mints },
+    )
+}
+
+#[tokio::test]
+async fn batch_initialize_launches_every_mint() {
+    let mut test = TestContext::new().await;
+    let pairs = [setup_mint(&mut test, DECIMALS).await, setup_mint(&mut test, 6).await];
+    let mints = vec![
+        InitializeArgs::default(),
+        InitializeArgs { decimals: Some(6), ..InitializeArgs::default() },
+    ];
+
+    let instruction = batch_launch_instruction(&mut test, &pairs, mints).await;
+    test.process(&[instruction], &[]).await.unwrap();
+
+    for ((mint, token_account), decimals) in pairs.iter().zip([DECIMALS, 6]) {
+        let supply = TOTAL_SUPPLY * 10u64.pow(decimals as u32);
+        let mint_state = test.mint_state(mint).await;
+        assert_eq!(mint_state.supply, supply);
+        assert!(mint_state.mint_authority.is_none());
+        assert_eq!(test.token_account_state(token_account).await.amount, supply);
+    }
+}
+
+#[tokio::test]
+async fn batch_initialize_reports_the_failing_mint_and_launches_none() {
+    let mut test = TestContext::new().await;
+    let pairs = [setup_mint(&mut test, DECIMALS).await, setup_mint(&mut test, 3).await];
+    let mints = vec![
+        InitializeArgs::default(),
+        InitializeArgs { decimals: Some(3), ..InitializeArgs::default() },
+    ];
+
+    let instruction = batch_launch_instruction(&mut test, &pairs, mints).await;
+    let (result, logs) = test.simulate_logs(slice::from_ref(&instruction), &[]).await;
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(CustomError::DecimalsNotAllowed.code())
+        ))
+    );
+    assert!(logs.iter().any(|log| log.ends_with("Batch launch failed at mint index 1")));
+
+    // The first mint's launch is rolled back with the rest of the batch
+    let result = test.process(&[instruction], &[]).await;
+    assert_custom_error(result, CustomError::DecimalsNotAllowed);
+    let (first_mint, _) = pairs[0];
+    assert_eq!(test.mint_state(&first_mint).await.supply, 0);
+}
+
+#[tokio::test]
+async fn launch_rejects_the_system_program_as_the_authority() {
+    let mut test = TestContext::new().await;
+    let (mint, token_account) = setup_mint(&mut test, DECIMALS).await;
+    let gate = test.launch_gate().await;
+    let mut instruction = initialize_fixed_supply(
+        &test.program_id,
+        &gate,
+        &mint,
+        &token_account,
+        &system_program::id(),
+        &spl_token::id(),
+        None,
+        InitializeArgs::default(),
+        &[],
+    );
+    // Nobody can sign for the system program, so the transaction can only carry it unsigned
+    for account in &mut instruction.accounts {
+        account.is_signer &= account.pubkey != system_program::id();
+    }
+
+    let result = test.process(&[instruction], &[]).await;
+
+    assert_custom_error(result, CustomError::InvalidAuthorityAccount);
+    assert_eq!(test.mint_state(&mint).await.supply, 0);
+}
+
+// Splits a fresh mint's supply between `treasury` and `liquidity` at `treasury_bps`
+async fn launch_treasury_and_liquidity(
+    test: &mut TestContext,
+    mint: &Pubkey,
+    treasury: &Pubkey,
+    liquidity: &Pubkey,
+    treasury_bps: u16,
+) -> Result<(), BanksClientError> {
+    let gate = test.launch_gate().await;
+    let instruction = treasury_and_liquidity(
+        &test.program_id,
+        &gate,
+        mint,
+        treasury,
+        liquidity,
+        &test.payer().pubkey(),
+        &spl_token::id(),
+        TreasuryAndLiquidityArgs { treasury_bps, pool: None },
+        &[],
+    );
+    test.process(&[instruction], &[]).await
+}
+
+#[tokio::test]
+async fn treasury_and_liquidity_tranches_add_up_to_the_supply() {
+    let mut test = TestContext::new().await;
+    let authority = test.payer().pubkey();
+    let supply = TOTAL_SUPPLY * 10u64.pow(DECIMALS as u32);
+
+    for treasury_bps in [1, 2_500, 3_333, 9_999] {
+        let (mint, treasury) = setup_mint(&mut test, DECIMALS).await;
+        let liquidity = test.create_token_account(&authority, &mint, &spl_token::id()).await;
+        launch_treasury_and_liquidity(&mut test, &mint, &treasury, &liquidity, treasury_bps)
+            .await
+            .unwrap();
+
+        let treasury_amount = test.token_account_state(&treasury).await.amount;
+        let liquidity_amount = test.token_account_state(&liquidity).await.amount;
+        assert_eq!(treasury_amount, supply / 10_000 * treasury_bps as u64);
+        assert_eq!(treasury_amount + liquidity_amount, supply);
+        let mint_state = test.mint_state(&mint).await;
+        assert_eq!(mint_state.supply, supply);
+        assert!(mint_state.mint_authority.is_none());
+    }
+}
+
+#[tokio::test]
+async fn treasury_and_liquidity_needs_two_accounts() {
+    let mut test = TestContext::new().await;
+    let (mint, token_account) = setup_mint(&mut test, DECIMALS).await;
+
+    let result =
+        launch_treasury_and_liquidity(&mut test, &mint, &token_account, &token_account, 5_000)
+            .await;
+
+    assert_eq!(
+        result.map_err(|error| error.unwrap()),
+        Err(TransactionError::InstructionError(0, InstructionError::InvalidArgument))
+    );
+    assert_eq!(test.mint_state(&mint).await.supply, 0);
+}
+
+#[tokio::test]
+async fn launch_rejects_the_native_mint() {
+    let mut test = TestContext::new().await;
+    let authority = test.payer().pubkey();
+    let mint = spl_token::native_mint::id();
+    let token_account = test.create_ata(&authority, &mint, &spl_token::id()).await;
+
+    let result = launch(&mut test, &mint, &token_account, InitializeArgs::default()).await;
+
+    assert_custom_error(result, CustomError::CannotUseNativeMint);
+}
+
+#[tokio::test]
+async fn launch_rejects_a_token_account_of_the_wrong_size() {
+    let mut test = TestContext::new().await;
+    let (mint, _) = setup_mint(&mut test, DECIMALS).await;
+    // Owned by SPL Token, but a byte short of a token account
+    let token_account = Pubkey::new_unique();
+    let account = Account {
+        lamports: 1_000_000_000,
+        data: vec![0; spl_token::state::Account::LEN - 1],
+        owner: spl_token::id(),
+        executable: false,
+        rent_epoch: 0,
+    };
+    test.context.set_account(&token_account, &account.into());
+
+    let result = launch(&mut test, &mint, &token_account, InitializeArgs::default()).await;
+
+    assert_custom_error(result, CustomError::TokenAccountWrongSize);
+    assert_eq!(test.mint_state(&mint).await.supply, 0);
+}
+
+#[tokio::test]
+async fn launch_accepts_allowed_decimals() {
+    let mut test = TestContext::new().await;
+    let (mint, token_account) = setup_mint(&mut test, 6).await;
+
+    let args = InitializeArgs { decimals: Some(6), ..InitializeArgs::default() };
+    launch(&mut test, &mint, &token_account, args).await.unwrap();
+
+    assert_eq!(test.mint_state(&mint).await.supply, TOTAL_SUPPLY * 10u64.pow(6));
+}
+
+#[tokio::test]
+async fn launch_rejects_decimals_outside_the_allowed_set() {
+    let mut test = TestContext::new().await;
+    let (mint, token_account) = setup_mint(&mut test, 3).await;
+
+    let args = InitializeArgs { decimals: Some(3), ..InitializeArgs::default() };
+    let result = launch(&mut test, &mint, &token_account, args).await;
+
+    assert_custom_error(result, CustomError::DecimalsNotAllowed);
+}
+
+#[tokio::test]
+async fn launch_rejects_an_already_finalized_mint() {
+    let mut test = TestContext::new().await;
+    let authority = test.payer();
+    let token_program = spl_token::id();
+    let (mint, token_account) = setup_mint(&mut test, DECIMALS).await;
+    // A token that already launched: some supply and no mint authority left
+    test.mint_to(&token_program, &mint, &token_account, &authority, 1_000).await;
+    test.revoke_mint_authority(&token_program, &mint, &authority).await;
+
+    let result = launch(&mut test, &mint, &token_account, InitializeArgs::default()).await;
+
+    assert_custom_error(result, CustomError::MintAlreadyFinalized);
+}
+
+#[tokio::test]
+async fn create_ata_capability_creates_the_destination() {
+    let mut test = TestContext::new().await;
+    let authority = test.payer().pubkey();
+    let token_program = spl_token::id();
+    let mint = test.create_mint(&token_program, &authority, None).await.pubkey();
+    let token_account = find_associated_token_address(&authority, &mint, &token_program);
+    assert!(test.account(&token_account).await.is_none());
+
+    let args = InitializeArgs { capabilities: CAPABILITY_CREATE_ATA, ..InitializeArgs::default() };
+    launch(&mut test, &mint, &token_account, args).await.unwrap();
+
+    let supply = TOTAL_SUPPLY * 10u64.pow(DECIMALS as u32);
+    assert_eq!(test.token_account_state(&token_account).await.amount, supply);
+}
+
+#[tokio::test]
+async fn revoke_freeze_capability_revokes_the_freeze_authority() {
+    let mut test = TestContext::new().await;
+    let authority = test.payer().pubkey();
+    let token_program = spl_token::id();
+    let mint = test.create_mint(&token_program, &authority, Some(&authority)).await.pubkey();
+    let token_account = test.create_ata(&authority, &mint, &token_program).await;
+
+    let args =
+        InitializeArgs { capabilities: CAPABILITY_REVOKE_FREEZE, ..InitializeArgs::default() };
+    let gate = test.launch_gate().await;
+    let instruction = initialize_fixed_supply(
+        &test.program_id,
+        &gate,
+        &mint,
+        &token_account,
+        &authority,
+        &token_program,
+        Some(&authority),
+        args,
+        &[],
+    );
+    test.process(&[instruction], &[]).await.unwrap();
+
+    let mint_state = test.mint_state(&mint).await;
+    assert!(mint_state.mint_authority.is_none());
+    assert!(mint_state.freeze_authority.is_none());
+}
+
+#[tokio::test]
+async fn launch_rejects_an_external_mint_with_supply() {
+    let mut test = TestContext::new().await;
+    let authority = test.payer();
+    let token_program = spl_token::id();
+    let (mint, token_account) = setup_mint(&mut test, DECIMALS).await;
+    // The authority is still in place, but someone else already holds tokens of the mint
+    let holder = test.create_ata(&Pubkey::new_unique(), &mint, &token_program).await;
+    test.mint_to(&token_program, &mint, &holder, &authority, 1).await;
+
+    let result = launch(&mut test, &mint, &token_account, InitializeArgs::default()).await;
+
+    assert_custom_error(result, CustomError::MintSupplyNotZero);
+    assert_eq!(test.mint_state(&mint).await.supply, 1);
+}
+
+// Launches a mint whose freeze authority is the payer with `InitializeWithTimelockFreeze`
+// towards `timelock_program`, returning the mint and the result
+async fn launch_with_timelock_freeze(
+    test: &mut TestContext,
+    timelock_program: &Pubkey,
+) -> (Pubkey, Result<(), BanksClientError>) {
+    let authority = test.payer().pubkey();
+    let token_program = spl_token::id();
+    let mint = test.create_mint(&token_program, &authority, Some(&authority)).await.pubkey();
+    let token_account = test.create_ata(&authority, &mint, &token_program).await;
+    let gate = test.launch_gate().await;
+    let instruction = initialize_with_timelock_freeze(
+        &test.program_id,
+        &gate,
+        &mint,
+        &token_account,
+        &authority,
+        &token_program,
+        timelock_program,
+        None,
+    );
+    (mint, test.process(&[instruction], &[]).await)
+}
+
+#[tokio::test]
+async fn timelock_freeze_hands_the_freeze_authority_to_the_configured_timelock() {
+    let mut test = TestContext::new().await;
+    let admin = test.payer().pubkey();
+    let timelock_program = test.deploy_program_stub();
+    test.initialize_config(&admin).await;
+    let settings = ConfigSettings { timelock_program, ..ConfigSettings::default() };
+    let instruction = update_config(&test.program_id, &admin, settings);
+    test.process(&[instruction], &[]).await.unwrap();
+
+    let (mint, result) = launch_with_timelock_freeze(&mut test, &timelock_program).await;
+    result.unwrap();
+
+    let (timelock, _) = find_timelock_authority_address(&timelock_program, &mint);
+    let mint_state = test.mint_state(&mint).await;
+    assert_eq!(mint_state.freeze_authority, COption::Some(timelock));
+    assert!(mint_state.mint_authority.is_none());
+    assert_eq!(mint_state.supply, TOTAL_SUPPLY * 10u64.pow(DECIMALS as u32));
+}
+
+#[tokio::test]
+async fn timelock_freeze_needs_a_configured_timelock() {
+    let mut test = TestContext::new().await;
+
+    let (mint, result) = launch_with_timelock_freeze(&mut test, &Pubkey::new_unique()).await;
+
+    assert_eq!(
+        result.map_err(|error| error.unwrap()),
+        Err(TransactionError::InstructionError(0, InstructionError::InvalidArgument))
+    );
+    assert!(test.mint_state(&mint).await.mint_authority.is_some());
+}
+
+// Launches a mint controlled by its mint authority PDA with `InitializeWithPdaAuthority`, passing
+// the bump `adjust_bump` makes of the canonical one, returning the mint and the result
+async fn launch_with_pda_authority(
+    test: &mut TestContext,
+    adjust_bump: fn(u8) -> u8,
+) -> (Pubkey, Result<(), BanksClientError>) {
+    let payer = test.payer().pubkey();
+    let token_program = spl_token::id();
+    let mint = test.create_mint(&token_program, &payer, None).await.pubkey();
+    let (mint_authority, bump) = find_mint_authority_address(&test.program_id, &mint);
+    let instruction = set_authority(
+        &token_program,
+        &mint,
+        Some(&mint_authority),
+        AuthorityType::MintTokens,
+        &payer,
+        &[],
+    )
+    .unwrap();
+    test.process(&[instruction], &[]).await.unwrap();
+    let token_account = test.create_ata(&payer, &mint, &token_program).await;
+    let gate = test.launch_gate().await;
+    let mut instruction = initialize_with_pda_authority(
+        &test.program_id,
+        &gate,
+        &mint,
+        &token_account,
+        &payer,
+        &token_program,
+        None,
+    );
+    let args = PdaAuthorityArgs { bump: adjust_bump(bump), decimals: None };
+    instruction.data = TokenInstruction::InitializeWithPdaAuthority(args).pack();
+    (mint, test.process(&[instruction], &[]).await)
+}
+
+#[tokio::test]
+async fn pda_authority_launch_accepts_the_canonical_bump() {
+    let mut test = TestContext::new().await;
+
+    let (mint, result) = launch_with_pda_authority(&mut test, |bump| bump).await;
+
+    result.unwrap();
+    let mint_state = test.mint_state(&mint).await;
+    assert!(mint_state.mint_authority.is_none());
+    assert_eq!(mint_state.supply, TOTAL_SUPPLY * 10u64.pow(DECIMALS as u32));
+}
+
+#[tokio::test]
+async fn pda_authority_launch_rejects_a_wrong_bump() {
+    let mut test = TestContext::new().await;
+
+    let (mint, result) = launch_with_pda_authority(&mut test, |bump| bump.wrapping_sub(1)).await;
+
+    assert_eq!(
+        result.map_err(|error| error.unwrap()),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(6008 + ERROR_CODE_OFFSET)
+        ))
+    );
+    assert_eq!(test.mint_state(&mint).await.supply, 0);
+}
+
+// Launches `mint` into `token_account` with `InitializeWithUiSupply` for `ui_supply` whole tokens
+async fn launch_ui_supply(
+    test: &mut TestContext,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    ui_supply: u64,
+) -> Result<(), BanksClientError> {
+    let gate = test.launch_gate().await;
+    let args = UiSupplyArgs { ui_supply, decimals: DECIMALS, allow_impractical_decimals: false };
+    let instruction = initialize_with_ui_supply(
+        &test.program_id,
+        &gate,
+        mint,
+        token_account,
+        &test.payer().pubkey(),
+        &spl_token::id(),
+        args,
+    );
+    test.process(&[instruction], &[]).await
+}
+
+#[tokio::test]
+async fn ui_supply_launch_mints_whole_tokens_in_base_units() {
+    let mut test = TestContext::new().await;
+    let (mint, token_account) = setup_mint(&mut test, DECIMALS).await;
+    let ui_supply = 21_000_000;
+
+    launch_ui_supply(&mut test, &mint, &token_account, ui_supply).await.unwrap();
+
+    let supply = ui_supply * 10u64.pow(DECIMALS as u32);
+    let mint_state = test.mint_state(&mint).await;
+    assert_eq!(mint_state.supply, supply);
+    assert!(mint_state.mint_authority.is_none());
+    assert_eq!(test.token_account_state(&token_account).await.amount, supply);
+}
+
+#[tokio::test]
+async fn ui_supply_launch_rejects_a_supply_overflowing_base_units() {
+    let mut test = TestContext::new().await;
+    let (mint, token_account) = setup_mint(&mut test, DECIMALS).await;
+
+    let result = launch_ui_supply(&mut test, &mint, &token_account, u64::MAX).await;
+
+    assert_custom_error(result, CustomError::SupplyOverflow);
+    assert_eq!(test.mint_state(&mint).await.supply, 0);
+}
+
+#[tokio::test]
+async fn revoke_authorities_locks_a_token_2022_mint_with_a_transfer_fee() {
+    let mut test = TestContext::new().await;
+    let authority = test.payer().pubkey();
+    let token_program = spl_token_2022::id();
+    let mint = Keypair::new();
+    let space = token2022_mint_len(&[ExtensionType::TransferFeeConfig]).unwrap();
+    let rent = test.context.banks_client.get_rent().await.unwrap();
+    let instructions = [
+        system_instruction::create_account(
+            &authority,
+            &mint.pubkey(),
+            rent.minimum_balance(space),
+            space as u64,
+            &token_program,
+        ),
+        initialize_transfer_fee_config(
+            &token_program,
+            &mint.pubkey(),
+            Some(&authority),
+            Some(&authority),
+            50,
+            1_000,
+        )
+        .unwrap(),
+        initialize_mint2(&token_program, &mint.pubkey(), &authority, Some(&authority), DECIMALS)
+            .unwrap(),
+    ];
+    test.process(&instructions, &[&mint]).await.unwrap();
+
+    let revocations = [
+        AuthorityType::MintTokens,
+        AuthorityType::FreezeAccount,
+        AuthorityType::TransferFeeConfig,
+    ]
+    .map(|authority_type| (authority_type as u8, authority));
+    let instruction =
+        revoke_authorities(&test.program_id, &mint.pubkey(), &token_program, &revocations);
+    test.process(&[instruction], &[]).await.unwrap();
+
+    let account = test.account(&mint.pubkey()).await.unwrap();
+    let state = StateWithExtensions::<Mint>::unpack(&account.data).unwrap();
+    assert!(state.base.mint_authority.is_none());
+    assert!(state.base.freeze_authority.is_none());
+    let transfer_fee = state.get_extension::<TransferFeeConfig>().unwrap();
+    assert_eq!(Option::<Pubkey>::from(transfer_fee.transfer_fee_config_authority), None);
+}
+
+// Revokes the `authority_type` authority of the payer's `mint` through `RevokeAuthority`
+async fn revoke_with_program(
+    test: &mut TestContext,
+    mint: &Pubkey,
+    authority_type: AuthorityType,
+) -> Result<(), BanksClientError> {
+    let args = RevokeAuthorityArgs { authority_type: authority_type as u8 };
+    let instruction =
+        revoke_authority(&test.program_id, mint, &test.payer().pubkey(), &spl_token::id(), args);
+    test.process(&[instruction], &[]).await
+}
+
+#[tokio::test]
+async fn revoke_authority_revokes_the_mint_and_freeze_authorities_separately() {
+    let mut test = TestContext::new().await;
+    let authority = test.payer().pubkey();
+    let token_program = spl_token::id();
+    let mint = test.create_mint(&token_program, &authority, Some(&authority)).await.pubkey();
+
+    revoke_with_program(&mut test, &mint, AuthorityType::MintTokens).await.unwrap();
+    let mint_state = test.mint_state(&mint).await;
+    assert!(mint_state.mint_authority.is_none());
+    assert_eq!(mint_state.freeze_authority, COption::Some(authority));
+
+    revoke_with_program(&mut test, &mint, AuthorityType::FreezeAccount).await.unwrap();
+    assert!(test.mint_state(&mint).await.freeze_authority.is_none());
+}
+
+#[tokio::test]
+async fn revoke_authority_rejects_other_authority_types() {
+    let mut test = TestContext::new().await;
+    let authority = test.payer().pubkey();
+    let token_program = spl_token::id();
+    let mint = test.create_mint(&token_program, &authority, Some(&authority)).await.pubkey();
+
+    let result = revoke_with_program(&mut test, &mint, AuthorityType::CloseMint).await;
+
+    assert_eq!(
+        result.map_err(|error| error.unwrap()),
+        Err(TransactionError::InstructionError(0, InstructionError::InvalidInstructionData))
+    );
+    let mint_state = test.mint_state(&mint).await;
+    assert_eq!(mint_state.mint_authority, COption::Some(authority));
+    assert_eq!(mint_state.freeze_authority, COption::Some(authority));
+}
@@ -0,0 +1,276 @@
+// Program tests of the launch policy the config PDA applies to every launching instruction
+
+mod common;
+
+use common::{assert_custom_error, TestContext};
+use fixed_supply_token::{
+    client::{
+        add_launcher, initialize_fixed_supply, initialize_with_ui_supply, remove_launcher,
+        update_config, verify_and_finalize,
+    },
+    error::CustomError,
+    instruction::{InitializeArgs, UiSupplyArgs},
+    state::{ConfigSettings, OracleApproval},
+    DECIMALS,
+};
+use solana_program_test::BanksClientError;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, InstructionError},
+    pubkey::Pubkey,
+    signature::Signer,
+    transaction::TransactionError,
+};
+
+// Creates the config with the payer as its admin and applies `settings`
+async fn configure(test: &mut TestContext, settings: ConfigSettings) {
+    let admin = test.payer().pubkey();
+    test.initialize_config(&admin).await;
+    let instruction = update_config(&test.program_id, &admin, settings);
+    test.process(&[instruction], &[]).await.unwrap();
+}
+
+// Settings that only let allowlisted keys launch
+fn allowlist_only() -> ConfigSettings {
+    ConfigSettings { launcher_allowlist_enabled: true, ..ConfigSettings::default() }
+}
+
+// Settings that charge every launch `lamports` for `treasury`
+fn launch_fee(lamports: u64, treasury: Pubkey) -> ConfigSettings {
+    ConfigSettings {
+        launch_fee_lamports: lamports,
+        fee_treasury: treasury,
+        ..ConfigSettings::default()
+    }
+}
+
+// Settings that need `oracle_program`'s approval of every launched mint
+fn oracle_gate(oracle_program: Pubkey) -> ConfigSettings {
+    ConfigSettings { oracle_program, ..ConfigSettings::default() }
+}
+
+// Writes the oracle's verdict on `mint` to a fresh approval account it owns
+fn write_approval(
+    test: &mut TestContext,
+    oracle_program: &Pubkey,
+    mint: &Pubkey,
+    approved: bool,
+) -> Pubkey {
+    let approval = Pubkey::new_unique();
+    let account = Account {
+        lamports: 1_000_000_000,
+        data: borsh::to_vec(&OracleApproval { approved, mint: *mint }).unwrap(),
+        owner: *oracle_program,
+        executable: false,
+        rent_epoch: 0,
+    };
+    test.context.set_account(&approval, &account.into());
+    approval
+}
+
+// Adds `launcher` to the allowlist, signed by the payer as the config admin
+async fn allowlist(test: &mut TestContext, launcher: &Pubkey) {
+    let instruction = add_launcher(&test.program_id, &test.payer().pubkey(), launcher);
+    test.process(&[instruction], &[]).await.unwrap();
+}
+
+// Creates an SPL Token mint controlled by the payer and the payer's token account for it
+async fn setup_mint(test: &mut TestContext) -> (Pubkey, Pubkey) {
+    let authority = test.payer().pubkey();
+    let token_program = spl_token::id();
+    let mint = test.create_mint(&token_program, &authority, None).await.pubkey();
+    let token_account = test.create_ata(&authority, &mint, &token_program).await;
+    (mint, token_account)
+}
+
+// Launches a fresh mint with `InitializeFixedSupply`, returning the mint and the result
+async fn launch(test: &mut TestContext) -> (Pubkey, Result<(), BanksClientError>) {
+    let (mint, token_account) = setup_mint(test).await;
+    let gate = test.launch_gate().await;
+    (mint, launch_with_gate(test, &gate, &mint, &token_account).await)
+}
+
+// Launches `mint` into `token_account` with `InitializeFixedSupply` through `gate`
+async fn launch_with_gate(
+    test: &mut TestContext,
+    gate: &[AccountMeta],
+    mint: &Pubkey,
+    token_account: &Pubkey,
+) -> Result<(), BanksClientError> {
+    let instruction = initialize_fixed_supply(
+        &test.program_id,
+        gate,
+        mint,
+        token_account,
+        &test.payer().pubkey(),
+        &spl_token::id(),
+        None,
+        InitializeArgs::default(),
+        &[],
+    );
+    test.process(&[instruction], &[]).await
+}
+
+#[tokio::test]
+async fn allowlisted_launcher_can_launch() {
+    let mut test = TestContext::new().await;
+    configure(&mut test, allowlist_only()).await;
+    let launcher = test.payer().pubkey();
+    allowlist(&mut test, &launcher).await;
+
+    let (mint, result) = launch(&mut test).await;
+
+    result.unwrap();
+    assert!(test.mint_state(&mint).await.mint_authority.is_none());
+}
+
+#[tokio::test]
+async fn launcher_off_the_allowlist_is_rejected_by_every_launch_path() {
+    let mut test = TestContext::new().await;
+    configure(&mut test, allowlist_only()).await;
+    allowlist(&mut test, &Pubkey::new_unique()).await;
+    let authority = test.payer().pubkey();
+    let token_program = spl_token::id();
+
+    let (mint, result) = launch(&mut test).await;
+    assert_custom_error(result, CustomError::LauncherNotAllowlisted);
+    assert_eq!(test.mint_state(&mint).await.supply, 0);
+
+    let (mint, token_account) = setup_mint(&mut test).await;
+    let gate = test.launch_gate().await;
+    let args =
+        UiSupplyArgs { ui_supply: 1_000, decimals: DECIMALS, allow_impractical_decimals: true };
+    let instruction = initialize_with_ui_supply(
+        &test.program_id,
+        &gate,
+        &mint,
+        &token_account,
+        &authority,
+        &token_program,
+        args,
+    );
+    let result = test.process(&[instruction], &[]).await;
+    assert_custom_error(result, CustomError::LauncherNotAllowlisted);
+
+    let instruction = verify_and_finalize(
+        &test.program_id,
+        &gate,
+        &mint,
+        &token_account,
+        &authority,
+        &token_program,
+        None,
+    );
+    let result = test.process(&[instruction], &[]).await;
+    assert_custom_error(result, CustomError::LauncherNotAllowlisted);
+    assert_eq!(test.mint_state(&mint).await.supply, 0);
+}
+
+#[tokio::test]
+async fn added_and_removed_launchers_apply_to_the_next_launch() {
+    let mut test = TestContext::new().await;
+    configure(&mut test, allowlist_only()).await;
+    let launcher = test.payer().pubkey();
+
+    allowlist(&mut test, &launcher).await;
+    let (_, result) = launch(&mut test).await;
+    result.unwrap();
+
+    let instruction = remove_launcher(&test.program_id, &launcher, &launcher);
+    test.process(&[instruction], &[]).await.unwrap();
+    let (mint, result) = launch(&mut test).await;
+    assert_custom_error(result, CustomError::LauncherNotAllowlisted);
+    assert!(test.mint_state(&mint).await.mint_authority.is_some());
+}
+
+#[tokio::test]
+async fn launch_gate_must_start_with_the_config_pda() {
+    let mut test = TestContext::new().await;
+    configure(&mut test, allowlist_only()).await;
+    let (mint, token_account) = setup_mint(&mut test).await;
+
+    // An empty account in place of the config would otherwise read as "no policy"
+    let gate = [AccountMeta::new_readonly(Pubkey::new_unique(), false)];
+    let result = launch_with_gate(&mut test, &gate, &mint, &token_account).await;
+
+    assert_eq!(
+        result.map_err(|error| error.unwrap()),
+        Err(TransactionError::InstructionError(0, InstructionError::InvalidSeeds))
+    );
+}
+
+#[tokio::test]
+async fn update_config_is_gated_by_the_config_admin() {
+    let mut test = TestContext::new().await;
+    test.initialize_config(&Pubkey::new_unique()).await;
+
+    let instruction = update_config(&test.program_id, &test.payer().pubkey(), allowlist_only());
+    let result = test.process(&[instruction], &[]).await;
+
+    assert_custom_error(result, CustomError::AdminMismatch);
+}
+
+#[tokio::test]
+async fn launch_pays_the_configured_fee_to_the_treasury() {
+    let mut test = TestContext::new().await;
+    let treasury = Pubkey::new_unique();
+    let fee = 100_000_000;
+    configure(&mut test, launch_fee(fee, treasury)).await;
+
+    let (mint, result) = launch(&mut test).await;
+
+    result.unwrap();
+    assert_eq!(test.account(&treasury).await.unwrap().lamports, fee);
+    assert!(test.mint_state(&mint).await.mint_authority.is_none());
+}
+
+#[tokio::test]
+async fn payer_short_of_the_launch_fee_is_rejected() {
+    let mut test = TestContext::new().await;
+    let treasury = Pubkey::new_unique();
+    let payer = test.payer().pubkey();
+    let balance = test.context.banks_client.get_balance(payer).await.unwrap();
+    // More than the payer holds once the transaction fee is paid
+    configure(&mut test, launch_fee(balance, treasury)).await;
+
+    let (mint, result) = launch(&mut test).await;
+
+    assert_custom_error(result, CustomError::InsufficientForLaunchFee);
+    assert!(test.account(&treasury).await.is_none());
+    assert_eq!(test.mint_state(&mint).await.supply, 0);
+}
+
+#[tokio::test]
+async fn launch_needs_the_configured_oracle_approval() {
+    let mut test = TestContext::new().await;
+    let oracle_program = test.deploy_program_stub();
+    configure(&mut test, oracle_gate(oracle_program)).await;
+
+    let (approved_mint, token_account) = setup_mint(&mut test).await;
+    let approval = write_approval(&mut test, &oracle_program, &approved_mint, true);
+    let gate = test.launch_gate_with_approvals(&[approval]).await;
+    launch_with_gate(&mut test, &gate, &approved_mint, &token_account).await.unwrap();
+    assert!(test.mint_state(&approved_mint).await.mint_authority.is_none());
+
+    let (pending_mint, token_account) = setup_mint(&mut test).await;
+    let approval = write_approval(&mut test, &oracle_program, &pending_mint, false);
+    let gate = test.launch_gate_with_approvals(&[approval]).await;
+    let result = launch_with_gate(&mut test, &gate, &pending_mint, &token_account).await;
+    assert_custom_error(result, CustomError::OracleNotApproved);
+    assert_eq!(test.mint_state(&pending_mint).await.supply, 0);
+}
+
+#[tokio::test]
+async fn oracle_program_must_be_deployed() {
+    let mut test = TestContext::new().await;
+    let admin = test.payer().pubkey();
+    test.initialize_config(&admin).await;
+
+    let instruction = update_config(&test.program_id, &admin, oracle_gate(Pubkey::new_unique()));
+    let result = test.process(&[instruction], &[]).await;
+
+    assert_eq!(
+        result.map_err(|error| error.unwrap()),
+        Err(TransactionError::InstructionError(0, InstructionError::InvalidAccountData))
+    );
+}
@@ -0,0 +1,221 @@
+// Program tests against a misbehaving token program
+// The mock is loaded in place of SPL Token and passes every instruction on to the real processor,
+// except for the one its misbehavior mode tampers with, so the program's own re-reads after each
+// CPI are what has to catch it.
+
+mod common;
+
+use common::{assert_custom_error, TestContext};
+use fixed_supply_token::{
+    client::{initialize_fixed_supply, treasury_and_liquidity},
+    error::CustomError,
+    instruction::{InitializeArgs, TreasuryAndLiquidityArgs, CAPABILITY_SKIP_FINAL_VERIFICATION},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    program_option::COption, program_pack::Pack, pubkey::Pubkey,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest};
+use solana_sdk::signature::Signer;
+use spl_token::{
+    instruction::{AuthorityType, TokenInstruction},
+    processor::Processor,
+    state::Mint,
+};
+
+// Reports success for a mint authority revocation without revoking anything
+fn ignore_revocation(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
+    if let Ok(TokenInstruction::SetAuthority {
+        authority_type: AuthorityType::MintTokens,
+        new_authority: COption::None,
+    }) = TokenInstruction::unpack(input)
+    {
+        msg!("Mock token program: ignoring the mint authority revocation");
+        return Ok(());
+    }
+    Processor::process(program_id, accounts, input)
+}
+
+// Fails a mint authority revocation outright
+fn reject_revocation(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
+    if let Ok(TokenInstruction::SetAuthority {
+        authority_type: AuthorityType::MintTokens,
+        new_authority: COption::None,
+    }) = TokenInstruction::unpack(input)
+    {
+        msg!("Mock token program: rejecting the mint authority revocation");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Processor::process(program_id, accounts, input)
+}
+
+// Mints one base unit less than requested
+fn short_mint(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
+    match TokenInstruction::unpack(input) {
+        Ok(TokenInstruction::MintTo { amount }) if amount > 0 => {
+            msg!("Mock token program: minting {} instead of {}", amount - 1, amount);
+            let input = TokenInstruction::MintTo { amount: amount - 1 }.pack();
+            Processor::process(program_id, accounts, &input)
+        }
+        _ => Processor::process(program_id, accounts, input),
+    }
+}
+
+// Credits the destination but leaves the mint's supply short by the minted amount, as a buggy
+// path that gets past the balance check would, so the revocation has to refuse
+fn mint_without_supply(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    Processor::process(program_id, accounts, input)?;
+    if let Ok(TokenInstruction::MintTo { amount }) = TokenInstruction::unpack(input) {
+        msg!("Mock token program: leaving {} base units out of the supply", amount);
+        let mint_account = &accounts[0];
+        let mut mint = Mint::unpack(&mint_account.data.borrow())?;
+        mint.supply -= amount;
+        Mint::pack(mint, &mut mint_account.data.borrow_mut())?;
+    }
+    Ok(())
+}
+
+// Leaves the mint's supply one base unit short of overflowing after every mint, as a token program
+// that doesn't track the supply the way this program expects might
+fn inflate_supply(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
+    Processor::process(program_id, accounts, input)?;
+    if let Ok(TokenInstruction::MintTo { .. }) = TokenInstruction::unpack(input) {
+        msg!("Mock token program: inflating the supply to the u64 boundary");
+        let mint_account = &accounts[0];
+        let mut mint = Mint::unpack(&mint_account.data.borrow())?;
+        mint.supply = u64::MAX - 1;
+        Mint::pack(mint, &mut mint_account.data.borrow_mut())?;
+    }
+    Ok(())
+}
+
+// Launches a fresh SPL Token mint with the mock token program `program_test` loads, returning
+// the launch result
+async fn launch_with_mock_token_program(
+    program_test: ProgramTest,
+) -> (TestContext, Pubkey, Result<(), BanksClientError>) {
+    launch_with_mock_token_program_and_args(program_test, InitializeArgs::default()).await
+}
+
+// Same as `launch_with_mock_token_program` with the given launch arguments
+async fn launch_with_mock_token_program_and_args(
+    program_test: ProgramTest,
+    args: InitializeArgs,
+) -> (TestContext, Pubkey, Result<(), BanksClientError>) {
+    let mut test = TestContext::start(program_test).await;
+
+    let authority = test.payer();
+    let token_program = spl_token::id();
+    let mint = test.create_mint(&token_program, &authority.pubkey(), None).await;
+    let token_account = test.create_ata(&authority.pubkey(), &mint.pubkey(), &token_program).await;
+    let gate = test.launch_gate().await;
+    let instruction = initialize_fixed_supply(
+        &test.program_id,
+        &gate,
+        &mint.pubkey(),
+        &token_account,
+        &authority.pubkey(),
+        &token_program,
+        None,
+        args,
+        &[],
+    );
+    let result = test.process(&[instruction], &[]).await;
+    (test, mint.pubkey(), result)
+}
+
+#[tokio::test]
+async fn ignored_revocation_is_caught_by_the_final_verification() {
+    let mut program_test = TestContext::program_test();
+    program_test.add_program("mock_token", spl_token::id(), processor!(ignore_revocation));
+    let (mut test, mint, result) = launch_with_mock_token_program(program_test).await;
+
+    assert_custom_error(result, CustomError::MintAuthorityNotRevoked);
+    // The failed launch reverted, so the mint is still untouched and can be launched later
+    let mint_state = test.mint_state(&mint).await;
+    assert_eq!(mint_state.supply, 0);
+    assert!(mint_state.mint_authority.is_some());
+}
+
+#[tokio::test]
+async fn failed_revocation_cpi_is_told_apart_from_a_failed_verification() {
+    let mut program_test = TestContext::program_test();
+    program_test.add_program("mock_token", spl_token::id(), processor!(reject_revocation));
+    let (mut test, mint, result) = launch_with_mock_token_program(program_test).await;
+
+    // Unlike an ignored revocation, which only the re-read catches as `MintAuthorityNotRevoked`
+    assert_custom_error(result, CustomError::RevocationCpiFailed);
+    assert!(test.mint_state(&mint).await.mint_authority.is_some());
+}
+
+#[tokio::test]
+async fn skipped_final_verification_trusts_an_ignored_revocation() {
+    let mut program_test = TestContext::program_test();
+    program_test.add_program("mock_token", spl_token::id(), processor!(ignore_revocation));
+    let args = InitializeArgs {
+        capabilities: CAPABILITY_SKIP_FINAL_VERIFICATION,
+        ..InitializeArgs::default()
+    };
+    let (mut test, mint, result) =
+        launch_with_mock_token_program_and_args(program_test, args).await;
+
+    // Without the re-read nothing notices the authority survived, which is the documented cost
+    result.unwrap();
+    let mint_state = test.mint_state(&mint).await;
+    assert!(mint_state.supply > 0);
+    assert!(mint_state.mint_authority.is_some());
+}
+
+#[tokio::test]
+async fn short_mint_is_caught_by_the_balance_delta_check() {
+    let mut program_test = TestContext::program_test();
+    program_test.add_program("mock_token", spl_token::id(), processor!(short_mint));
+    let (mut test, mint, result) = launch_with_mock_token_program(program_test).await;
+
+    assert_custom_error(result, CustomError::UnexpectedBalanceDelta);
+    assert_eq!(test.mint_state(&mint).await.supply, 0);
+}
+
+#[tokio::test]
+async fn incomplete_supply_is_caught_before_the_revocation() {
+    let mut program_test = TestContext::program_test();
+    program_test.add_program("mock_token", spl_token::id(), processor!(mint_without_supply));
+    let (mut test, mint, result) = launch_with_mock_token_program(program_test).await;
+
+    assert_custom_error(result, CustomError::IncompleteSupplyBeforeRevoke);
+    assert!(test.mint_state(&mint).await.mint_authority.is_some());
+}
+
+#[tokio::test]
+async fn supply_near_the_u64_boundary_is_caught_before_the_next_mint() {
+    let mut program_test = TestContext::program_test();
+    program_test.add_program("mock_token", spl_token::id(), processor!(inflate_supply));
+    let mut test = TestContext::start(program_test).await;
+    let authority = test.payer().pubkey();
+    let token_program = spl_token::id();
+    let mint = test.create_mint(&token_program, &authority, None).await.pubkey();
+    let treasury = test.create_ata(&authority, &mint, &token_program).await;
+    let liquidity = test.create_token_account(&authority, &mint, &token_program).await;
+
+    // The treasury tranche leaves the supply at the boundary, so the liquidity tranche can't fit
+    let gate = test.launch_gate().await;
+    let instruction = treasury_and_liquidity(
+        &test.program_id,
+        &gate,
+        &mint,
+        &treasury,
+        &liquidity,
+        &authority,
+        &token_program,
+        TreasuryAndLiquidityArgs { treasury_bps: 5_000, pool: None },
+        &[],
+    );
+    let result = test.process(&[instruction], &[]).await;
+
+    assert_custom_error(result, CustomError::SupplyOverflow);
+    assert_eq!(test.mint_state(&mint).await.supply, 0);
+}
@@ -0,0 +1,125 @@
+// Program tests of the launch receipt and supply cap records
+
+mod common;
+
+use borsh::BorshDeserialize;
+use common::{assert_custom_error, TestContext};
+use fixed_supply_token::{
+    client::{
+        cleanup, get_supply_cap, initialize_fixed_supply, record_supply_cap,
+        verify_receipt_integrity,
+    },
+    error::CustomError,
+    instruction::{InitializeArgs, ReceiptArgs},
+    pda::{find_receipt_address, find_supply_cap_address},
+    state::SupplyCap,
+};
+use solana_sdk::{
+    instruction::InstructionError, pubkey::Pubkey, signature::Signer,
+    transaction::TransactionError,
+};
+
+const NONCE: u64 = 1;
+
+// Launches a fresh SPL Token mint with a receipt and returns the mint and receipt addresses
+async fn launch_with_receipt(test: &mut TestContext) -> (Pubkey, Pubkey) {
+    let authority = test.payer().pubkey();
+    let token_program = spl_token::id();
+    let mint = test.create_mint(&token_program, &authority, None).await.pubkey();
+    let token_account = test.create_ata(&authority, &mint, &token_program).await;
+    let (receipt, bump) = find_receipt_address(&test.program_id, &mint, NONCE);
+
+    let args = InitializeArgs {
+        receipt: Some(ReceiptArgs { nonce: NONCE, bump }),
+        ..InitializeArgs::default()
+    };
+    let gate = test.launch_gate().await;
+    let instruction = initialize_fixed_supply(
+        &test.program_id,
+        &gate,
+        &mint,
+        &token_account,
+        &authority,
+        &token_program,
+        None,
+        args,
+        &[],
+    );
+    test.process(&[instruction], &[]).await.unwrap();
+    (mint, receipt)
+}
+
+#[tokio::test]
+async fn intact_receipt_passes_the_integrity_check() {
+    let mut test = TestContext::new().await;
+    let (mint, _) = launch_with_receipt(&mut test).await;
+
+    let instruction = verify_receipt_integrity(&test.program_id, &mint, NONCE);
+    test.process(&[instruction], &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn corrupted_receipt_fails_the_integrity_check() {
+    let mut test = TestContext::new().await;
+    let (mint, receipt) = launch_with_receipt(&mut test).await;
+
+    // Flip a bit of the recorded supply, right after the tag, mint and creator
+    let mut account = test.account(&receipt).await.unwrap();
+    account.data[1 + 32 + 32] ^= 1;
+    test.context.set_account(&receipt, &account.into());
+
+    let instruction = verify_receipt_integrity(&test.program_id, &mint, NONCE);
+    let result = test.process(&[instruction], &[]).await;
+
+    assert_custom_error(result, CustomError::ReceiptCorrupted);
+}
+
+#[tokio::test]
+async fn supply_cap_records_the_minted_supply_once() {
+    let mut test = TestContext::new().await;
+    let (mint, _) = launch_with_receipt(&mut test).await;
+    let instruction = record_supply_cap(&test.program_id, &mint, &test.payer().pubkey());
+    test.process(&[instruction], &[]).await.unwrap();
+
+    let instruction = get_supply_cap(&test.program_id, &mint);
+    let (result, return_data) = test.simulate(&[instruction], &[]).await;
+    result.unwrap();
+    let cap = SupplyCap::try_from_slice(&return_data).unwrap();
+    let mint_state = test.mint_state(&mint).await;
+    assert_eq!(cap.mint, mint);
+    assert_eq!(cap.supply, mint_state.supply);
+    assert_eq!(cap.decimals, mint_state.decimals);
+
+    // Nobody can record it again, not even with the same value
+    let other_payer = test.funded_keypair().await;
+    let instruction = record_supply_cap(&test.program_id, &mint, &other_payer.pubkey());
+    let result = test.process(&[instruction], &[&other_payer]).await;
+    assert_eq!(
+        result.map_err(|error| error.unwrap()),
+        Err(TransactionError::InstructionError(0, InstructionError::AccountAlreadyInitialized))
+    );
+    let (cap_address, _) = find_supply_cap_address(&test.program_id, &mint);
+    let account = test.account(&cap_address).await.unwrap();
+    assert_eq!(SupplyCap::try_from_slice(&account.data).unwrap(), cap);
+}
+
+#[tokio::test]
+async fn cleanup_keeps_the_receipt_and_supply_cap() {
+    let mut test = TestContext::new().await;
+    let authority = test.payer().pubkey();
+    let (mint, receipt) = launch_with_receipt(&mut test).await;
+    let instruction = record_supply_cap(&test.program_id, &mint, &authority);
+    test.process(&[instruction], &[]).await.unwrap();
+    let (cap, _) = find_supply_cap_address(&test.program_id, &mint);
+
+    for record in [receipt, cap] {
+        let instruction = cleanup(&test.program_id, &mint, &authority, &authority, &[record]);
+        let result = test.process(&[instruction], &[]).await;
+
+        assert_eq!(
+            result.map_err(|error| error.unwrap()),
+            Err(TransactionError::InstructionError(0, InstructionError::InvalidAccountData))
+        );
+        assert_eq!(test.account(&record).await.unwrap().owner, test.program_id);
+    }
+}
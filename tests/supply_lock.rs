@@ -0,0 +1,173 @@
+// Program tests of the supply lock instructions
+
+mod common;
+
+use borsh::BorshDeserialize;
+use common::{assert_custom_error, TestContext};
+use fixed_supply_token::{
+    client::{initialize_with_lock, rescue},
+    error::CustomError,
+    pda::find_supply_lock_address,
+    state::{AccountType, SupplyLock, VestingSchedule},
+    DECIMALS, TOTAL_SUPPLY,
+};
+use solana_program_test::BanksClientError;
+use solana_sdk::{clock::Clock, pubkey::Pubkey, signature::Signer};
+use spl_token_2022::instruction::transfer_checked;
+
+// Base units the launches below lock away
+const LOCK_AMOUNT: u64 = 1_000_000;
+
+// Accounts of a locked launch of a fresh SPL Token mint by the payer
+struct LockedLaunch {
+    mint: Pubkey,
+    treasury: Pubkey,
+    vault: Pubkey,
+}
+
+// Launches a fresh mint with `lock_amount` base units locked in the supply lock's vault for a day
+async fn launch_with_lock(
+    test: &mut TestContext,
+    lock_amount: u64,
+) -> (LockedLaunch, Result<(), BanksClientError>) {
+    let authority = test.payer().pubkey();
+    let token_program = spl_token::id();
+    let mint = test.create_mint(&token_program, &authority, None).await.pubkey();
+    let treasury = test.create_ata(&authority, &mint, &token_program).await;
+    let (lock, _) = find_supply_lock_address(&test.program_id, &mint);
+    let vault = test.create_ata(&lock, &mint, &token_program).await;
+    let clock = test.context.banks_client.get_sysvar::<Clock>().await.unwrap();
+
+    let gate = test.launch_gate().await;
+
+    let instruction = initialize_with_lock(
+        &test.program_id,
+        &gate,
+        &mint,
+        &treasury,
+        &vault,
+        &authority,
+        &token_program,
+        lock_amount,
+        clock.unix_timestamp + 86_400,
+        VestingSchedule::CLIFF,
+        None,
+    );
+    let result = test.process(&[instruction], &[]).await;
+    (LockedLaunch { mint, treasury, vault }, result)
+}
+
+#[tokio::test]
+async fn locked_launch_splits_the_supply_between_treasury_and_vault() {
+    let mut test = TestContext::new().await;
+    let (launch, result) = launch_with_lock(&mut test, LOCK_AMOUNT).await;
+    result.unwrap();
+
+    let supply = TOTAL_SUPPLY * 10u64.pow(DECIMALS as u32);
+    let mint_state = test.mint_state(&launch.mint).await;
+    assert_eq!(mint_state.supply, supply);
+    assert!(mint_state.mint_authority.is_none());
+    let circulating = test.token_account_state(&launch.treasury).await.amount;
+    assert_eq!(circulating, supply - LOCK_AMOUNT);
+    assert_eq!(test.token_account_state(&launch.vault).await.amount, LOCK_AMOUNT);
+
+    let (lock_address, bump) = find_supply_lock_address(&test.program_id, &launch.mint);
+    let account = test.account(&lock_address).await.unwrap();
+    assert_eq!(account.owner, test.program_id);
+    let lock = SupplyLock::try_from_slice(&account.data).unwrap();
+    let clock = test.context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    assert_eq!(lock.account_type, AccountType::SupplyLock);
+    assert_eq!(lock.mint, launch.mint);
+    assert_eq!(lock.vault, launch.vault);
+    assert_eq!(lock.beneficiary, test.payer().pubkey());
+    assert_eq!(lock.circulating_amount, circulating);
+    assert_eq!(lock.locked_amount, LOCK_AMOUNT);
+    assert!(lock.unlock_timestamp > clock.unix_timestamp);
+    assert_eq!(lock.vesting, VestingSchedule::CLIFF);
+    assert_eq!(lock.released_amount, 0);
+    assert_eq!(lock.bump, bump);
+}
+
+#[tokio::test]
+async fn locked_launch_must_leave_part_of_the_supply_circulating() {
+    let mut test = TestContext::new().await;
+    let supply = TOTAL_SUPPLY * 10u64.pow(DECIMALS as u32);
+
+    let (launch, result) = launch_with_lock(&mut test, supply).await;
+
+    assert_custom_error(result, CustomError::CannotLockEntireSupply);
+    assert_eq!(test.mint_state(&launch.mint).await.supply, 0);
+}
+
+#[tokio::test]
+async fn rescue_returns_tokens_sent_to_the_vault_by_mistake() {
+    let mut test = TestContext::new().await;
+    let admin = test.payer();
+    test.initialize_config(&admin.pubkey()).await;
+    let (launch, result) = launch_with_lock(&mut test, LOCK_AMOUNT).await;
+    result.unwrap();
+    let token_program = spl_token::id();
+    // Someone overfunds the escrow vault on top of the locked amount
+    let stray = 5_000;
+    let instruction = transfer_checked(
+        &token_program,
+        &launch.treasury,
+        &launch.mint,
+        &launch.vault,
+        &admin.pubkey(),
+        &[],
+        stray,
+        DECIMALS,
+    )
+    .unwrap();
+    test.process(&[instruction], &[]).await.unwrap();
+    let destination = test.create_ata(&Pubkey::new_unique(), &launch.mint, &token_program).await;
+
+    // The stray tokens can leave, the locked ones can't
+    let program_id = test.program_id;
+    let rescue_amount = |amount| {
+        rescue(
+            &program_id,
+            &launch.mint,
+            &launch.vault,
+            &launch.mint,
+            &destination,
+            &admin.pubkey(),
+            &token_program,
+            amount,
+        )
+    };
+    let too_much = rescue_amount(stray + 1);
+    let result = test.process(&[too_much], &[]).await;
+    assert_custom_error(result, CustomError::RescueExceedsSurplus);
+    let instruction = rescue_amount(stray);
+    test.process(&[instruction], &[]).await.unwrap();
+
+    assert_eq!(test.token_account_state(&destination).await.amount, stray);
+    assert_eq!(test.token_account_state(&launch.vault).await.amount, LOCK_AMOUNT);
+}
+
+#[tokio::test]
+async fn rescue_is_gated_by_the_config_admin() {
+    let mut test = TestContext::new().await;
+    test.initialize_config(&Pubkey::new_unique()).await;
+    let (launch, result) = launch_with_lock(&mut test, LOCK_AMOUNT).await;
+    result.unwrap();
+    let token_program = spl_token::id();
+    let destination = test.create_ata(&Pubkey::new_unique(), &launch.mint, &token_program).await;
+
+    // The payer launched the token but isn't the admin
+    let instruction = rescue(
+        &test.program_id,
+        &launch.mint,
+        &launch.vault,
+        &launch.mint,
+        &destination,
+        &test.payer().pubkey(),
+        &token_program,
+        1,
+    );
+    let result = test.process(&[instruction], &[]).await;
+
+    assert_custom_error(result, CustomError::AdminMismatch);
+}
@@ -0,0 +1,147 @@
+// Program tests of the validation report instructions
+
+mod common;
+
+use borsh::BorshDeserialize;
+use common::TestContext;
+use fixed_supply_token::{
+    client::{validate_only, verify_and_finalize},
+    error::CustomError,
+    instruction::{FinalizeReport, ReportValue, ValidationCheck, ValidationReport},
+    DECIMALS, TOTAL_SUPPLY,
+};
+use solana_sdk::{
+    instruction::InstructionError, pubkey::Pubkey, signature::Signer,
+    transaction::TransactionError,
+};
+use std::slice;
+
+// The transaction error of an instruction failing with `ValidationFailed`
+fn validation_failed() -> TransactionError {
+    TransactionError::InstructionError(
+        0,
+        InstructionError::Custom(CustomError::ValidationFailed.code()),
+    )
+}
+
+#[tokio::test]
+async fn validate_only_reports_every_failed_check() {
+    let mut test = TestContext::new().await;
+    let authority = test.payer().pubkey();
+    let token_program = spl_token::id();
+    // Three things wrong at once: the decimals, a freeze authority and the destination's owner
+    let mint = test
+        .create_mint_with_decimals(&token_program, &authority, Some(&authority), 6)
+        .await
+        .pubkey();
+    let owner = Pubkey::new_unique();
+    let token_account = test.create_ata(&owner, &mint, &token_program).await;
+
+    let instruction =
+        validate_only(&test.program_id, &mint, &token_account, &authority, &token_program, Some(9));
+    let (result, return_data) = test.simulate(&[instruction], &[]).await;
+
+    assert_eq!(result, Err(validation_failed()));
+    let report = ValidationReport::try_from_slice(&return_data).unwrap();
+    let failed: Vec<_> = report.failures.iter().map(|failure| failure.check).collect();
+    let expected = [
+        ValidationCheck::Decimals,
+        ValidationCheck::FreezeAuthority,
+        ValidationCheck::DestinationOwner,
+    ];
+    assert_eq!(failed, expected);
+    assert_eq!(report.failures[0].observed, ReportValue::Number(6));
+    assert_eq!(report.failures[0].expected, ReportValue::Number(9));
+    assert_eq!(report.failures[2].observed, ReportValue::Key(owner));
+    assert!(!report.truncated);
+}
+
+#[tokio::test]
+async fn validate_only_reports_no_failures_for_a_valid_launch() {
+    let mut test = TestContext::new().await;
+    let authority = test.payer().pubkey();
+    let token_program = spl_token::id();
+    let mint = test.create_mint(&token_program, &authority, None).await.pubkey();
+    let token_account = test.create_ata(&authority, &mint, &token_program).await;
+
+    let instruction =
+        validate_only(&test.program_id, &mint, &token_account, &authority, &token_program, None);
+    let (result, return_data) = test.simulate(&[instruction], &[]).await;
+
+    assert_eq!(result, Ok(()));
+    let report = ValidationReport::try_from_slice(&return_data).unwrap();
+    assert_eq!(report, ValidationReport::default());
+}
+
+#[tokio::test]
+async fn verify_and_finalize_launches_and_reports_a_valid_mint() {
+    let mut test = TestContext::new().await;
+    let authority = test.payer().pubkey();
+    let token_program = spl_token::id();
+    let mint = test.create_mint(&token_program, &authority, None).await.pubkey();
+    let token_account = test.create_ata(&authority, &mint, &token_program).await;
+
+    let gate = test.launch_gate().await;
+
+    let instruction = verify_and_finalize(
+        &test.program_id,
+        &gate,
+        &mint,
+        &token_account,
+        &authority,
+        &token_program,
+        None,
+    );
+    let (result, return_data) = test.simulate(slice::from_ref(&instruction), &[]).await;
+
+    assert_eq!(result, Ok(()));
+    let supply = TOTAL_SUPPLY * 10u64.pow(DECIMALS as u32);
+    let expected = FinalizeReport {
+        validation: ValidationReport::default(),
+        supply,
+        decimals: DECIMALS,
+        mint_authority_revoked: true,
+    };
+    assert_eq!(FinalizeReport::try_from_slice(&return_data).unwrap(), expected);
+
+    test.process(&[instruction], &[]).await.unwrap();
+    let mint_state = test.mint_state(&mint).await;
+    assert_eq!(mint_state.supply, supply);
+    assert!(mint_state.mint_authority.is_none());
+    assert_eq!(test.token_account_state(&token_account).await.amount, supply);
+}
+
+#[tokio::test]
+async fn verify_and_finalize_fails_without_side_effects() {
+    let mut test = TestContext::new().await;
+    let authority = test.payer().pubkey();
+    let token_program = spl_token::id();
+    // A freeze authority is left in place, so the launch has to refuse
+    let mint = test.create_mint(&token_program, &authority, Some(&authority)).await.pubkey();
+    let token_account = test.create_ata(&authority, &mint, &token_program).await;
+
+    let gate = test.launch_gate().await;
+
+    let instruction = verify_and_finalize(
+        &test.program_id,
+        &gate,
+        &mint,
+        &token_account,
+        &authority,
+        &token_program,
+        None,
+    );
+    let (result, return_data) = test.simulate(slice::from_ref(&instruction), &[]).await;
+
+    assert_eq!(result, Err(validation_failed()));
+    let report = ValidationReport::try_from_slice(&return_data).unwrap();
+    let failed: Vec<_> = report.failures.iter().map(|failure| failure.check).collect();
+    assert_eq!(failed, [ValidationCheck::FreezeAuthority]);
+
+    let result = test.process(&[instruction], &[]).await;
+    assert_eq!(result.map_err(|error| error.unwrap()), Err(validation_failed()));
+    let mint_state = test.mint_state(&mint).await;
+    assert_eq!(mint_state.supply, 0);
+    assert!(mint_state.mint_authority.is_some());
+    assert_eq!(test.token_account_state(&token_account).await.amount, 0);
+}